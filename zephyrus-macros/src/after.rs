@@ -16,7 +16,7 @@ pub fn after(input: TokenStream2) -> Result<TokenStream2> {
     match sig.inputs.len() {
         c if c != 3 => {
             // This hook is expected to have three arguments, a reference to an `SlashContext`,
-            // a &str indicating the name of the command and the result of a command execution.
+            // a &str indicating the name of the command and the outcome of the command.
             return Err(Error::new(sig.inputs.span(), "Expected three arguments"));
         }
         _ => (),
@@ -35,12 +35,15 @@ pub fn after(input: TokenStream2) -> Result<TokenStream2> {
     crate::util::check_return_type(&sig.output, quote::quote!(()))?;
 
     let (_, ty) = crate::util::get_context_type_and_ident(&sig)?;
+    // The error type of the commands, taken out of the type of the result argument
+    let result = &crate::util::get_pat(sig.inputs.iter().nth(2).unwrap())?.ty;
+    let error = quote::quote!(<#result as ::zephyrus::command::CommandOutput>::Error);
     // Get the futurize macro so we can fit the function into a normal fn pointer
     let futurize = crate::util::get_futurize_macro();
     let path = quote::quote!(::zephyrus::hook::AfterHook);
 
     Ok(quote::quote! {
-        pub fn #ident() -> #path<#ty> {
+        pub fn #ident() -> #path<#ty, #error> {
             #path(#fn_ident)
         }
 
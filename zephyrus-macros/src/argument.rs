@@ -2,7 +2,24 @@ use crate::{attr, util};
 use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
 use syn::spanned::Spanned;
-use syn::{Attribute, Error, FnArg, Result, Type};
+use syn::{
+    Attribute, Error, FnArg, GenericArgument, Lit, Meta, MetaNameValue, NestedMeta, PathArguments,
+    Result, Type,
+};
+
+/// The maximum number of options a command can have.
+const MAX_OPTIONS: usize = 25;
+
+/// The details of a `Vec<T>` argument expanded into numbered options, set using the
+/// `#[varargs(max = 5)]` attribute.
+pub struct Varargs {
+    /// The number of options which are required.
+    pub min: usize,
+    /// The number of options the argument is expanded into.
+    pub max: usize,
+    /// The type of every item.
+    pub item: Type,
+}
 
 /// A command argument, and all its details, skipping the first one, which must be an `SlashContext`
 /// reference.
@@ -20,9 +37,9 @@ pub struct Argument<'a> {
     ///
     /// This macro can be used two ways:
     ///
-    ///     - List way: #[description("Some description")]
+    /// - List way: #[description("Some description")]
     ///
-    ///     - Named value way: #[description = "Some description"]
+    /// - Named value way: #[description = "Some description"]
     ///
     /// e.g.: fn a(#[description = "some here"] arg: String), being the fields inside `description`
     /// this field
@@ -31,6 +48,13 @@ pub struct Argument<'a> {
     /// used to parse the argument and register the command in discord
     pub renaming: Option<String>,
     pub autocomplete: Option<Ident>,
+    /// The message shown instead of the parse error when the argument fails to parse, set using
+    /// the `#[error_message = "..."]` attribute.
+    pub error_message: Option<String>,
+    /// The details of this argument if it's expanded into numbered options.
+    pub varargs: Option<Varargs>,
+    /// Whether the value of this argument is redacted, set using the `#[sensitive]` attribute.
+    pub sensitive: bool,
     trait_type: &'a Type,
 }
 
@@ -41,120 +65,301 @@ impl<'a> Argument<'a> {
         let name = util::get_ident(&pat.pat)?;
         let type_ = pat.ty.clone();
 
-        let mut descriptions = pat
-            .attrs
-            .iter()
-            .map(Self::extract_description)
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
-
-        let mut names = pat
-            .attrs
-            .iter()
-            .map(Self::extract_name)
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
-
-        let mut autocompletes = pat
-            .attrs
-            .iter()
-            .map(Self::extract_autocomplete)
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
-
-        if descriptions.len() > 1 {
+        let mut descriptions = Self::extract_all(&pat.attrs, Self::extract_description)?;
+        let docs = Self::extract_all(&pat.attrs, Self::extract_doc)?;
+        let mut names = Self::extract_all(&pat.attrs, Self::extract_name)?;
+        let mut autocompletes = Self::extract_all(&pat.attrs, Self::extract_autocomplete)?;
+        let mut varargs = Self::extract_all(&pat.attrs, Self::extract_varargs)?;
+        let mut error_messages = Self::extract_all(&pat.attrs, Self::extract_error_message)?;
+        let sensitives = Self::extract_all(&pat.attrs, Self::extract_sensitive)?;
+
+        if let Some((attr, _)) = descriptions.get(1) {
             // We only want a single description attribute
             return Err(Error::new(
-                arg.span(),
+                attr.path.span(),
                 "Only allowed a single description attribute",
             ));
-        } else if descriptions.is_empty() {
-            // Description attribute is required
-            return Err(Error::new(arg.span(), "Description attribute is required"));
+        } else if descriptions.is_empty() && docs.is_empty() {
+            // A description is required, either as an attribute or as doc comments
+            return Err(Error::new(
+                pat.pat.span(),
+                format!(
+                    "Missing description for argument `{}`, add one using `#[description = \"...\"]` or a doc comment",
+                    name
+                ),
+            ));
         }
 
-        if names.len() > 1 {
+        if let Some((attr, _)) = names.get(1) {
             // While this attribute is not required, we only accept a single use of it per parameter
             return Err(Error::new(
-                arg.span(),
+                attr.path.span(),
                 "Only allowed a single name attribute",
             ));
         }
 
-        if autocompletes.len() > 1 {
+        if let Some((attr, _)) = autocompletes.get(1) {
             return Err(Error::new(
-                arg.span(),
+                attr.path.span(),
                 "Only allowed a single autocomplete attribute",
             ));
         }
 
+        if let Some((attr, _)) = error_messages.get(1) {
+            return Err(Error::new(
+                attr.path.span(),
+                "Only allowed a single error_message attribute",
+            ));
+        }
+
+        if let Some((attr, _)) = sensitives.get(1) {
+            return Err(Error::new(
+                attr.path.span(),
+                "Only allowed a single sensitive attribute",
+            ));
+        }
+
+        if let Some((attr, _)) = varargs.get(1) {
+            return Err(Error::new(
+                attr.path.span(),
+                "Only allowed a single varargs attribute",
+            ));
+        }
+
+        let varargs = match varargs.pop() {
+            Some((_, (min, max))) => Some(Varargs {
+                min,
+                max,
+                item: Self::vec_item(&type_)?,
+            }),
+            None => None,
+        };
+
         Ok(Self {
             name,
             ty: type_,
-            description: descriptions.remove(0),
+            description: match descriptions.pop() {
+                Some((_, description)) => description,
+                None => docs
+                    .into_iter()
+                    .map(|(_, line)| line)
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            },
             renaming: if names.is_empty() {
                 None
             } else {
-                Some(names.remove(0))
+                Some(names.remove(0).1)
             },
             autocomplete: if autocompletes.is_empty() {
                 None
             } else {
-                Some(autocompletes.remove(0))
+                Some(autocompletes.remove(0).1)
             },
+            error_message: error_messages.pop().map(|(_, message)| message),
+            varargs,
+            sensitive: !sensitives.is_empty(),
             trait_type,
         })
     }
 
-    /// Executes the given closure into an [attr](crate::attr::Attr)
-    fn exec<F, R>(attr: &Attribute, fun: F) -> Result<R>
+    /// The type parsed out of every option of this argument, being the item type for varargs
+    pub fn parsed_type(&self) -> &Type {
+        match &self.varargs {
+            Some(varargs) => &varargs.item,
+            None => &self.ty,
+        }
+    }
+
+    /// Whether the first option of this argument is required, which is the case of every argument
+    /// but `Option<T>` ones and varargs without required items
+    pub fn starts_required(&self) -> bool {
+        match &self.varargs {
+            Some(varargs) => varargs.min > 0,
+            None => !Self::is_option(&self.ty),
+        }
+    }
+
+    /// Whether any option of this argument is optional, which is the case of `Option<T>`
+    /// arguments and varargs with optional items
+    pub fn has_optional(&self) -> bool {
+        match &self.varargs {
+            Some(varargs) => varargs.min < varargs.max,
+            None => Self::is_option(&self.ty),
+        }
+    }
+
+    /// Whether the given type is an `Option<T>`
+    fn is_option(ty: &Type) -> bool {
+        match ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Option"),
+            _ => false,
+        }
+    }
+
+    /// Extracts the values of all the given attributes using the given function, alongside the
+    /// attribute they come from
+    fn extract_all<F, R>(attrs: &[Attribute], fun: F) -> Result<Vec<(&Attribute, R)>>
+    where
+        F: Fn(&Attribute) -> Result<Option<R>>,
+    {
+        let mut values = Vec::new();
+        for attr in attrs {
+            if let Some(value) = fun(attr)? {
+                values.push((attr, value));
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Executes the given closure into an [attr](crate::attr::Attr) if the given attribute has
+    /// the given name, returning `None` otherwise
+    fn exec<F, R>(attr: &Attribute, name: &str, fun: F) -> Result<Option<R>>
     where
         F: FnOnce(attr::Attr) -> Result<R>,
     {
-        fun(attr::parse_attribute(attr)?)
+        if attr.path.is_ident(name) {
+            fun(attr::parse_attribute(attr)?).map(Some)
+        } else {
+            Ok(None)
+        }
     }
 
     /// Extracts the description from the given attribute, returning `None` if this attribute does
     /// not correspond to the description one
     fn extract_description(attr: &Attribute) -> Result<Option<String>> {
-        Self::exec(attr, |parsed| {
-            if parsed.path.is_ident("description") {
-                Ok(Some(parsed.parse_string()?))
-            } else {
-                Ok(None)
-            }
-        })
+        Self::exec(attr, "description", |parsed| parsed.parse_string())
+    }
+
+    /// Extracts a line of the doc comments from the given attribute, returning `None` if this
+    /// attribute is not a doc comment
+    fn extract_doc(attr: &Attribute) -> Result<Option<String>> {
+        if !attr.path.is_ident("doc") {
+            return Ok(None);
+        }
+
+        match attr.parse_meta()? {
+            Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(lit), ..
+            }) => Ok(Some(lit.value().trim().to_string())),
+            meta => Err(Error::new(meta.span(), "Expected a doc comment")),
+        }
+    }
+
+    /// Extracts the error message from the given attribute, returning `None` if this attribute
+    /// does not correspond to the error message one
+    fn extract_error_message(attr: &Attribute) -> Result<Option<String>> {
+        Self::exec(attr, "error_message", |parsed| parsed.parse_string())
+    }
+
+    /// Extracts the sensitive flag from the given attribute, returning `None` if this attribute
+    /// does not correspond to the sensitive one
+    fn extract_sensitive(attr: &Attribute) -> Result<Option<()>> {
+        if !attr.path.is_ident("sensitive") {
+            return Ok(None);
+        }
+
+        match attr.parse_meta()? {
+            Meta::Path(_) => Ok(Some(())),
+            meta => Err(Error::new(meta.span(), "Expected `#[sensitive]` without arguments")),
+        }
     }
 
     /// Extracts the name from a given attribute, returning `None` if this attribute does not
     /// correspond to the name one
     fn extract_name(attr: &Attribute) -> Result<Option<String>> {
-        Self::exec(attr, |parsed| {
-            if parsed.path.is_ident("rename") {
-                Ok(Some(parsed.parse_string()?))
+        Self::exec(attr, "rename", |parsed| parsed.parse_string())
+    }
+
+    /// Extracts the minimum and maximum number of items from the given attribute, returning
+    /// `None` if this attribute does not correspond to the varargs one
+    fn extract_varargs(attr: &Attribute) -> Result<Option<(usize, usize)>> {
+        if !attr.path.is_ident("varargs") {
+            return Ok(None);
+        }
+
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            _ => {
+                return Err(Error::new(
+                    attr.span(),
+                    "Expected the maximum number of items, like #[varargs(max = 5)]",
+                ))
+            }
+        };
+
+        let (mut min, mut max) = (None, None);
+        for nested in list.nested {
+            let (key, value) = match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) => match &nv.lit {
+                    Lit::Int(int) => (nv.path.clone(), int.base10_parse::<usize>()?),
+                    other => return Err(Error::new(other.span(), "Expected an integer")),
+                },
+                other => return Err(Error::new(other.span(), "Expected `min = ...` or `max = ...`")),
+            };
+
+            if key.is_ident("min") {
+                min = Some(value);
+            } else if key.is_ident("max") {
+                max = Some(value);
             } else {
-                Ok(None)
+                return Err(Error::new(key.span(), "Expected `min` or `max`"));
             }
-        })
+        }
+
+        let max = max.ok_or_else(|| Error::new(attr.span(), "Missing the maximum number of items"))?;
+        let min = min.unwrap_or(1);
+
+        if max == 0 || max > MAX_OPTIONS {
+            return Err(Error::new(
+                attr.span(),
+                format!("The maximum number of items must be between 1 and {}", MAX_OPTIONS),
+            ));
+        }
+        if min > max {
+            return Err(Error::new(
+                attr.span(),
+                "The minimum number of items can't be greater than the maximum",
+            ));
+        }
+
+        Ok(Some((min, max)))
     }
 
-    fn extract_autocomplete(attr: &Attribute) -> Result<Option<Ident>> {
-        Self::exec(attr, |parsed| {
-            if parsed.path.is_ident("autocomplete") {
-                if let Ok(s) = parsed.parse_string() {
-                    return Ok(Some(Ident::new(&s, parsed.span())));
-                }
+    /// Gets the type of the items of the given `Vec<T>` type
+    fn vec_item(ty: &Type) -> Result<Type> {
+        let error = || Error::new(ty.span(), "Varargs arguments must be of type `Vec<T>`");
 
-                Ok(Some(parsed.parse_identifier()?))
-            } else {
-                Ok(None)
+        let segment = match ty {
+            Type::Path(path) => path.path.segments.last().ok_or_else(error)?,
+            _ => return Err(error()),
+        };
+        if segment.ident != "Vec" {
+            return Err(error());
+        }
+
+        match &segment.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+                GenericArgument::Type(item) => Ok(item.clone()),
+                _ => Err(error()),
+            },
+            _ => Err(error()),
+        }
+    }
+
+    fn extract_autocomplete(attr: &Attribute) -> Result<Option<Ident>> {
+        Self::exec(attr, "autocomplete", |parsed| {
+            if let Ok(s) = parsed.parse_string() {
+                return Ok(Ident::new(&s, parsed.span()));
             }
+
+            parsed.parse_identifier()
         })
     }
 }
@@ -164,29 +369,50 @@ impl ToTokens for Argument<'_> {
         let des = &self.description;
         let ty = &self.ty;
         let tt = &self.trait_type;
-        let argument_path = quote::quote!(::zephyrus::argument::CommandArgument);
+        // Spanned at the type, so an error is shown there if it doesn't implement `Parse`
+        let argument_path = quote::quote_spanned!(ty.span()=> ::zephyrus::argument::CommandArgument);
 
         let name = match &self.renaming {
             Some(rename) => rename.clone(),
             None => self.name.to_string(),
         };
 
-        if let Some(autocomplete) = &self.autocomplete {
-            tokens.extend(quote::quote! {
-                .add_argument(#argument_path::<#tt>::new::<#ty>(
-                    #name,
-                    #des,
-                    Some(#autocomplete())
-                ))
-            });
-        } else {
-            tokens.extend(quote::quote! {
-                .add_argument(#argument_path::<#tt>::new::<#ty>(
-                    #name,
-                    #des,
-                    None
-                ))
-            });
+        let autocomplete = match &self.autocomplete {
+            Some(autocomplete) => quote::quote!(Some(#autocomplete())),
+            None => quote::quote!(None),
+        };
+
+        let sensitive = self.sensitive;
+
+        match &self.varargs {
+            Some(Varargs { min, max, item }) => {
+                // Every item is registered as a numbered option, being optional after the minimum
+                for index in 1..=*max {
+                    let name = format!("{}{}", name, index);
+                    let ty = if index <= *min {
+                        quote::quote!(#item)
+                    } else {
+                        quote::quote!(::std::option::Option<#item>)
+                    };
+
+                    tokens.extend(quote::quote! {
+                        .add_argument(#argument_path::<#tt>::new::<#ty>(
+                            #name,
+                            #des,
+                            #autocomplete
+                        ).sensitive(#sensitive))
+                    });
+                }
+            }
+            None => {
+                tokens.extend(quote::quote! {
+                    .add_argument(#argument_path::<#tt>::new::<#ty>(
+                        #name,
+                        #des,
+                        #autocomplete
+                    ).sensitive(#sensitive))
+                });
+            }
         }
     }
 }
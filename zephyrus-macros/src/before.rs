@@ -1,5 +1,5 @@
 use proc_macro2::TokenStream as TokenStream2;
-use syn::{parse2, spanned::Spanned, Error, ItemFn, Result};
+use syn::{parse2, spanned::Spanned, Error, ItemFn, Result, ReturnType};
 
 /// The implementation of before macro, this macro takes the given input, which must be another
 /// function and prepares it to be an before hook, wrapping it in a struct and providing a pointer
@@ -10,7 +10,7 @@ pub fn before(input: TokenStream2) -> Result<TokenStream2> {
         attrs,
         vis,
         mut sig,
-        block,
+        mut block,
     } = fun;
 
     if sig.inputs.len() > 2 {
@@ -27,10 +27,22 @@ pub fn before(input: TokenStream2) -> Result<TokenStream2> {
     let fn_ident = quote::format_ident!("_{}", &ident);
     sig.ident = fn_ident.clone();
     /*
-        Check the return of the function, returning if it does not match, this function is required
-        to return a `bool` indicating if the recognised command should be executed or not
+        Check the return of the function, this function is required to return either a
+        `HookVerdict` or a `bool` indicating if the recognised command should be executed or not,
+        in which case the `bool` is converted into a verdict
     */
-    crate::util::check_return_type(&sig.output, quote::quote!(bool))?;
+    let path = quote::quote!(::zephyrus::hook::HookVerdict);
+    if crate::util::check_return_type(&sig.output, quote::quote!(bool)).is_ok() {
+        sig.output = parse2(quote::quote!(-> #path))?;
+        block = parse2(quote::quote!({
+            #path::from(async move #block.await)
+        }))?;
+    } else if let ReturnType::Default = &sig.output {
+        return Err(Error::new(
+            sig.ident.span(),
+            "Expected `HookVerdict` or `bool` as return type",
+        ));
+    }
 
     let (_, ty) = crate::util::get_context_type_and_ident(&sig)?;
     // Get the futurize macro so we can fit the function into a normal fn pointer
@@ -1,9 +1,14 @@
+use crate::{attr::Attr, details::parse_flag, util};
 use proc_macro2::{Ident, TokenStream as TokenStream2};
-use syn::{parse2, spanned::Spanned, Block, Error, ItemFn, Result, Signature, Type};
+use std::convert::TryFrom;
+use syn::{
+    parse2, spanned::Spanned, Attribute, Block, Error, ItemFn, LitStr, Result, ReturnType,
+    Signature, Type,
+};
 
-/// The implementation of the command macro, this macro modifies the provided function body to allow
-/// parsing all function arguments and wraps it into a command struct, registering all command names,
-/// types and descriptions.
+/// The implementation of the button macro, this macro modifies the provided function body to
+/// parse the placeholders of the custom id template into the function parameters and wraps it
+/// into a button struct, generating also a function to build custom ids matching the template.
 pub fn button(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
     let fun = parse2::<ItemFn>(input)?;
 
@@ -17,44 +22,76 @@ pub fn button(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenStr
     if sig.inputs.is_empty() {
         // The function must have at least one argument, which must be an `SlashContext`
         return Err(Error::new(
-            sig.inputs.span(),
-            "Expected at least SlashContext as a parameter",
+            sig.paren_token.span,
+            "Expected `&SlashContext<T>` as the first parameter",
         ));
     }
 
-    // If we provided a name at macro invocation, use it, if not, use the function's one
-    let name = if macro_attrs.is_empty() {
-        sig.ident.to_string()
+    // If we provided a template at macro invocation, use it, if not, use the function's name
+    let template = if macro_attrs.is_empty() {
+        LitStr::new(&sig.ident.to_string(), sig.ident.span())
     } else {
-        parse2::<syn::LitStr>(macro_attrs)?.value()
+        parse2::<LitStr>(macro_attrs)?
     };
+    let placeholders = parse_template(&template)?;
 
-    /*
-    Set the return type of the function, warning the user if the provided output does not match
-    the required one.
-    */
-    sig.output = parse2(quote::quote!(-> ::zephyrus::prelude::CommandResult))?;
+    let error = match &sig.output {
+        ReturnType::Default => {
+            sig.output = parse2(quote::quote!(-> ::zephyrus::prelude::CommandResult))?;
+            quote::quote!(::zephyrus::command::DefaultError)
+        }
+        ReturnType::Type(_, ty) => {
+            quote::quote!(<#ty as ::zephyrus::command::CommandOutput>::Error)
+        }
+    };
 
     // The name of the function
     let ident = sig.ident.clone();
     // The name the function will have after macro execution
     let fn_ident = quote::format_ident!("_{}", &sig.ident);
     sig.ident = fn_ident.clone();
+    // The name of the function building custom ids
+    let custom_id_ident = quote::format_ident!("{}_custom_id", &ident);
+    // The name of the function building the component of the button
+    let component_ident = quote::format_ident!("{}_component", &ident);
 
-    let (context_ident, context_type) = crate::util::get_context_type_and_ident(&sig)?;
+    let (context_ident, context_type) = util::get_context_type_and_ident(&sig)?;
     // Get the futurize macro so we can fit the function into a normal fn pointer
-    let extract_output = crate::util::get_futurize_macro();
-    let button_path = crate::util::get_button_path();
+    let extract_output = util::get_futurize_macro();
 
-    let args = parse_arguments(&mut sig, &mut block, context_ident, &context_type)?;
-    let opts = crate::options::CommandDetails::parse(&mut attrs)?;
+    let params = parse_placeholders(&mut sig, &mut block, context_ident, &template, &placeholders)?;
+    let opts = parse_details(&mut attrs)?;
+
+    // The template with every placeholder replaced by `{}`, used to build custom ids
+    let format = LitStr::new(
+        &placeholders
+            .iter()
+            .fold(template.value(), |format, name| {
+                format.replace(&format!("{{{}}}", name), "{}")
+            }),
+        template.span(),
+    );
+    let (names, types): (Vec<_>, Vec<_>) = params.iter().cloned().unzip();
+    let ordered = placeholders
+        .iter()
+        .map(|name| &params.iter().find(|(ident, _)| ident == name).unwrap().0);
 
     Ok(quote::quote! {
-        pub fn #ident() -> #button_path<#context_type> {
-            #button_path::new(#fn_ident)
-                .name(#name)
+        pub fn #ident() -> ::zephyrus::button::Button<#context_type, #error> {
+            ::zephyrus::button::Button::new(#template, #fn_ident)
                 #opts
-                #(#args)*
+        }
+
+        /// Builds a custom id handled by this button.
+        #[allow(clippy::too_many_arguments)]
+        #vis fn #custom_id_ident(#(#names: #types),*) -> ::std::string::String {
+            format!(#format, #(#ordered),*)
+        }
+
+        /// Builds the component of this button, using a custom id handled by it.
+        #[allow(clippy::too_many_arguments)]
+        #vis fn #component_ident(#(#names: #types),*) -> ::zephyrus::twilight_exports::Button {
+            #ident().component(#custom_id_ident(#(#names),*))
         }
 
         #[#extract_output]
@@ -63,61 +100,124 @@ pub fn button(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenStr
     })
 }
 
-/// Prepares the given function to parse the required arguments
-pub fn parse_arguments<'a>(
+/// Gets the names of the placeholders of the given template, validating it.
+fn parse_template(template: &LitStr) -> Result<Vec<String>> {
+    let value = template.value();
+    let mut placeholders = Vec::new();
+    let mut rest = value.as_str();
+    let mut previous_placeholder = false;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(Error::new(template.span(), "Unexpected `}` in custom id template"));
+        }
+        if start == 0 && previous_placeholder {
+            return Err(Error::new(
+                template.span(),
+                "Placeholders must be separated by text",
+            ));
+        }
+
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => return Err(Error::new(template.span(), "Unclosed `{` in custom id template")),
+        };
+        let name = &rest[start + 1..end];
+
+        if syn::parse_str::<Ident>(name).is_err() {
+            return Err(Error::new(
+                template.span(),
+                format!("Placeholder `{{{}}}` must be a parameter name", name),
+            ));
+        }
+        if placeholders.iter().any(|p| p == name) {
+            return Err(Error::new(
+                template.span(),
+                format!("Placeholder `{{{}}}` is repeated", name),
+            ));
+        }
+
+        placeholders.push(name.to_string());
+        rest = &rest[end + 1..];
+        previous_placeholder = true;
+    }
+
+    Ok(placeholders)
+}
+
+/// Prepares the given function to parse the placeholders of the template into its parameters,
+/// returning the name and type of every parameter.
+fn parse_placeholders(
     sig: &mut Signature,
     block: &mut Block,
     ctx_ident: Ident,
-    ctx_type: &'a Type,
-) -> Result<Vec<crate::argument::Argument<'a>>> {
-    use crate::argument::Argument;
+    template: &LitStr,
+    placeholders: &[String],
+) -> Result<Vec<(Ident, Type)>> {
+    let mut params = Vec::new();
+
+    for input in sig.inputs.iter().skip(1) {
+        let pat = util::get_pat(input)?;
+        if let Some(attr) = pat.attrs.first() {
+            return Err(Error::new(attr.span(), "Button parameters don't accept attributes"));
+        }
+        let ident = util::get_ident(&pat.pat)?;
 
-    let mut arguments = Vec::new();
+        if !placeholders.iter().any(|name| ident == name) {
+            return Err(Error::new(
+                ident.span(),
+                format!("Parameter `{}` is not a placeholder of the custom id template", ident),
+            ));
+        }
+
+        params.push((ident, (*pat.ty).clone()));
+    }
+
+    if let Some(missing) = placeholders
+        .iter()
+        .find(|name| !params.iter().any(|(ident, _)| ident == *name))
+    {
+        return Err(Error::new(
+            template.span(),
+            format!("Placeholder `{{{}}}` has no matching parameter", missing),
+        ));
+    }
+
+    // The only parameter left in the function is the context
     while sig.inputs.len() > 1 {
-        arguments.push(Argument::new(
-            sig.inputs.pop().unwrap().into_value(),
-            ctx_type,
-        )?);
+        sig.inputs.pop();
     }
 
-    arguments.reverse();
+    if params.is_empty() {
+        return Ok(params);
+    }
 
-    let (names, types, renames) = (
-        arguments.iter().map(|s| &s.name).collect::<Vec<_>>(),
-        arguments.iter().map(|s| &s.ty).collect::<Vec<_>>(),
-        arguments
-            .iter()
-            .map(|s| {
-                if let Some(renaming) = &s.renaming {
-                    renaming.to_owned()
-                } else {
-                    s.name.to_string()
-                }
-            })
-            .collect::<Vec<_>>(),
-    );
-    let parse_trait = crate::util::get_parse_trait();
+    let parsers = params.iter().map(|(ident, ty)| {
+        let index = placeholders.iter().position(|name| ident == name).unwrap();
+        let name = ident.to_string();
+        quote::quote_spanned! {ty.span()=>
+            let #ident: #ty = ::zephyrus::button::parse_placeholder(#name, __values[#index])?;
+        }
+    });
+    let names = params.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
 
     // The original block of the function
     let b = &block;
 
-    // Modify the block to parse arguments
+    // Modify the block to parse the placeholders
     *block = parse2(quote::quote! {{
         let (#(#names),*) = {
-            #[allow(unused_mut)]
-            let mut __options = ::zephyrus::iter::DataIterator::new(
-                vec![]
-            );
-
-            #(let #names: #types =
-                #parse_trait::<#ctx_type>::named_parse(#renames, &#ctx_ident.http_client, &#ctx_ident.data, &mut __options).await?;)*
-
-            if __options.len() > 0 {
-                return Err(
-                    Box::new(::zephyrus::prelude::ParseError::StructureMismatch("Too many arguments received".to_string()))
-                    as Box<dyn std::error::Error + Sync + std::marker::Send>
-                );
-            }
+            let __custom_id = match #ctx_ident.interaction.data.as_ref().unwrap() {
+                ::zephyrus::twilight_exports::InteractionData::MessageComponent(data) => data.custom_id.as_str(),
+                _ => unreachable!()
+            };
+            let __values = ::zephyrus::button::CustomIdTemplate::new(#template)
+                .extract(__custom_id)
+                .ok_or_else(|| ::zephyrus::prelude::ParseError::StructureMismatch(
+                    "Custom id does not match the template".to_string()
+                ))?;
+
+            #(#parsers)*
 
             (#(#names),*)
         };
@@ -125,5 +225,61 @@ pub fn parse_arguments<'a>(
         #b
     }})?;
 
-    Ok(arguments)
+    Ok(params)
+}
+
+/// Parses the attributes allowed in buttons, removing them from the given list.
+fn parse_details(attrs: &mut Vec<Attribute>) -> Result<TokenStream2> {
+    let mut tokens = TokenStream2::new();
+    let mut i = 0;
+
+    while i < attrs.len() {
+        let attr = &attrs[i];
+
+        match attr.path.get_ident().map(|ident| ident.to_string()).as_deref() {
+            Some("required_permissions") => {
+                let permissions = Attr::try_from(attr)?.parse_all()?;
+                let permissions = permissions.iter().map(|permission| {
+                    quote::quote!(::zephyrus::twilight_exports::Permissions::#permission)
+                });
+                tokens.extend(quote::quote!(.required_permissions(#(#permissions)|*)));
+            }
+            Some("style") => {
+                let style = Attr::try_from(attr)?.parse_identifier()?;
+                tokens.extend(quote::quote!(.style(::zephyrus::twilight_exports::ButtonStyle::#style)));
+            }
+            Some("label") => {
+                let label = Attr::try_from(attr)?.parse_string()?;
+                tokens.extend(quote::quote!(.label(#label)));
+            }
+            Some("emoji") => {
+                let emoji = Attr::try_from(attr)?.parse_string()?;
+                tokens.extend(quote::quote! {
+                    .emoji(::zephyrus::twilight_exports::EmojiReactionType::Unicode {
+                        name: ::std::string::String::from(#emoji)
+                    })
+                });
+            }
+            Some("disabled") => {
+                let disabled = parse_flag(attr)?;
+                tokens.extend(quote::quote!(.disabled(#disabled)));
+            }
+            Some("ephemeral") => {
+                let ephemeral = parse_flag(attr)?;
+                tokens.extend(quote::quote!(.ephemeral(#ephemeral)));
+            }
+            Some("checks") => {
+                let checks = Attr::try_from(attr)?.parse_all()?;
+                tokens.extend(quote::quote!(.checks(vec![#(#checks()),*])));
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+
+        attrs.remove(i);
+    }
+
+    Ok(tokens)
 }
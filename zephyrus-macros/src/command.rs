@@ -1,11 +1,22 @@
 use proc_macro2::{Ident, TokenStream as TokenStream2};
-use syn::{parse2, spanned::Spanned, Block, Error, ItemFn, Result, Signature, Type};
+use syn::{parse2, spanned::Spanned, Block, Error, ItemFn, Result, ReturnType, Signature, Type};
 use crate::{argument::Argument, details::CommandDetails, util};
 
 /// The implementation of the command macro, this macro modifies the provided function body to allow
 /// parsing all function arguments and wraps it into a command struct, registering all command names,
 /// types and descriptions.
 pub fn command(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    expand(macro_attrs, input, true)
+}
+
+/// Same as [command], but keeps the modified function `async` instead of boxing its future, so
+/// it can be called directly by the code generated for static dispatch, boxing it only inside of
+/// the function given to the command.
+pub fn static_command(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    expand(macro_attrs, input, false)
+}
+
+fn expand(macro_attrs: TokenStream2, input: TokenStream2, boxed: bool) -> Result<TokenStream2> {
     let fun = parse2::<ItemFn>(input)?;
 
     let ItemFn {
@@ -18,8 +29,8 @@ pub fn command(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenSt
     if sig.inputs.is_empty() {
         // The function must have at least one argument, which must be an `SlashContext`
         return Err(Error::new(
-            sig.inputs.span(),
-            "Expected at least SlashContext as a parameter",
+            sig.paren_token.span,
+            "Expected `&SlashContext<T>` as the first parameter",
         ));
     }
 
@@ -31,10 +42,18 @@ pub fn command(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenSt
     };
 
     /*
-    Set the return type of the function, warning the user if the provided output does not match
-    the required one.
+    Get the error type of the command out of its return type, using the default one if no return
+    type is provided.
     */
-    sig.output = parse2(quote::quote!(-> ::zephyrus::prelude::CommandResult))?;
+    let error = match &sig.output {
+        ReturnType::Default => {
+            sig.output = parse2(quote::quote!(-> ::zephyrus::prelude::CommandResult))?;
+            quote::quote!(::zephyrus::command::DefaultError)
+        }
+        ReturnType::Type(_, ty) => {
+            quote::quote!(<#ty as ::zephyrus::command::CommandOutput>::Error)
+        }
+    };
 
     // The name of the function
     let ident = sig.ident.clone();
@@ -43,16 +62,80 @@ pub fn command(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenSt
     sig.ident = fn_ident.clone();
 
     let (context_ident, context_type) = util::get_context_type_and_ident(&sig)?;
+
+    /*
+    Commands can be generic over the data type, in which case it must satisfy the bounds the
+    framework requires for it.
+    */
+    let generic_context = sig
+        .generics
+        .type_params()
+        .any(|param| matches!(&context_type, Type::Path(p) if p.path.is_ident(&param.ident)));
+    if generic_context {
+        sig.generics
+            .make_where_clause()
+            .predicates
+            .push(parse2(quote::quote!(#context_type: Send + Sync + 'static))?);
+    }
+
+    let args = parse_arguments(&mut sig, &mut block, context_ident, &context_type)?;
+
+    /*
+    Requiring the arguments to be parseable in the signatures makes a missing `Parse`
+    implementation a single error at the type, instead of one for every use of the argument.
+    */
+    for argument in &args {
+        let ty = argument.parsed_type();
+        let bound = quote::quote_spanned!(ty.span()=> #ty: ::zephyrus::parse::Parse<#context_type>);
+        sig.generics.make_where_clause().predicates.push(parse2(bound)?);
+    }
+
+    let generics = sig.generics.clone();
+    let type_params = generics.type_params().map(|param| &param.ident).collect::<Vec<_>>();
+    let turbofish = if type_params.is_empty() {
+        quote::quote!()
+    } else {
+        quote::quote!(::<#(#type_params),*>)
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
     // Get the futurize macro so we can fit the function into a normal fn pointer
     let extract_output = util::get_futurize_macro();
     let command_path = util::get_command_path();
 
-    let args = parse_arguments(&mut sig, &mut block, context_ident, &context_type)?;
-    let opts = CommandDetails::parse(&mut attrs)?;
+    let opts = CommandDetails::parse(&mut attrs, &ident)?;
+
+    if !boxed {
+        if !generics.params.is_empty() {
+            return Err(Error::new(
+                generics.span(),
+                "Statically dispatched commands can't be generic",
+            ));
+        }
+
+        return Ok(quote::quote! {
+            pub fn #ident () -> #command_path<#context_type, #error> {
+                fn __boxed<'a>(
+                    ctx: &'a ::zephyrus::context::SlashContext<'a, #context_type>
+                ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<
+                    Output = ::zephyrus::command::CommandResult<#error>
+                > + 'a + Send>> {
+                    Box::pin(#fn_ident(ctx))
+                }
+
+                #command_path::new(__boxed)
+                    .name(#name)
+                    #opts
+                    #(#args)*
+            }
+
+            #(#attrs)*
+            #vis #sig #block
+        });
+    }
 
     Ok(quote::quote! {
-        pub fn #ident() -> #command_path<#context_type> {
-            #command_path::new(#fn_ident)
+        pub fn #ident #impl_generics () -> #command_path<#context_type, #error> #where_clause {
+            #command_path::new(#fn_ident #turbofish)
                 .name(#name)
                 #opts
                 #(#args)*
@@ -64,6 +147,29 @@ pub fn command(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenSt
     })
 }
 
+/// Checks that no required argument is declared after an optional one, as discord requires
+/// required options to come first
+fn check_order(arguments: &[Argument<'_>]) -> Result<()> {
+    let mut optional = None;
+    for argument in arguments {
+        if let (Some(optional), true) = (optional, argument.starts_required()) {
+            return Err(Error::new(
+                argument.ty.span(),
+                format!(
+                    "Required argument `{}` must be declared before optional argument `{}`",
+                    argument.name, optional
+                ),
+            ));
+        }
+
+        if optional.is_none() && argument.has_optional() {
+            optional = Some(&argument.name);
+        }
+    }
+
+    Ok(())
+}
+
 /// Prepares the given function to parse the required arguments
 pub fn parse_arguments<'a>(
     sig: &mut Signature,
@@ -80,10 +186,10 @@ pub fn parse_arguments<'a>(
     }
 
     arguments.reverse();
+    check_order(&arguments)?;
 
-    let (names, types, renames) = (
+    let (names, renames) = (
         arguments.iter().map(|s| &s.name).collect::<Vec<_>>(),
-        arguments.iter().map(|s| &s.ty).collect::<Vec<_>>(),
         arguments
             .iter()
             .map(|s| {
@@ -96,6 +202,32 @@ pub fn parse_arguments<'a>(
             .collect::<Vec<_>>(),
     );
 
+    // Spanned at the types, so errors such as a type not implementing `Parse` point to them
+    let parsers = arguments.iter().zip(renames.iter()).map(|(arg, rename)| {
+        let (name, ty) = (&arg.name, &arg.ty);
+        // The custom error message, if any, replaces the message of the parse error
+        let message = arg.error_message.as_ref().map(|message| {
+            quote::quote!(.map_err(|why| why.with_message(#rename, #message)))
+        });
+        match &arg.varargs {
+            Some(varargs) => {
+                let (min, max, item) = (varargs.min, varargs.max, &varargs.item);
+                quote::quote_spanned! {ty.span()=>
+                    let #name: #ty = #ctx_ident
+                        .parse_varargs::<#item>(#rename, #min, #max, &mut __options)
+                        .await
+                        #message?;
+                }
+            }
+            None => quote::quote_spanned! {ty.span()=>
+                let #name: #ty = #ctx_ident
+                    .named_parse::<#ty>(#rename, &mut __options)
+                    .await
+                    #message?;
+            },
+        }
+    });
+
     // The original block of the function
     let b = &block;
 
@@ -107,21 +239,14 @@ pub fn parse_arguments<'a>(
                 _ => unreachable!()
             };
             #[allow(unused_mut)]
-            let mut __options = ::zephyrus::iter::DataIterator::new(
-                data
-                .options
-                .iter()
-                .collect::<Vec<_>>()
-            );
-
-            #(let #names: #types =
-                #ctx_ident.named_parse(#renames, &mut __options).await?;)*
-
-            if __options.len() > 0 {
-                return Err(
-                    Box::new(::zephyrus::prelude::ParseError::StructureMismatch("Too many arguments received".to_string()))
-                    as Box<dyn std::error::Error + Sync + std::marker::Send>
-                );
+            let mut __options = ::zephyrus::iter::OptionMap::new(&data.options);
+
+            #(#parsers)*
+
+            if !__options.is_empty() {
+                return Err(::std::convert::From::from(
+                    ::zephyrus::prelude::ParseError::StructureMismatch("Too many arguments received".to_string())
+                ));
             }
 
             (#(#names),*)
@@ -0,0 +1,262 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse2, spanned::Spanned, Attribute, Error, FnArg, Ident, ImplItem, ImplItemMethod, ItemImpl,
+    GenericArgument, LitStr, PathArguments, Result, ReturnType, Type,
+};
+use crate::util;
+
+/// The implementation of the commands macro, this macro turns every `#[command]` method of the
+/// given impl block into a command which calls the method on the shared data, implementing
+/// `CommandSet` for the type with all of them.
+///
+/// Given `static_dispatch`, the type also implements `StaticDispatch`, calling the command
+/// functions directly instead of through their boxed futures.
+pub fn commands(macro_attrs: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    let static_dispatch = if macro_attrs.is_empty() {
+        false
+    } else {
+        let ident = parse2::<Ident>(macro_attrs)?;
+        if ident != "static_dispatch" {
+            return Err(Error::new(ident.span(), "Expected `static_dispatch`"));
+        }
+        true
+    };
+
+    let mut item = parse2::<ItemImpl>(input)?;
+
+    if !item.generics.params.is_empty() || item.trait_.is_some() {
+        return Err(Error::new(
+            item.impl_token.span(),
+            "#[commands] can only be used on non generic inherent impl blocks",
+        ));
+    }
+
+    let self_ty = item.self_ty.clone();
+    let mut wrappers = Vec::new();
+    let mut builders = Vec::new();
+    let mut dispatched = Vec::new();
+    let mut set_types = None;
+
+    for impl_item in item.items.iter_mut() {
+        let method = match impl_item {
+            ImplItem::Method(method) => method,
+            _ => continue,
+        };
+
+        let position = match method.attrs.iter().position(is_command_attr) {
+            Some(position) => position,
+            None => continue,
+        };
+
+        let command_attr = method.attrs.remove(position);
+        let name = if command_attr.tokens.is_empty() {
+            method.sig.ident.to_string()
+        } else {
+            command_attr.parse_args::<LitStr>()?.value()
+        };
+
+        // The attributes of the command are moved to the wrapper, leaving only the docs
+        let attrs = std::mem::take(&mut method.attrs);
+        let (docs, attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|a| a.path.is_ident("doc"));
+        method.attrs = docs;
+
+        let wrapper_ident = quote::format_ident!(
+            "__{}_{}",
+            type_name(&self_ty).to_lowercase(),
+            method.sig.ident
+        );
+        let (wrapper, context_type) = wrapper(method, &self_ty, &wrapper_ident)?;
+
+        let error = match &method.sig.output {
+            ReturnType::Default => quote::quote!(::zephyrus::command::DefaultError),
+            ReturnType::Type(_, ty) => {
+                quote::quote!(<#ty as ::zephyrus::command::CommandOutput>::Error)
+            }
+        };
+        set_types.get_or_insert((context_type, error));
+
+        let wrapper = quote::quote! {
+            #(#attrs)*
+            #wrapper
+        };
+        let command = if static_dispatch {
+            dispatched.push((name.clone(), quote::format_ident!("_{}", wrapper_ident)));
+            crate::command::static_command(quote::quote!(#name), wrapper)?
+        } else {
+            crate::command::command(quote::quote!(#name), wrapper)?
+        };
+
+        wrappers.push(command);
+        builders.push(wrapper_ident);
+    }
+
+    let (context_type, error) = match set_types {
+        Some(types) => types,
+        None => {
+            return Err(Error::new(
+                item.self_ty.span(),
+                "#[commands] requires at least one method marked with #[command]",
+            ))
+        }
+    };
+
+    let dispatch = static_dispatch.then(|| dispatch_impl(&self_ty, &context_type, &error, &dispatched));
+
+    Ok(quote::quote! {
+        #item
+
+        #(
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            #wrappers
+        )*
+
+        impl ::zephyrus::command::CommandSet<#context_type, #error> for #self_ty {
+            fn commands() -> ::std::vec::Vec<::zephyrus::command::Command<#context_type, #error>> {
+                ::std::vec![#(#builders()),*]
+            }
+        }
+
+        #dispatch
+    })
+}
+
+/// Implements `StaticDispatch` for the given type, matching the invoked path against the name of
+/// every command and nesting the futures into `Either`s, so the returned future has a single
+/// type known at compile time. Other commands are executed through their boxed function.
+fn dispatch_impl(
+    self_ty: &Type,
+    context_type: &Type,
+    error: &TokenStream2,
+    commands: &[(String, Ident)],
+) -> TokenStream2 {
+    let either = quote::quote!(::zephyrus::dispatch::Either);
+    let nest = |position: usize, future: TokenStream2| {
+        (0..position).fold(
+            if position == commands.len() {
+                future
+            } else {
+                quote::quote!(#either::left(#future))
+            },
+            |future, _| quote::quote!(#either::right(#future)),
+        )
+    };
+
+    let arms = commands.iter().enumerate().map(|(position, (name, ident))| {
+        let future = nest(position, quote::quote!(#ident(ctx)));
+        quote::quote!(#name => #future,)
+    });
+    let fallback = nest(commands.len(), quote::quote!((command.fun)(ctx)));
+
+    quote::quote! {
+        impl ::zephyrus::dispatch::StaticDispatch<#context_type, #error> for #self_ty {
+            fn execute<'a>(
+                command: &'a ::zephyrus::command::Command<#context_type, #error>,
+                path: &'a str,
+                ctx: &'a ::zephyrus::context::SlashContext<'a, #context_type>,
+            ) -> impl ::std::future::Future<
+                Output = ::zephyrus::command::CommandResult<#error>
+            > + Send + 'a {
+                match path {
+                    #(#arms)*
+                    _ => #fallback,
+                }
+            }
+        }
+    }
+}
+
+/// Checks whether the given attribute is the `#[command]` one.
+fn is_command_attr(attr: &Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .map(|segment| segment.ident == "command")
+        .unwrap_or(false)
+}
+
+/// Gets the name of the given type, used to name the generated functions.
+fn type_name(ty: &Type) -> String {
+    match util::get_path(ty) {
+        Ok(path) => path.segments.last().unwrap().ident.to_string(),
+        Err(_) => String::from("commands"),
+    }
+}
+
+/// Creates a free function calling the given method on the data of the context, moving the
+/// argument attributes of the method to it.
+fn wrapper(
+    method: &mut ImplItemMethod,
+    self_ty: &Type,
+    ident: &syn::Ident,
+) -> Result<(TokenStream2, Type)> {
+    let mut inputs = method.sig.inputs.iter_mut();
+
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_none() => (),
+        _ => {
+            return Err(Error::new(
+                method.sig.inputs.span(),
+                "#[command] methods must take `&self` as their first parameter",
+            ))
+        }
+    }
+
+    let mut params = Vec::new();
+    let mut idents = Vec::new();
+    for input in inputs {
+        let pat = util::get_pat_mut(input)?;
+        params.push(pat.clone());
+        idents.push(util::get_ident(&pat.pat)?);
+        pat.attrs.clear();
+
+        if idents.len() == 1 {
+            // `async` methods don't allow eliding the lifetime of the context
+            add_context_lifetime(&mut pat.ty)?;
+        }
+    }
+
+    if params.is_empty() {
+        return Err(Error::new(
+            method.sig.paren_token.span,
+            "Expected `&SlashContext<T>` as the first parameter",
+        ));
+    }
+
+    let mut sig = method.sig.clone();
+    sig.ident = ident.clone();
+    sig.inputs = params.into_iter().map(FnArg::Typed).collect();
+    let (context_ident, context_type) = util::get_context_type_and_ident(&sig)?;
+
+    let method_ident = &method.sig.ident;
+    let arguments = idents.iter().skip(1);
+
+    Ok((
+        quote::quote! {
+            #sig {
+                ::std::borrow::Borrow::<#self_ty>::borrow(#context_ident.data)
+                    .#method_ident(#context_ident, #(#arguments),*)
+                    .await
+            }
+        },
+        context_type,
+    ))
+}
+
+/// Adds an anonymous lifetime to the given context type if it doesn't have one.
+fn add_context_lifetime(ty: &mut Type) -> Result<()> {
+    let path = util::get_path_mut(ty)?;
+
+    if let PathArguments::AngleBracketed(arguments) = &mut path.segments.last_mut().unwrap().arguments {
+        let has_lifetime = arguments
+            .args
+            .iter()
+            .any(|argument| matches!(argument, GenericArgument::Lifetime(_)));
+
+        if !has_lifetime {
+            arguments.args.insert(0, syn::parse_quote!('_));
+        }
+    }
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use crate::attr::*;
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::ToTokens;
 use std::convert::TryFrom;
-use syn::{spanned::Spanned, Attribute, Error, Result};
+use syn::{spanned::Spanned, Attribute, Error, Lit, LitInt, Meta, NestedMeta, Result};
 
 #[derive(Default)]
 /// The details of a given command
@@ -10,11 +10,25 @@ pub struct CommandDetails {
     /// The description of this command
     pub description: String,
     pub required_permissions: Option<Vec<Ident>>,
-    pub checks: Vec<Ident>
+    pub checks: Vec<Ident>,
+    /// The category of this command, used only as metadata
+    pub category: Option<String>,
+    /// Arbitrary key-value pairs attached to the command
+    pub meta: Vec<(String, String)>,
+    /// The maximum concurrent executions of the command and the scope they apply to
+    pub max_concurrency: Option<(LitInt, Ident)>,
+    /// The seconds the command can't be used again and the scope they apply to
+    pub cooldown: Option<(LitInt, Ident)>,
+    /// Whether the responses of the command are ephemeral by default
+    pub ephemeral: bool,
+    /// The sku the invoker of the command must have an entitlement to
+    pub premium_sku: Option<LitInt>,
 }
 
 impl CommandDetails {
-    pub fn parse(attrs: &mut Vec<Attribute>) -> Result<Self> {
+    /// Parses the details of the command with the given name out of its attributes, removing
+    /// them from the given list
+    pub fn parse(attrs: &mut Vec<Attribute>, ident: &Ident) -> Result<Self> {
         let mut s = Self::default();
 
         let i = 0;
@@ -43,6 +57,51 @@ impl CommandDetails {
                     let checks = attr.parse_all()?;
                     s.checks = checks;
                 }
+                "category" => {
+                    if s.category.is_some() {
+                        return Err(Error::new(attr.span(), "Category already set"));
+                    }
+
+                    s.category = Some(Attr::try_from(attr)?.parse_string()?);
+                }
+                "meta" => {
+                    for (key, value) in parse_meta(attr)? {
+                        if s.meta.iter().any(|(k, _)| *k == key) {
+                            return Err(Error::new(attr.span(), format!("Meta key `{}` already set", key)));
+                        }
+                        s.meta.push((key, value));
+                    }
+                }
+                "max_concurrency" => {
+                    if s.max_concurrency.is_some() {
+                        return Err(Error::new(attr.span(), "Max concurrency already set"));
+                    }
+
+                    s.max_concurrency = Some(parse_scoped(attr, "Expected the maximum concurrent executions")?);
+                }
+                "cooldown" => {
+                    if s.cooldown.is_some() {
+                        return Err(Error::new(attr.span(), "Cooldown already set"));
+                    }
+
+                    s.cooldown = Some(parse_scoped(attr, "Expected the seconds of the cooldown")?);
+                }
+                "ephemeral" => {
+                    s.ephemeral = parse_flag(attr)?;
+                }
+                "premium_required" => {
+                    if s.premium_sku.is_some() {
+                        return Err(Error::new(attr.span(), "Premium sku already set"));
+                    }
+
+                    s.premium_sku = Some(Attr::try_from(attr)?.parse_value(|value| match value {
+                        Value::Lit(Lit::Int(sku)) => match sku.base10_parse::<u64>()? {
+                            0 => Err(Error::new(sku.span(), "The id of the sku can't be zero")),
+                            _ => Ok(sku.clone()),
+                        },
+                        other => Err(Error::new(other.span(), "Expected the id of the sku")),
+                    })?);
+                }
                 _ => return Err(Error::new(attr.span(), "Attribute not recognized")),
             }
 
@@ -51,8 +110,11 @@ impl CommandDetails {
 
         if s.description.is_empty() {
             return Err(Error::new(
-                proc_macro2::Span::call_site(),
-                "Description is required",
+                ident.span(),
+                format!(
+                    "Missing description for command `{}`, add one using `#[description = \"...\"]`",
+                    ident
+                ),
             ));
         }
 
@@ -60,6 +122,83 @@ impl CommandDetails {
     }
 }
 
+/// Parses an attribute which can be used either as `#[flag]` or as `#[flag(true)]`
+pub fn parse_flag(attr: &Attribute) -> Result<bool> {
+    let a = Attr::try_from(attr)?;
+    if a.values.is_empty() {
+        Ok(true)
+    } else {
+        a.parse_bool()
+    }
+}
+
+/// Parses an attribute like `#[max_concurrency(1, guild)]` or `#[cooldown(5, user)]` into its
+/// value and the scope it applies to, using the global scope if none is given
+fn parse_scoped(attr: &Attribute, expected: &str) -> Result<(LitInt, Ident)> {
+    let a = Attr::try_from(attr)?;
+    let mut values = a.values.iter();
+
+    let max = match values.next() {
+        Some(Value::Lit(Lit::Int(max))) => max.clone(),
+        _ => return Err(Error::new(attr.span(), expected)),
+    };
+
+    let scope = match values.next() {
+        None => Ident::new("Global", attr.span()),
+        Some(Value::Ident(scope)) => match scope.to_string().as_str() {
+            "global" => Ident::new("Global", scope.span()),
+            "guild" => Ident::new("Guild", scope.span()),
+            "channel" => Ident::new("Channel", scope.span()),
+            "user" => Ident::new("User", scope.span()),
+            _ => {
+                return Err(Error::new(
+                    scope.span(),
+                    "Scope must be one of `global`, `guild`, `channel` or `user`",
+                ))
+            }
+        },
+        Some(other) => return Err(Error::new(other.span(), "Expected a scope identifier")),
+    };
+
+    if let Some(other) = values.next() {
+        return Err(Error::new(other.span(), "Expected at most two arguments"));
+    }
+
+    Ok((max, scope))
+}
+
+/// Parses a `#[meta(key = "value", ...)]` attribute into its key-value pairs
+fn parse_meta(attr: &Attribute) -> Result<Vec<(String, String)>> {
+    let list = match attr.parse_meta()? {
+        Meta::List(list) => list,
+        other => {
+            return Err(Error::new(
+                other.span(),
+                "Expected a list of key-value pairs, like #[meta(key = \"value\")]",
+            ))
+        }
+    };
+
+    list.nested
+        .into_iter()
+        .map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(nv)) => {
+                let key = nv
+                    .path
+                    .get_ident()
+                    .ok_or_else(|| Error::new(nv.path.span(), "Meta key must be an identifier"))?
+                    .to_string();
+
+                match nv.lit {
+                    Lit::Str(value) => Ok((key, value.value())),
+                    other => Err(Error::new(other.span(), "Meta value must be a string")),
+                }
+            }
+            other => Err(Error::new(other.span(), "Expected a key-value pair")),
+        })
+        .collect()
+}
+
 impl ToTokens for CommandDetails {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let d = &self.description;
@@ -85,7 +224,43 @@ impl ToTokens for CommandDetails {
         let checks = &self.checks;
 
         tokens.extend(quote::quote! {
-            .checks(vec![#(#checks),*])
+            .checks(vec![#(#checks()),*])
         });
+
+        if let Some(category) = &self.category {
+            tokens.extend(quote::quote!(.category(#category)));
+        }
+
+        if self.ephemeral {
+            tokens.extend(quote::quote!(.ephemeral(true)));
+        }
+
+        if let Some(sku) = &self.premium_sku {
+            tokens.extend(quote::quote! {
+                .premium_required(::zephyrus::twilight_exports::Id::new(#sku))
+            });
+        }
+
+        for (key, value) in &self.meta {
+            tokens.extend(quote::quote!(.meta(#key, #value)));
+        }
+
+        if let Some((max, scope)) = &self.max_concurrency {
+            tokens.extend(quote::quote! {
+                .max_concurrency(::zephyrus::concurrency::ConcurrencyLimit::new(
+                    #max,
+                    ::zephyrus::concurrency::ConcurrencyScope::#scope,
+                ))
+            });
+        }
+
+        if let Some((secs, scope)) = &self.cooldown {
+            tokens.extend(quote::quote! {
+                .cooldown(::zephyrus::cooldown::Cooldown::new(
+                    ::std::time::Duration::from_secs(#secs),
+                    ::zephyrus::concurrency::ConcurrencyScope::#scope,
+                ))
+            });
+        }
     }
 }
@@ -1,6 +1,6 @@
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use syn::{
-    parse2, spanned::Spanned, Error, FnArg, GenericParam, ItemFn, Lifetime, LifetimeDef, Result,
+    parse2, Error, FnArg, GenericParam, ItemFn, Lifetime, LifetimeDef, Result,
     ReturnType, Signature, Type,
 };
 
@@ -16,7 +16,7 @@ pub fn futurize(input: TokenStream2) -> Result<TokenStream2> {
         block,
     } = fun;
 
-    let sig_span = sig.span();
+    let sig_span = sig.fn_token.span;
     let Signature {
         asyncness,
         ident,
@@ -55,6 +55,8 @@ pub fn futurize(input: TokenStream2) -> Result<TokenStream2> {
         }),
     );
 
+    let where_clause = generics.where_clause.take();
+
     for i in &mut inputs {
         if let FnArg::Typed(kind) = i {
             // If the argument is a reference, assign the previous defined lifetime to it
@@ -67,7 +69,9 @@ pub fn futurize(input: TokenStream2) -> Result<TokenStream2> {
     Ok(quote::quote! {
         #(#attrs)*
         #vis fn #ident #generics (#inputs)
-        -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = #o> + 'future + Send>> {
+        -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = #o> + 'future + Send>>
+        #where_clause
+        {
             Box::pin(async move #block)
         }
     })
@@ -0,0 +1,54 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{parse2, spanned::Spanned, Error, ItemFn, Result, Type};
+
+/// The implementation of the interceptor macro, this macro takes the given input, which must be
+/// another function taking the data of the framework and a mutable interaction, and prepares it
+/// to be used as an interceptor hook
+pub fn interceptor(input: TokenStream2) -> Result<TokenStream2> {
+    let fun = parse2::<ItemFn>(input)?;
+    let ItemFn {
+        attrs,
+        vis,
+        mut sig,
+        block,
+    } = fun;
+
+    if sig.inputs.len() != 2 {
+        // This hook is expected to have a `&T` and a `&mut Interaction` parameter.
+        return Err(Error::new(
+            sig.inputs.span(),
+            "Function parameter must only be &T and &mut Interaction",
+        ));
+    }
+
+    // The name of the original function
+    let ident = sig.ident.clone();
+    // The name the function will have after this macro's execution
+    let fn_ident = quote::format_ident!("_{}", &ident);
+    sig.ident = fn_ident.clone();
+
+    // The data of the framework is the first parameter, taken by reference
+    let data = crate::util::get_pat(sig.inputs.first().unwrap())?;
+    let ty = match &*data.ty {
+        Type::Reference(reference) if reference.mutability.is_none() => &reference.elem,
+        other => {
+            return Err(Error::new(
+                other.span(),
+                "The data must be taken by reference, like `&T`",
+            ))
+        }
+    };
+
+    // Get the futurize macro so we can fit the function into a normal fn pointer
+    let futurize = crate::util::get_futurize_macro();
+
+    Ok(quote::quote! {
+        pub fn #ident() -> ::zephyrus::hook::InterceptorHook<#ty> {
+            ::zephyrus::hook::InterceptorHook(#fn_ident)
+        }
+
+        #[#futurize]
+        #(#attrs)*
+        #vis #sig #block
+    })
+}
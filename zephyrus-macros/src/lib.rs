@@ -7,10 +7,16 @@ mod argument;
 mod attr;
 mod autocomplete;
 mod before;
+mod button;
 mod command;
+mod commands;
 mod futurize;
 mod details;
+mod error_handler;
+mod fallback;
+mod interceptor;
 mod parse;
+mod select;
 mod util;
 
 /// Converts an `async` function into a normal function returning a
@@ -31,16 +37,16 @@ pub fn futurize(_: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// This macro can be used two ways:
 ///
-///     - Without arguments, as #[command], which takes the caller function name as the name of the command.
-///     - Providing the name, as #[command("command name")] which takes the provided name as the command name.
+/// - Without arguments, as #[command], which takes the caller function name as the name of the command.
+/// - Providing the name, as #[command("command name")] which takes the provided name as the command name.
 ///
 /// When marking a function with this attribute macro, you **must** provide a description of the
 /// command that will be seen on discord when using the command, this is made by adding a
 /// `description` attribute, which can be added two ways:
 ///
-///     - List way: #[description("Some description")]
+/// - List way: #[description("Some description")]
 ///
-///     - Named value way: #[description = "Some description"]
+/// - Named value way: #[description = "Some description"]
 ///
 /// ## Arguments:
 ///
@@ -49,6 +55,17 @@ pub fn futurize(_: TokenStream, input: TokenStream) -> TokenStream {
 /// done with all the arguments except the context, which must be the first one, the accepted
 /// syntax is the same as the previous `description` one.
 ///
+/// Doc comments can be used instead of the `description` attribute, their lines being joined into
+/// the description of the argument:
+///
+/// ```rust,ignore
+/// async fn greet(
+///     ctx: &SlashContext<()>,
+///     /// The user to greet
+///     user: Id<UserMarker>,
+/// ) -> CommandResult
+/// ```
+///
 /// ### Renaming:
 /// Adding a `rename` attribute is optional, but can be used to modify the name of the argument seen
 /// in discord, it is allowed to have only one `rename` attribute per argument and the attribute can
@@ -59,6 +76,23 @@ pub fn futurize(_: TokenStream, input: TokenStream) -> TokenStream {
 /// the user's input for an argument. This attribute is used the same way as the description one,
 /// but it *must* point to a function marked with the `#[autocomplete]` attribute macro.
 ///
+/// ### Error messages:
+/// Adding an `error_message` attribute is optional, it replaces the message of the
+/// `ParseError` returned when the argument fails to parse, like
+/// `#[error_message = "Please provide a member of this server"]`. The original error is kept as
+/// the source of the new one.
+///
+/// ### Varargs:
+/// A `Vec<T>` argument marked with `#[varargs(max = 5)]` is registered as the numbered options
+/// `name1` to `name5`, which are collected back into the vector when parsing the command. Only
+/// the first one is required, which can be changed using `min`, like
+/// `#[varargs(min = 2, max = 5)]`.
+///
+/// ### Sensitive arguments:
+/// An argument marked with `#[sensitive]` has its value replaced by `[redacted]` in audit
+/// records, error reports, the debug output of framework events and the messages of its parse
+/// errors, which is useful for arguments such as tokens or emails.
+///
 /// ## Specifying required permissions
 ///
 /// It is possible to specify the permissions needed to execute the command by using the
@@ -66,11 +100,84 @@ pub fn futurize(_: TokenStream, input: TokenStream) -> TokenStream {
 /// [twilight permissions](https://docs.rs/twilight-model/latest/twilight_model/guild/struct.Permissions.html).
 /// For example, to specify that a user needs to have administrator permissions to execute a command,
 /// the attribute would be used like this `#[required_permissions(ADMINISTRATOR)]`.
+///
+/// ## Categories
+///
+/// A command can be given a category using the `#[category]` attribute, like
+/// `#[category("Moderation")]`. The category is only used as metadata, allowing help commands
+/// and other tools to group commands.
+///
+/// ## Concurrency limits
+///
+/// The number of concurrent executions of a command can be limited using the
+/// `#[max_concurrency]` attribute, which takes the limit and optionally the scope it applies to,
+/// one of `global`, `guild`, `channel` or `user`. For example, `#[max_concurrency(1, guild)]`
+/// only allows one execution of the command at a time in every guild. The scope defaults to
+/// `global`.
+///
+/// ## Cooldowns
+///
+/// The `#[cooldown]` attribute prevents a command from being used again for the given number of
+/// seconds, taking the same scopes as `#[max_concurrency]`. For example, `#[cooldown(30, user)]`
+/// only allows every user to use the command once every 30 seconds.
+///
+/// ## Ephemeral responses
+///
+/// Marking a command with `#[ephemeral]` makes the responses sent by the framework for it, such
+/// as the one returned by the command or the busy response, only visible to the user who invoked
+/// it.
+///
+/// ## Premium commands
+///
+/// Commands marked with `#[premium_required(sku_id)]` are only executed for users with an
+/// entitlement to the given sku, other users receive a button to purchase it instead.
+///
+/// ## Metadata
+///
+/// Arbitrary key-value pairs can be attached to a command using the `#[meta]` attribute, like
+/// `#[meta(tier = "premium", feature = "music")]`. The pairs are available from the command
+/// itself and from the context given to hooks.
 #[proc_macro_attribute]
 pub fn command(attrs: TokenStream, input: TokenStream) -> TokenStream {
     extract(command::command(attrs.into(), input.into()))
 }
 
+/// Turns every method marked with `#[command]` inside of an impl block into a command, allowing
+/// to define commands as `&self` methods of a type.
+///
+/// The methods take `&self` followed by the same parameters a `#[command]` function takes, and
+/// accept the same attributes. When executed, the method is called on the data of the framework,
+/// which must be the type itself or implement `Borrow` for it.
+///
+/// The type implements `CommandSet`, so all of its commands can be registered at once using
+/// `FrameworkBuilder::command_set`.
+///
+/// Given `static_dispatch`, as `#[commands(static_dispatch)]`, the type also implements
+/// `StaticDispatch`, so processing the interactions using `Framework::process_static` executes
+/// its commands without boxing their futures. Only the command handlers are dispatched statically,
+/// hooks are still called through their boxed futures.
+///
+/// # Examples:
+///
+/// ```rust,ignore
+/// struct Music {
+///     volume: u8,
+/// }
+///
+/// #[commands]
+/// impl Music {
+///     #[command]
+///     #[description = "Shows the current volume"]
+///     async fn volume(&self, ctx: &SlashContext<Music>) -> CommandResult {
+///         // self.volume is available here
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn commands(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    extract(commands::commands(attrs.into(), input.into()))
+}
+
 /// Prepares the function to allow it to be set as an after hook, see
 /// the implementation for more information about this macro's behaviour.
 #[proc_macro_attribute]
@@ -80,16 +187,111 @@ pub fn after(_: TokenStream, input: TokenStream) -> TokenStream {
 
 /// Prepares the function to allow it to be set as a before hook, see
 /// the implementation for more information about this macro's behaviour.
+///
+/// The function returns either a `HookVerdict` or a `bool`, `false` skipping the command.
 #[proc_macro_attribute]
 pub fn before(_: TokenStream, input: TokenStream) -> TokenStream {
     extract(before::before(input.into()))
 }
 
+/// Prepares the function to allow it to be set as an error hook, receiving the errors produced
+/// by the framework while executing a command, such as panics.
+#[proc_macro_attribute]
+pub fn error_handler(_: TokenStream, input: TokenStream) -> TokenStream {
+    extract(error_handler::error_handler(input.into()))
+}
+
+/// Prepares the function to allow it to be set as an unknown command hook, executed when an
+/// interaction refers to a command the framework doesn't know, such as a removed or renamed one.
+#[proc_macro_attribute]
+pub fn unknown_command(_: TokenStream, input: TokenStream) -> TokenStream {
+    extract(fallback::fallback(
+        input.into(),
+        quote::quote!(::zephyrus::hook::UnknownCommandHook),
+        "&str",
+    ))
+}
+
+/// Prepares the function to allow it to be set as an unhandled component hook, executed when a
+/// message component is used but no button handles its custom id, such as the components of
+/// messages sent before a restart.
+#[proc_macro_attribute]
+pub fn unhandled_component(_: TokenStream, input: TokenStream) -> TokenStream {
+    extract(fallback::fallback(
+        input.into(),
+        quote::quote!(::zephyrus::hook::UnhandledComponentHook),
+        "&str",
+    ))
+}
+
+/// Prepares the function to allow it to be set as an unhandled interaction hook, executed when
+/// the framework receives an interaction of a type it doesn't handle, such as modal submits,
+/// receiving the type of the interaction.
+#[proc_macro_attribute]
+pub fn unhandled_interaction(_: TokenStream, input: TokenStream) -> TokenStream {
+    extract(fallback::fallback(
+        input.into(),
+        quote::quote!(::zephyrus::hook::UnhandledInteractionHook),
+        "InteractionType",
+    ))
+}
+
+/// Prepares the function to allow it to be set as an interceptor, executed before routing every
+/// interaction. It receives the data of the framework and the interaction, which it can modify,
+/// and returns an `Intercept` deciding whether the framework keeps processing the interaction.
+#[proc_macro_attribute]
+pub fn interceptor(_: TokenStream, input: TokenStream) -> TokenStream {
+    extract(interceptor::interceptor(input.into()))
+}
+
 #[proc_macro_attribute]
 pub fn check(attrs: TokenStream, input: TokenStream) -> TokenStream {
     before(attrs, input)
 }
 
+/// Converts an `async-compatible` function into a button handler, executed when a message
+/// component whose custom id matches the given template is used.
+///
+/// # Usage:
+///
+/// The template is given at macro invocation, as `#[button("role:{role_id}:{action}")]`, and
+/// defaults to the name of the function. Every `{name}` of the template is a placeholder which
+/// must have a parameter of the same name after the context, whose type must implement
+/// `FromStr`. When the button is used, the values found in the custom id are parsed into those
+/// parameters.
+///
+/// The macro also generates a `<function>_custom_id` function taking the placeholders as
+/// parameters, which builds the custom ids handled by the button when sending components.
+///
+/// The look of the button is set using the `#[style(Success)]`, `#[label = "..."]`,
+/// `#[emoji = "..."]` and `#[disabled]` attributes, the style being one of the
+/// [button styles](https://docs.rs/twilight-model/latest/twilight_model/channel/message/component/enum.ButtonStyle.html)
+/// and `Primary` by default. A `<function>_component` function is generated as well, taking the
+/// same parameters as the custom id one and building the component shown in discord.
+///
+/// The `#[required_permissions]`, `#[checks]` and `#[ephemeral]` attributes can be used the
+/// same way as in commands.
+///
+/// # Examples:
+///
+/// ```rust,ignore
+/// #[button("role:{role_id}:{action}")]
+/// #[style(Success)]
+/// #[label = "Toggle role"]
+/// async fn role(ctx: &SlashContext<()>, role_id: Id<RoleMarker>, action: String) -> CommandResult {
+///     // ...
+/// }
+///
+/// // Builds "role:1234:add"
+/// let custom_id = role_custom_id(Id::new(1234), String::from("add"));
+/// // Builds the button, using the same custom id
+/// let component = role_component(Id::new(1234), String::from("add"));
+/// ```
+#[proc_macro_attribute]
+pub fn button(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    extract(button::button(attrs.into(), input.into()))
+}
+
 /// Prepares the function to be used to autocomplete command arguments.
 #[proc_macro_attribute]
 pub fn autocomplete(_: TokenStream, input: TokenStream) -> TokenStream {
@@ -101,6 +303,32 @@ pub fn parse(input: TokenStream) -> TokenStream {
     extract(parse::parse(input.into()))
 }
 
+/// Implements `SelectOptions` for a fieldless enum, allowing its variants to be chosen using a
+/// string select menu.
+///
+/// Every variant becomes an option whose label and value are the name of the variant, which can
+/// be changed using the `#[label]` and `#[value]` attributes. A `#[description]` and an unicode
+/// `#[emoji]` can also be set, and options can be selected by default using `#[selected]`.
+///
+/// # Examples:
+///
+/// ```rust,ignore
+/// #[derive(SelectOptions)]
+/// enum Color {
+///     #[label = "Red"]
+///     #[emoji = "🔴"]
+///     #[selected]
+///     Red,
+///     #[label = "Blue"]
+///     #[description = "The color of the sky"]
+///     Blue,
+/// }
+/// ```
+#[proc_macro_derive(SelectOptions, attributes(label, value, description, emoji, selected))]
+pub fn select_options(input: TokenStream) -> TokenStream {
+    extract(select::select_options(input.into()))
+}
+
 /// Extracts the given result, throwing a compile error if an error is given.
 fn extract(res: syn::Result<TokenStream2>) -> TokenStream {
     match res {
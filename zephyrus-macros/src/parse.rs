@@ -22,9 +22,9 @@ impl Variant {
         let value = &self.value;
         let index = self.index as i64;
         tokens.extend(quote::quote! {
-            choices.push(::zephyrus::twilight_exports::CommandOptionChoice::Int {
+            choices.push(::zephyrus::twilight_exports::CommandOptionChoice {
                 name: #value.to_string(),
-                value: #index,
+                value: ::zephyrus::twilight_exports::CommandOptionChoiceValue::Integer(#index),
                 name_localizations: None
             });
         })
@@ -44,9 +44,8 @@ pub fn parse(input: TokenStream2) -> Result<TokenStream2> {
     };
 
     let mut variants = Vec::new();
-    let mut index = 1;
 
-    for variant in enumeration.variants {
+    for (index, variant) in (1..).zip(enumeration.variants) {
         if !matches!(&variant.fields, syn::Fields::Unit) {
             return Err(Error::new(
                 variant.span(),
@@ -67,8 +66,6 @@ pub fn parse(input: TokenStream2) -> Result<TokenStream2> {
             value: name,
             index,
         });
-
-        index += 1;
     }
 
     let mut parse_stream = TokenStream2::new();
@@ -113,6 +110,9 @@ pub fn parse(input: TokenStream2) -> Result<TokenStream2> {
 
                     Some(choices)
                 }
+                fn validates_choices() -> bool {
+                    true
+                }
             }
         };
     })
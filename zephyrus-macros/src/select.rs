@@ -0,0 +1,152 @@
+use crate::attr::Attr;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use std::convert::TryFrom;
+use syn::{spanned::Spanned, DeriveInput, Error, Result};
+
+struct Variant {
+    ident: Ident,
+    label: String,
+    value: String,
+    description: Option<String>,
+    emoji: Option<String>,
+    selected: bool,
+}
+
+impl Variant {
+    fn option_tokens(&self, tokens: &mut TokenStream2) {
+        let label = &self.label;
+        let value = &self.value;
+        let selected = self.selected;
+        let description = match &self.description {
+            Some(description) => quote::quote!(Some(String::from(#description))),
+            None => quote::quote!(None),
+        };
+        let emoji = match &self.emoji {
+            Some(emoji) => quote::quote! {
+                Some(::zephyrus::twilight_exports::EmojiReactionType::Unicode {
+                    name: String::from(#emoji)
+                })
+            },
+            None => quote::quote!(None),
+        };
+
+        tokens.extend(quote::quote! {
+            ::zephyrus::twilight_exports::SelectMenuOption {
+                default: #selected,
+                description: #description,
+                emoji: #emoji,
+                label: String::from(#label),
+                value: String::from(#value),
+            },
+        })
+    }
+
+    fn parse_tokens(&self, tokens: &mut TokenStream2) {
+        let ident = &self.ident;
+        let value = &self.value;
+        tokens.extend(quote::quote! {
+            #value => Some(Self::#ident),
+        })
+    }
+
+    fn value_tokens(&self, tokens: &mut TokenStream2) {
+        let ident = &self.ident;
+        let value = &self.value;
+        tokens.extend(quote::quote! {
+            Self::#ident => #value,
+        })
+    }
+}
+
+pub fn select_options(input: TokenStream2) -> Result<TokenStream2> {
+    let derive = syn::parse2::<DeriveInput>(input)?;
+    let enumeration = match derive.data {
+        syn::Data::Enum(e) => e,
+        _ => {
+            return Err(Error::new(
+                derive.ident.span(),
+                "This derive is only available for enums",
+            ))
+        }
+    };
+
+    let mut variants = Vec::new();
+
+    for variant in enumeration.variants {
+        if !matches!(&variant.fields, syn::Fields::Unit) {
+            return Err(Error::new(
+                variant.fields.span(),
+                "Select menu options cannot have inner values",
+            ));
+        }
+
+        let mut parsed = Variant {
+            ident: variant.ident.clone(),
+            label: variant.ident.to_string(),
+            value: variant.ident.to_string(),
+            description: None,
+            emoji: None,
+            selected: false,
+        };
+
+        for attribute in variant.attrs {
+            if attribute.path.is_ident("selected") {
+                parsed.selected = true;
+                continue;
+            }
+
+            let attr = Attr::try_from(&attribute)?;
+            if attr.path.is_ident("label") {
+                parsed.label = attr.parse_string()?;
+            } else if attr.path.is_ident("value") {
+                parsed.value = attr.parse_string()?;
+            } else if attr.path.is_ident("description") {
+                parsed.description = Some(attr.parse_string()?);
+            } else if attr.path.is_ident("emoji") {
+                parsed.emoji = Some(attr.parse_string()?);
+            }
+        }
+
+        if let Some(other) = variants.iter().find(|v: &&Variant| v.value == parsed.value) {
+            return Err(Error::new(
+                variant.ident.span(),
+                format!("Value `{}` is already used by `{}`", parsed.value, other.ident),
+            ));
+        }
+
+        variants.push(parsed);
+    }
+
+    let mut option_stream = TokenStream2::new();
+    let mut parse_stream = TokenStream2::new();
+    let mut value_stream = TokenStream2::new();
+    for variant in &variants {
+        variant.option_tokens(&mut option_stream);
+        variant.parse_tokens(&mut parse_stream);
+        variant.value_tokens(&mut value_stream);
+    }
+
+    let enum_name = &derive.ident;
+
+    Ok(quote::quote! {
+        #[automatically_derived]
+        impl ::zephyrus::select::SelectOptions for #enum_name {
+            fn options() -> Vec<::zephyrus::twilight_exports::SelectMenuOption> {
+                vec![#option_stream]
+            }
+
+            fn from_value(value: &str) -> Option<Self> {
+                match value {
+                    #parse_stream
+                    _ => None,
+                }
+            }
+
+            fn value(&self) -> &'static str {
+                match self {
+                    #value_stream
+                }
+            }
+        }
+    })
+}
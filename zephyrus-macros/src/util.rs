@@ -95,44 +95,52 @@ pub fn get_context_type_and_ident(sig: &Signature) -> Result<(Ident, Type)> {
     let ctx = match sig.inputs.iter().next() {
         None => {
             return Err(Error::new(
-                sig.inputs.span(),
-                "Expected SlashContext as first paramenter",
+                sig.paren_token.span,
+                "Expected `&SlashContext<T>` as the first parameter",
             ))
         }
         Some(c) => get_pat(c)?,
     };
 
     let ctx_ident = get_ident(&ctx.pat)?;
-    let path = get_path(&ctx.ty)?;
+    let elem = match &*ctx.ty {
+        Type::Reference(reference) => &reference.elem,
+        other => {
+            return Err(Error::new(
+                other.span(),
+                "The context must be taken by reference, like `&SlashContext<T>`",
+            ))
+        }
+    };
+
+    let path = get_path(elem)?;
+    let segment = path.segments.last().unwrap();
+    if segment.ident != "SlashContext" {
+        return Err(Error::new(
+            elem.span(),
+            format!(
+                "Expected `&SlashContext<T>` as the first parameter, found `&{}`",
+                elem.to_token_stream()
+            ),
+        ));
+    }
+
     let mut args = get_generic_arguments(path)?;
 
     let ty = loop {
         match args.next() {
             Some(GenericArgument::Lifetime(_)) => (),
-            Some(a) => {
-                break match a {
-                    GenericArgument::Type(t) => {
-                        if let Type::Infer(_) = t {
-                            return Err(Error::new(
-                                sig.inputs.span(),
-                                "SlashContext must have a known type",
-                            ));
-                        } else {
-                            t.clone()
-                        }
-                    }
-                    _ => {
-                        return Err(Error::new(
-                            sig.inputs.span(),
-                            "SlashContext type must be a type",
-                        ))
-                    }
-                }
+            Some(GenericArgument::Type(Type::Infer(t))) => {
+                return Err(Error::new(t.span(), "SlashContext must have a known type"))
+            }
+            Some(GenericArgument::Type(t)) => break t.clone(),
+            Some(other) => {
+                return Err(Error::new(other.span(), "SlashContext type must be a type"))
             }
             None => {
                 return Err(Error::new(
-                    sig.inputs.span(),
-                    "SlashContext type must be set",
+                    segment.span(),
+                    "SlashContext type must be set, like `SlashContext<T>`",
                 ))
             }
         }
@@ -0,0 +1,140 @@
+//! Measures the work done to hand the options of an interaction to the invoked command and to
+//! parse them.
+//!
+//! Each group compares what the framework does against what it did before: cloning the options
+//! of the invoked subcommand and building the choices of derived enums on every parse.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+use zephyrus::{builder::WrappedClient, prelude::*, twilight_exports::*};
+
+#[derive(Parse)]
+#[allow(dead_code)]
+enum Choice {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    Eighth,
+    Ninth,
+    Tenth,
+}
+
+/// Drives a future which never waits to completion.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn client() -> WrappedClient {
+    // The http client needs a runtime and a crypto provider to be built, even though it's never
+    // used.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let _guard = runtime.enter();
+    WrappedClient::Arc(Arc::new(Client::builder().ratelimiter(None).build()))
+}
+
+fn subcommand_options() -> Vec<CommandDataOption> {
+    let options = (0..5)
+        .map(|i| CommandDataOption {
+            name: format!("option-{}", i),
+            value: CommandOptionValue::String(format!("Some input for option {}", i)),
+        })
+        .collect();
+
+    vec![CommandDataOption {
+        name: String::from("group"),
+        value: CommandOptionValue::SubCommandGroup(vec![CommandDataOption {
+            name: String::from("subcommand"),
+            value: CommandOptionValue::SubCommand(options),
+        }]),
+    }]
+}
+
+/// Clones the options provided to the invoked command, as the framework did before.
+fn cloned(options: &[CommandDataOption]) -> Vec<CommandDataOption> {
+    match options.first().map(|option| &option.value) {
+        Some(CommandOptionValue::SubCommandGroup(inner))
+        | Some(CommandOptionValue::SubCommand(inner)) => cloned(inner),
+        _ => options.to_vec(),
+    }
+}
+
+/// Takes the options provided to the invoked command out of the given ones, as the framework
+/// does now.
+fn taken(options: &mut Vec<CommandDataOption>) -> Vec<CommandDataOption> {
+    match options.first_mut().map(|option| &mut option.value) {
+        Some(CommandOptionValue::SubCommandGroup(inner))
+        | Some(CommandOptionValue::SubCommand(inner)) => taken(inner),
+        _ => std::mem::take(options),
+    }
+}
+
+fn options(c: &mut Criterion) {
+    let mut group = c.benchmark_group("options");
+    let options = subcommand_options();
+
+    group.bench_function("clone", |b| {
+        b.iter_batched_ref(
+            || options.clone(),
+            |options| cloned(options),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("take", |b| {
+        b.iter_batched_ref(
+            || options.clone(),
+            taken,
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn choices(c: &mut Criterion) {
+    let mut group = c.benchmark_group("choices");
+    let client = client();
+    let value = CommandOptionValue::Integer(7);
+
+    group.bench_function("validated", |b| {
+        b.iter(|| {
+            let value = black_box(&value);
+            let choice = block_on(<Choice as Parse<()>>::parse(&client, &(), Some(value)));
+            choice.is_ok()
+        })
+    });
+    group.bench_function("rebuilt", |b| {
+        b.iter(|| {
+            let value = black_box(&value);
+            let choice = block_on(<Choice as Parse<()>>::parse(&client, &(), Some(value)));
+            let valid = <Choice as Parse<()>>::choices()
+                .unwrap_or_default()
+                .iter()
+                .any(|choice| matches!(
+                    (&choice.value, value),
+                    (CommandOptionChoiceValue::Integer(a), CommandOptionValue::Integer(b)) if a == b
+                ));
+            choice.is_ok() && valid
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, options, choices);
+criterion_main!(benches);
@@ -0,0 +1,133 @@
+//! Measures how long the framework takes to find the command targeted by an interaction.
+//!
+//! The `lookup` group compares the perfect hash tables used by the framework against a map using
+//! the standard hasher and against a sorted slice searched with a binary search.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+use zephyrus::{
+    command::Command,
+    prelude::*,
+    route::Routes,
+    twilight_exports::*,
+};
+
+const SIZES: [usize; 3] = [10, 100, 500];
+
+fn command(name: String) -> Command<(), ParseError> {
+    Command::new(|_| {
+        Box::pin(async {
+            Ok(InteractionResponse {
+                kind: InteractionResponseType::DeferredChannelMessageWithSource,
+                data: None,
+            })
+        })
+    })
+        .name(name)
+        .description("Benchmark command")
+}
+
+fn framework(size: usize) -> Framework<(), ParseError> {
+    let client = Arc::new(Client::builder().ratelimiter(None).build());
+    let mut builder = Framework::builder(client, Id::new(1), ());
+
+    for i in 0..size {
+        builder = builder.insert_command(command(format!("command-{}", i)));
+    }
+
+    builder
+        .group(|group| {
+            group
+                .name("group")
+                .description("Benchmark group")
+                .insert_command(command(String::from("subcommand")))
+        })
+        .build()
+}
+
+fn data(name: &str, options: Vec<CommandDataOption>) -> CommandData {
+    CommandData {
+        guild_id: None,
+        id: Id::new(1),
+        name: name.to_string(),
+        kind: CommandType::ChatInput,
+        options,
+        resolved: None,
+        target_id: None,
+    }
+}
+
+fn resolve(c: &mut Criterion) {
+    // The http client needs a runtime and a crypto provider to be built, even though it's never
+    // used.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let _guard = runtime.enter();
+    let mut group = c.benchmark_group("resolve");
+
+    for size in SIZES {
+        let framework = framework(size);
+        let data = data(&format!("command-{}", size / 2), Vec::new());
+
+        group.bench_with_input(BenchmarkId::new("command", size), &data, |b, data| {
+            b.iter(|| framework.resolve(black_box(data)).is_some())
+        });
+    }
+
+    let framework = framework(SIZES[0]);
+    let data = data(
+        "group",
+        vec![CommandDataOption {
+            name: String::from("subcommand"),
+            value: CommandOptionValue::SubCommand(Vec::new()),
+        }],
+    );
+    group.bench_function("subcommand", |b| {
+        b.iter(|| framework.resolve(black_box(&data)).is_some())
+    });
+
+    group.finish();
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+
+    for size in SIZES {
+        let names = (0..size)
+            .map(|i| Cow::Owned(format!("command-{}", i)))
+            .collect::<Vec<Cow<'static, str>>>();
+        let key = format!("command-{}", size / 2);
+
+        let route = Routes::new(names.iter().cloned().zip(0..).collect::<HashMap<_, usize>>());
+        group.bench_with_input(BenchmarkId::new("routes", size), key.as_str(), |b, key| {
+            b.iter(|| route.get(black_box(key)).copied())
+        });
+
+        let std = names
+            .iter()
+            .cloned()
+            .zip(0..)
+            .collect::<HashMap<Cow<'static, str>, usize>>();
+        group.bench_with_input(BenchmarkId::new("std_map", size), key.as_str(), |b, key| {
+            b.iter(|| std.get(black_box(key)).copied())
+        });
+
+        let mut sorted = names.into_iter().zip(0..).collect::<Vec<_>>();
+        sorted.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        let sorted = sorted.into_boxed_slice();
+        group.bench_with_input(BenchmarkId::new("sorted_slice", size), key.as_str(), |b, key| {
+            b.iter(|| {
+                let key = black_box(key);
+                sorted
+                    .binary_search_by(|(name, _)| name.as_ref().cmp(key))
+                    .ok()
+                    .map(|i| sorted[i].1)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, resolve, lookup);
+criterion_main!(benches);
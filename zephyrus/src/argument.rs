@@ -1,22 +1,143 @@
+use crate::compat;
 use crate::hook::AutocompleteHook;
 use crate::twilight_exports::*;
-use twilight_model::application::command::CommandOptionValue;
-use crate::parse::Parse;
+use twilight_model::application::{
+    command::CommandOptionValue,
+    interaction::application_command::CommandOptionValue as InputValue,
+};
+use crate::parse::{Parse, ParseError};
+use serde::Serialize;
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Formatter, Result as FmtResult},
+};
 
 /// The constraints the arguments impose to the user.
 /// This is normally provided by implementing [parse](crate::parse::Parse) into a type.
-#[derive(Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, Serialize)]
 pub struct ArgumentLimits {
+    /// The minimum value allowed, only valid for numeric arguments.
     pub min: Option<CommandOptionValue>,
-    pub max: Option<CommandOptionValue>
+    /// The maximum value allowed, only valid for numeric arguments.
+    pub max: Option<CommandOptionValue>,
+    /// The minimum length allowed, only valid for string arguments.
+    pub min_length: Option<u16>,
+    /// The maximum length allowed, only valid for string arguments.
+    pub max_length: Option<u16>,
+}
+
+impl ArgumentLimits {
+    /// Checks whether the given input satisfies these limits.
+    pub fn check(&self, input: &InputValue) -> Result<(), String> {
+        let as_f64 = |value: CommandOptionValue| match value {
+            CommandOptionValue::Integer(i) => i as f64,
+            CommandOptionValue::Number(n) => n,
+        };
+
+        let number = match input {
+            InputValue::Integer(i) => Some(*i as f64),
+            InputValue::Number(n) => Some(*n),
+            _ => None,
+        };
+
+        if let Some(number) = number {
+            if let Some(min) = self.min.map(as_f64) {
+                if number < min {
+                    return Err(format!("Input must be greater than or equal to {}", min));
+                }
+            }
+            if let Some(max) = self.max.map(as_f64) {
+                if number > max {
+                    return Err(format!("Input must be less than or equal to {}", max));
+                }
+            }
+        }
+
+        if let InputValue::String(s) = input {
+            let length = s.chars().count();
+            if let Some(min) = self.min_length {
+                if length < min as usize {
+                    return Err(format!("Input must have at least {} characters", min));
+                }
+            }
+            if let Some(max) = self.max_length {
+                if length > max as usize {
+                    return Err(format!("Input must have at most {} characters", max));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the given input against the constraints declared by `T`, as discord side validation
+/// can be bypassed by sending requests directly to the API.
+pub(crate) fn check_constraints<T, D>(
+    input: &InputValue,
+    resolved: Option<&InteractionDataResolved>,
+) -> Result<(), ParseError>
+where
+    T: Parse<D>,
+    D: Send + Sync,
+{
+    let result = (|| {
+        if let Some(limits) = T::limits() {
+            limits.check(input)?;
+        }
+
+        let choices = if T::validates_choices() {
+            None
+        } else {
+            T::choices()
+        };
+
+        if let Some(choices) = choices.filter(|choices| !choices.is_empty()) {
+            let valid = choices.iter().any(|choice| match (&choice.value, input) {
+                (CommandOptionChoiceValue::String(value), InputValue::String(s)) => value == s,
+                (CommandOptionChoiceValue::Integer(value), InputValue::Integer(i)) => value == i,
+                (CommandOptionChoiceValue::Number(value), InputValue::Number(n)) => value == n,
+                _ => false,
+            });
+
+            if !valid {
+                return Err(String::from("Input is not one of the allowed choices"));
+            }
+        }
+
+        if let (Some(types), InputValue::Channel(id)) = (T::channel_types(), input) {
+            let channel = resolved.and_then(|resolved| resolved.channels.get(id));
+            if let Some(channel) = channel {
+                if !types.is_empty() && !types.contains(&channel.kind) {
+                    return Err(format!("Channel type {:?} is not allowed", channel.kind));
+                }
+            }
+        }
+
+        Ok(())
+    })();
+
+    result.map_err(|error| ParseError::Parsing {
+        argument_name: String::new(),
+        required: T::required(),
+        type_: format!("{:?}", T::kind()),
+        error,
+    })
+}
+
+/// Whether the argument with the given name is [sensitive](CommandArgument::sensitive).
+pub(crate) fn is_sensitive<D>(arguments: &[CommandArgument<D>], name: &str) -> bool {
+    arguments
+        .iter()
+        .any(|argument| argument.sensitive && argument.name == name)
 }
 
 /// A command argument.
 pub struct CommandArgument<D> {
     /// Argument name.
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     /// Description of the argument.
-    pub description: &'static str,
+    pub description: Cow<'static, str>,
     /// Whether the argument is required.
     pub required: bool,
     /// The type this argument has.
@@ -25,92 +146,90 @@ pub struct CommandArgument<D> {
     pub choices: Option<Vec<CommandOptionChoice>>,
     /// The input limits of this argument.
     pub limits: Option<ArgumentLimits>,
+    /// The channel types allowed as input, only valid for channel arguments.
+    pub channel_types: Option<Vec<ChannelType>>,
     /// A function used to autocomplete fields.
     pub autocomplete: Option<AutocompleteHook<D>>,
+    /// Whether the value of this argument is sensitive, such as a token or an email, being
+    /// replaced by [REDACTED](crate::audit::REDACTED) in audit records, error reports, the debug
+    /// output of [events](crate::event::FrameworkEvent) and parse errors.
+    pub sensitive: bool,
+}
+
+impl<D> Debug for CommandArgument<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("CommandArgument")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("required", &self.required)
+            .field("kind", &self.kind)
+            .field("choices", &self.choices)
+            .field("limits", &self.limits)
+            .field("channel_types", &self.channel_types)
+            .field("autocomplete", &self.autocomplete.is_some())
+            .field("sensitive", &self.sensitive)
+            .finish()
+    }
 }
 
 impl<D> CommandArgument<D> {
+    /// Sets whether the value of this argument is sensitive, redacting it wherever the framework
+    /// shows the values of the arguments.
+    pub fn sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
     pub fn as_option(&self) -> CommandOption {
+        let limits = self.limits.unwrap_or_default();
+        let mut option = compat::option(self.kind, &self.name, &self.description);
+        option.required = Some(self.required);
+
         match self.kind {
-            CommandOptionType::String => CommandOption::String(ChoiceCommandOptionData {
-                autocomplete: self.autocomplete.is_some(),
-                choices: self.choices.clone().unwrap_or_default(),
-                description: self.description.to_string(),
-                name: self.name.to_string(),
-                required: self.required,
-                ..Default::default()
-            }),
-            CommandOptionType::Integer => CommandOption::Integer(NumberCommandOptionData {
-                autocomplete: self.autocomplete.is_some(),
-                choices: self.choices.clone().unwrap_or_default(),
-                description: self.description.to_string(),
-                max_value: self.limits.unwrap_or_default().max,
-                min_value: self.limits.unwrap_or_default().min,
-                name: self.name.to_string(),
-                required: self.required,
-                ..Default::default()
-            }),
-            CommandOptionType::Boolean => CommandOption::Boolean(BaseCommandOptionData {
-                description: self.description.to_string(),
-                name: self.name.to_string(),
-                required: self.required,
-                ..Default::default()
-            }),
-            CommandOptionType::User => CommandOption::User(BaseCommandOptionData {
-                description: self.description.to_string(),
-                name: self.name.to_string(),
-                required: self.required,
-                ..Default::default()
-            }),
-            CommandOptionType::Channel => CommandOption::Channel(ChannelCommandOptionData {
-                channel_types: Vec::new(),
-                description: self.description.to_string(),
-                name: self.name.to_string(),
-                required: self.required,
-                ..Default::default()
-            }),
-            CommandOptionType::Role => CommandOption::Role(BaseCommandOptionData {
-                description: self.description.to_string(),
-                name: self.name.to_string(),
-                required: self.required,
-                ..Default::default()
-            }),
-            CommandOptionType::Mentionable => CommandOption::Mentionable(BaseCommandOptionData {
-                description: self.description.to_string(),
-                name: self.name.to_string(),
-                required: self.required,
-                ..Default::default()
-            }),
-            CommandOptionType::Number => CommandOption::Number(NumberCommandOptionData {
-                autocomplete: self.autocomplete.is_some(),
-                choices: self.choices.clone().unwrap_or_default(),
-                description: self.description.to_string(),
-                max_value: self.limits.unwrap_or_default().max,
-                min_value: self.limits.unwrap_or_default().min,
-                name: self.name.to_string(),
-                required: self.required,
-                ..Default::default()
-            }),
+            CommandOptionType::String => {
+                option.autocomplete = Some(self.autocomplete.is_some());
+                option.choices = self.choices.clone();
+                option.max_length = limits.max_length;
+                option.min_length = limits.min_length;
+            }
+            CommandOptionType::Integer | CommandOptionType::Number => {
+                option.autocomplete = Some(self.autocomplete.is_some());
+                option.choices = self.choices.clone();
+                option.max_value = limits.max;
+                option.min_value = limits.min;
+            }
+            CommandOptionType::Channel => {
+                option.channel_types = self.channel_types.clone();
+            }
+            CommandOptionType::Boolean
+            | CommandOptionType::User
+            | CommandOptionType::Role
+            | CommandOptionType::Mentionable
+            | CommandOptionType::Attachment => (),
             _ => unreachable!(),
         }
+
+        option
     }
 }
 
 impl<D: Send + Sync> CommandArgument<D> {
     pub fn new<T: Parse<D>>(
-        name: &'static str,
-        description: &'static str,
+        name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
         autocomplete: Option<AutocompleteHook<D>>
     ) -> Self
     {
         Self {
-            name,
-            description,
+            name: name.into(),
+            description: description.into(),
             required: T::required(),
             kind: T::kind(),
             choices: T::choices(),
             limits: T::limits(),
-            autocomplete
+            channel_types: T::channel_types(),
+            autocomplete,
+            sensitive: false
         }
     }
 }
@@ -0,0 +1,257 @@
+//! Attachment arguments, whose content can be downloaded enabling the `attachment-download`
+//! feature.
+use crate::parse_impl::error;
+use crate::prelude::*;
+use crate::twilight_exports::*;
+#[cfg(feature = "attachment-download")]
+use http_body_util::{BodyExt, Empty, Limited};
+#[cfg(feature = "attachment-download")]
+use hyper::body::Bytes;
+#[cfg(feature = "attachment-download")]
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client as HyperClient},
+    rt::TokioExecutor,
+};
+#[cfg(feature = "attachment-download")]
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    sync::OnceLock,
+};
+
+pub use twilight_model::channel::Attachment;
+
+/// The maximum size of the files [downloaded](Download::download) without other limits, 25 MiB.
+#[cfg(feature = "attachment-download")]
+pub const DEFAULT_MAX_DOWNLOAD_SIZE: u64 = 25 * 1024 * 1024;
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for Attachment {
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_resolved(http_client, data, value, None).await
+    }
+
+    async fn parse_resolved(
+        _: &WrappedClient,
+        _: &T,
+        value: Option<&CommandOptionValue>,
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Result<Self, ParseError> {
+        let id = match value {
+            Some(CommandOptionValue::Attachment(id)) => *id,
+            _ => return Err(error("Attachment", true, "Attachment expected")),
+        };
+
+        resolved
+            .and_then(|resolved| resolved.attachments.get(&id))
+            .cloned()
+            .ok_or_else(|| error("Attachment", true, "Attachment not resolved"))
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::Attachment
+    }
+}
+
+/// The limits the files [downloaded](Download::download_with) must satisfy.
+#[cfg(feature = "attachment-download")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadLimits {
+    /// The maximum size of the file, in bytes.
+    pub max_size: u64,
+    /// The content types the file can have, such as `image/png`. Types ending in `/*`, like
+    /// `image/*`, allow every subtype. Every content type is allowed if empty.
+    pub content_types: Vec<String>,
+}
+
+#[cfg(feature = "attachment-download")]
+impl Default for DownloadLimits {
+    fn default() -> Self {
+        Self {
+            max_size: DEFAULT_MAX_DOWNLOAD_SIZE,
+            content_types: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "attachment-download")]
+impl DownloadLimits {
+    /// Creates limits allowing files of up to the given size, in bytes, of any content type.
+    pub fn new(max_size: u64) -> Self {
+        Self {
+            max_size,
+            content_types: Vec::new(),
+        }
+    }
+
+    /// Allows the given content type, such as `image/png` or `image/*`.
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_types.push(content_type.into());
+        self
+    }
+
+    /// Checks whether the given content type is allowed.
+    fn allows(&self, content_type: Option<&str>) -> bool {
+        if self.content_types.is_empty() {
+            return true;
+        }
+
+        // Parameters such as the charset are ignored
+        let content_type = match content_type {
+            Some(content_type) => content_type.split(';').next().unwrap_or_default().trim(),
+            None => return false,
+        };
+
+        self.content_types.iter().any(|allowed| match allowed.strip_suffix('*') {
+            Some(prefix) => content_type.starts_with(prefix),
+            None => allowed.eq_ignore_ascii_case(content_type),
+        })
+    }
+}
+
+/// The errors produced when [downloading](Download::download) an attachment.
+#[cfg(feature = "attachment-download")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DownloadError {
+    /// The file is larger than the maximum size allowed, containing its size if known.
+    TooLarge(Option<u64>),
+    /// The content type of the file isn't allowed, containing it if known.
+    ContentType(Option<String>),
+    /// Discord responded with the given status code.
+    Status(u16),
+    /// The request failed.
+    Request(Box<dyn Error + Send + Sync>),
+    /// No default [crypto provider](rustls::crypto::CryptoProvider) is installed for rustls, so
+    /// no TLS connection can be made.
+    NoCryptoProvider,
+}
+
+#[cfg(feature = "attachment-download")]
+impl Display for DownloadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::TooLarge(Some(size)) => write!(f, "The file is too large: {} bytes", size),
+            Self::TooLarge(None) => f.write_str("The file is too large"),
+            Self::ContentType(Some(kind)) => write!(f, "Content type {} is not allowed", kind),
+            Self::ContentType(None) => f.write_str("The file has no content type"),
+            Self::Status(status) => write!(f, "Discord responded with status {}", status),
+            Self::Request(why) => write!(f, "The request failed: {}", why),
+            Self::NoCryptoProvider => f.write_str("No default rustls crypto provider is installed"),
+        }
+    }
+}
+
+#[cfg(feature = "attachment-download")]
+impl Error for DownloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Request(why) => Some(why.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Downloads the content of [attachments](Attachment), such as the ones given to commands.
+///
+/// Downloads are made over TLS using rustls, which needs a process wide
+/// [crypto provider](rustls::crypto::CryptoProvider) installed before the first download, such as
+/// by calling `rustls::crypto::ring::default_provider().install_default()` at startup, as
+/// required by the http client too. Downloads fail with [DownloadError::NoCryptoProvider]
+/// otherwise.
+#[cfg(feature = "attachment-download")]
+#[async_trait]
+pub trait Download {
+    /// Downloads the content of the attachment, limited to [DEFAULT_MAX_DOWNLOAD_SIZE] bytes.
+    async fn download(&self) -> Result<Vec<u8>, DownloadError> {
+        self.download_with(&DownloadLimits::default()).await
+    }
+
+    /// Downloads the content of the attachment, checking it satisfies the given limits.
+    async fn download_with(&self, limits: &DownloadLimits) -> Result<Vec<u8>, DownloadError>;
+}
+
+#[cfg(feature = "attachment-download")]
+#[async_trait]
+impl Download for Attachment {
+    async fn download_with(&self, limits: &DownloadLimits) -> Result<Vec<u8>, DownloadError> {
+        // Rejected before downloading anything using the metadata given by discord
+        if self.size > limits.max_size {
+            return Err(DownloadError::TooLarge(Some(self.size)));
+        }
+        if self.content_type.is_some() && !limits.allows(self.content_type.as_deref()) {
+            return Err(DownloadError::ContentType(self.content_type.clone()));
+        }
+
+        let uri = self
+            .url
+            .parse::<hyper::Uri>()
+            .map_err(|why| DownloadError::Request(Box::new(why)))?;
+        let response = client()?
+            .get(uri)
+            .await
+            .map_err(|why| DownloadError::Request(Box::new(why)))?;
+
+        if !response.status().is_success() {
+            return Err(DownloadError::Status(response.status().as_u16()));
+        }
+
+        let content_type = response
+            .headers()
+            .get(hyper::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .or(self.content_type.as_deref());
+        if !limits.allows(content_type) {
+            return Err(DownloadError::ContentType(content_type.map(String::from)));
+        }
+
+        // The metadata could be wrong, so the body itself is limited too
+        let max_size = usize::try_from(limits.max_size).unwrap_or(usize::MAX);
+        let body = Limited::new(response.into_body(), max_size)
+            .collect()
+            .await
+            .map_err(|why| match why.downcast::<http_body_util::LengthLimitError>() {
+                Ok(_) => DownloadError::TooLarge(None),
+                Err(why) => DownloadError::Request(why),
+            })?;
+
+        Ok(body.to_bytes().to_vec())
+    }
+}
+
+/// The HTTPS client used to download attachments, built like the one of the http client.
+#[cfg(feature = "attachment-download")]
+type DownloadClient = HyperClient<hyper_rustls::HttpsConnector<HttpConnector>, Empty<Bytes>>;
+
+/// Gets the client used to download attachments, creating it the first time, which fails if no
+/// default crypto provider is installed.
+#[cfg(feature = "attachment-download")]
+fn client() -> Result<&'static DownloadClient, DownloadError> {
+    use hyper_rustls::ConfigBuilderExt;
+
+    static CLIENT: OnceLock<DownloadClient> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+
+    // Building the config panics without a default provider
+    if rustls::crypto::CryptoProvider::get_default().is_none() {
+        return Err(DownloadError::NoCryptoProvider);
+    }
+    let tls_config = rustls::ClientConfig::builder()
+        .try_with_platform_verifier()
+        .map_err(|why| DownloadError::Request(Box::new(why)))?
+        .with_no_client_auth();
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_tls_config(tls_config)
+        .https_only()
+        .enable_http1()
+        .build();
+
+    Ok(CLIENT.get_or_init(|| HyperClient::builder(TokioExecutor::new()).build(connector)))
+}
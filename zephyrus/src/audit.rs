@@ -0,0 +1,202 @@
+use crate::argument::{is_sensitive, CommandArgument};
+use crate::twilight_exports::{
+    ChannelMarker, CommandDataOption, CommandOptionValue, GuildMarker, Id, Interaction,
+    InteractionData, UserMarker,
+};
+use async_trait::async_trait;
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter, Result as FmtResult},
+    sync::atomic::{AtomicBool, Ordering},
+    time::SystemTime,
+};
+
+/// The value shown instead of the value of redacted arguments.
+pub const REDACTED: &str = "[redacted]";
+
+/// The outcome of an audited command invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOutcome {
+    /// The command was executed and returned successfully.
+    Success,
+    /// The command was executed and returned an error.
+    Failed,
+    /// The command was not executed because the before hook or a check rejected it.
+    Rejected,
+}
+
+/// A record of a command invocation.
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    /// The time the record was created at.
+    pub timestamp: SystemTime,
+    /// The user who invoked the command.
+    pub user_id: Option<Id<UserMarker>>,
+    /// The guild the command was invoked in.
+    pub guild_id: Option<Id<GuildMarker>>,
+    /// The channel the command was invoked in.
+    pub channel_id: Option<Id<ChannelMarker>>,
+    /// The path of the invoked command.
+    pub command: String,
+    /// The name and value of every argument provided, with the redacted and
+    /// [sensitive](CommandArgument::sensitive) ones replaced by [REDACTED](REDACTED).
+    pub arguments: Vec<(String, String)>,
+    /// The outcome of the invocation.
+    pub outcome: AuditOutcome,
+}
+
+impl Display for AuditRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "/{}", self.command)?;
+        for (name, value) in &self.arguments {
+            write!(f, " {}:{}", name, value)?;
+        }
+
+        let user = self.user_id.map(|id| id.to_string());
+        let guild = self.guild_id.map(|id| id.to_string());
+        let channel = self.channel_id.map(|id| id.to_string());
+        write!(
+            f,
+            " by {} in guild {} channel {}: {:?}",
+            user.as_deref().unwrap_or("unknown"),
+            guild.as_deref().unwrap_or("none"),
+            channel.as_deref().unwrap_or("unknown"),
+            self.outcome
+        )
+    }
+}
+
+/// A destination for [audit records](AuditRecord), such as a file or a database.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    /// Stores the given record.
+    async fn record(&self, record: AuditRecord);
+}
+
+/// An [audit sink](AuditSink) printing the records to the standard output.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink;
+
+#[async_trait]
+impl AuditSink for StdoutSink {
+    async fn record(&self, record: AuditRecord) {
+        println!("{}", record);
+    }
+}
+
+/// An audit layer recording every command invocation into an [audit sink](AuditSink).
+pub struct AuditLog {
+    sink: Box<dyn AuditSink>,
+    redacted: HashSet<&'static str>,
+    enabled: AtomicBool,
+}
+
+impl AuditLog {
+    /// Creates a new enabled audit layer emitting to the given sink.
+    pub fn new(sink: impl AuditSink + 'static) -> Self {
+        Self {
+            sink: Box::new(sink),
+            redacted: HashSet::new(),
+            enabled: AtomicBool::new(true),
+        }
+    }
+
+    /// Redacts the value of the arguments with the given name in all commands.
+    pub fn redact(mut self, argument: &'static str) -> Self {
+        self.redacted.insert(argument);
+        self
+    }
+
+    /// Checks whether the audit layer is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables the audit layer.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Records the invocation of the given command, having the given arguments, if the audit
+    /// layer is enabled.
+    pub(crate) async fn record<D>(
+        &self,
+        command: String,
+        interaction: &Interaction,
+        arguments: &[CommandArgument<D>],
+        outcome: AuditOutcome,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let options = match interaction.data.as_ref() {
+            Some(InteractionData::ApplicationCommand(data)) => data.options.as_slice(),
+            _ => &[],
+        };
+
+        let record = AuditRecord {
+            timestamp: SystemTime::now(),
+            user_id: interaction.author_id(),
+            guild_id: interaction.guild_id,
+            channel_id: interaction.channel.as_ref().map(|channel| channel.id),
+            command,
+            arguments: options
+                .iter()
+                .map(|option| self.argument(option, arguments))
+                .collect(),
+            outcome,
+        };
+
+        self.sink.record(record).await;
+    }
+
+    /// Gets the name and the displayed value of the given option, provided to a command having
+    /// the given arguments.
+    fn argument<D>(&self, option: &CommandDataOption, arguments: &[CommandArgument<D>]) -> (String, String) {
+        let value = if self.redacted.contains(option.name.as_str())
+            || is_sensitive(arguments, &option.name)
+        {
+            REDACTED.to_string()
+        } else {
+            display_value(&option.value)
+        };
+
+        (option.name.clone(), value)
+    }
+}
+
+/// Replaces the values of the given options named like one of the given sensitive arguments by
+/// [REDACTED], including the options of subcommands and the input being autocompleted.
+pub(crate) fn redact_options(options: &mut [CommandDataOption], sensitive: &[&str]) {
+    for option in options {
+        match &mut option.value {
+            CommandOptionValue::SubCommand(options) | CommandOptionValue::SubCommandGroup(options) => {
+                redact_options(options, sensitive)
+            }
+            CommandOptionValue::Focused(input, _) if sensitive.contains(&option.name.as_str()) => {
+                *input = REDACTED.to_string();
+            }
+            value if sensitive.contains(&option.name.as_str()) => {
+                *value = CommandOptionValue::String(REDACTED.to_string());
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Gets the value of an argument as displayed in records and reports.
+pub(crate) fn display_value(value: &CommandOptionValue) -> String {
+    match value {
+        CommandOptionValue::Attachment(id) => id.to_string(),
+        CommandOptionValue::Boolean(b) => b.to_string(),
+        CommandOptionValue::Channel(id) => id.to_string(),
+        CommandOptionValue::Integer(i) => i.to_string(),
+        CommandOptionValue::Mentionable(id) => id.to_string(),
+        CommandOptionValue::Number(n) => n.to_string(),
+        CommandOptionValue::Role(id) => id.to_string(),
+        CommandOptionValue::String(s) => s.clone(),
+        CommandOptionValue::User(id) => id.to_string(),
+        other => format!("{:?}", other),
+    }
+}
@@ -1,13 +1,43 @@
 use crate::{
-    command::{Command, CommandMap},
+    audit::AuditLog,
+    button::Button,
+    command::{Command, CommandMap, CommandSet, DefaultError},
+    concurrency::GlobalConcurrencyLimit,
+    cooldown::{self, CooldownStore},
+    entry_point::EntryPoint,
+    event::{FrameworkEvent, Observer},
     framework::Framework,
     group::{GroupParentBuilder, ParentGroupMap},
-    hook::{AfterHook, BeforeHook},
-    twilight_exports::{ApplicationMarker, Client, Id},
+    localization::Localizer,
+    help::HelpCommand,
+    parse::ParseError,
+    retry::RetryPolicy,
+    template::{DynamicCommandSource, DynamicCommands},
+    waiter::WaiterLimits,
+    hook::{
+        AfterHook, BeforeHook, ErrorHook, InterceptorHook, UnhandledComponentHook,
+        UnhandledInteractionHook, UnknownCommandHook,
+    },
+    twilight_exports::{
+        AllowedMentions, ApplicationMarker, Client, ClientBuilder, GuildMarker, Id, InteractionResponse,
+        InteractionResponseData, InteractionResponseType, MessageFlags,
+    },
 };
 #[cfg(feature = "rc")]
 use std::rc::Rc;
-use std::{ops::Deref, sync::Arc};
+use std::{error::Error, ops::Deref, sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+/// Where the commands are registered when building the framework using
+/// [build_and_register](FrameworkBuilder::build_and_register).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// The commands are registered globally.
+    Global,
+    /// The commands are registered in each of the given guilds, which is useful during
+    /// development since guild commands are updated instantly.
+    Guilds(Vec<Id<GuildMarker>>),
+}
 
 /// A wrapper around twilight's http client allowing the user to decide how to provide it to the framework.
 #[allow(clippy::large_enum_variant)]
@@ -20,6 +50,18 @@ pub enum WrappedClient {
 }
 
 impl WrappedClient {
+    /// Creates a client authenticated with the given bot token, configuring it with the given
+    /// function, such as setting a proxy or a timeout.
+    ///
+    /// A client without any configuration can be created by giving the token to the framework
+    /// directly.
+    pub fn with_config<F>(token: impl Into<String>, config: F) -> Self
+    where
+        F: FnOnce(ClientBuilder) -> ClientBuilder,
+    {
+        Self::from(config(Client::builder().token(token.into())).build())
+    }
+
     pub fn inner(&self) -> &Client {
         match self {
             Self::Arc(c) => c,
@@ -30,6 +72,17 @@ impl WrappedClient {
         }
     }
 
+    /// Gets a shared handle to the client, which can be moved into spawned tasks.
+    ///
+    /// Returns `None` unless the client is stored in an [Arc](WrappedClient::Arc), which is the
+    /// case of clients given to the framework as an owned [Client] or an `Arc<Client>`.
+    pub fn shared(&self) -> Option<Arc<Client>> {
+        match self {
+            Self::Arc(c) => Some(Arc::clone(c)),
+            _ => None,
+        }
+    }
+
     /// Casts the [client](WrappedClient) into T if it's [Boxed](WrappedClient::Boxed)
     ///
     /// **SAFETY: The caller must ensure the type given is the same as the boxed one.**
@@ -50,7 +103,19 @@ impl WrappedClient {
 
 impl From<Client> for WrappedClient {
     fn from(c: Client) -> Self {
-        WrappedClient::Raw(c)
+        WrappedClient::Arc(Arc::new(c))
+    }
+}
+
+impl From<String> for WrappedClient {
+    fn from(token: String) -> Self {
+        Self::from(Client::new(token))
+    }
+}
+
+impl From<&str> for WrappedClient {
+    fn from(token: &str) -> Self {
+        Self::from(token.to_string())
     }
 }
 
@@ -77,7 +142,7 @@ impl From<Rc<Client>> for WrappedClient {
 pub(crate) type FnPointer<T> = fn() -> T;
 
 /// A builder used to set all options before framework initialization.
-pub struct FrameworkBuilder<D> {
+pub struct FrameworkBuilder<D, E = DefaultError> {
     /// The http client used by the framework.
     pub http_client: WrappedClient,
     /// The application id of the client.
@@ -85,16 +150,68 @@ pub struct FrameworkBuilder<D> {
     /// Data that will be available to all commands.
     pub data: D,
     /// The actual commands, only the simple ones.
-    pub commands: CommandMap<D>,
+    pub commands: CommandMap<D, E>,
     /// All groups containing commands.
-    pub groups: ParentGroupMap<D>,
+    pub groups: ParentGroupMap<D, E>,
+    /// The buttons handling message components, checked in registration order.
+    pub buttons: Vec<Button<D, E>>,
     /// A hook executed before any command.
     pub before: Option<BeforeHook<D>>,
     /// A hook executed after command's completion.
-    pub after: Option<AfterHook<D>>,
+    pub after: Option<AfterHook<D, E>>,
+    /// A hook executed when the framework fails to execute a command.
+    pub on_error: Option<ErrorHook<D>>,
+    /// A hook executed before routing every interaction.
+    pub interceptor: Option<InterceptorHook<D>>,
+    /// A hook executed when an interaction refers to an unknown command.
+    pub unknown_command: Option<UnknownCommandHook<D>>,
+    /// A hook executed when a message component isn't handled by any button.
+    pub unhandled_component: Option<UnhandledComponentHook<D>>,
+    /// A hook executed when an interaction of a type the framework doesn't handle is received.
+    pub unhandled_interaction: Option<UnhandledInteractionHook<D>>,
+    /// The response sent when a command panics.
+    pub panic_response: Option<InteractionResponse>,
+    /// The response sent when a command reaches its concurrency limit.
+    pub busy_response: InteractionResponse,
+    /// The policy used to retry the http requests made by the framework.
+    pub retry_policy: RetryPolicy,
+    /// The limits applied to the waiters registered in the framework.
+    pub waiter_limits: WaiterLimits,
+    /// The storage of the active cooldowns of the commands.
+    pub cooldown_store: Option<Box<dyn CooldownStore>>,
+    /// Creates the response sent when a command is used while on cooldown, given the time
+    /// remaining for the cooldown to end.
+    pub cooldown_response: fn(Duration) -> InteractionResponse,
+    /// The allowed mentions applied to the responses which don't set their own ones.
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// The maximum number of concurrent command executions across the framework.
+    pub global_concurrency: Option<GlobalConcurrencyLimit>,
+    /// The help command generated by the framework.
+    pub help: Option<HelpCommand>,
+    /// The primary entry point command of the application.
+    pub entry_point: Option<EntryPoint<D, E>>,
+    /// The observers receiving the events emitted by the framework.
+    pub observers: Vec<Observer>,
+    /// The audit layer recording command invocations.
+    pub audit: Option<AuditLog>,
+    /// The commands loaded at runtime out of a source.
+    pub dynamic_commands: Option<DynamicCommands>,
+    /// Where the commands are registered when the framework is built.
+    pub auto_register: Option<Target>,
+    /// The time after which the framework defers the interactions not yet responded.
+    pub auto_defer: Option<Duration>,
+    /// Whether the framework sends the responses returned by commands.
+    pub send_responses: bool,
+    /// The source of the translated strings used to localize responses.
+    pub localizer: Option<Box<dyn Localizer>>,
+    /// The locale used when a string isn't translated to the locale of an interaction.
+    pub fallback_locale: Option<String>,
 }
 
-impl<D: Sized> FrameworkBuilder<D> {
+impl<D: Sized, E> FrameworkBuilder<D, E>
+where
+    E: From<ParseError>,
+{
     /// Creates a new [Builder](self::FrameworkBuilder).
     pub fn new(
         http_client: impl Into<WrappedClient>,
@@ -107,11 +224,60 @@ impl<D: Sized> FrameworkBuilder<D> {
             data,
             commands: Default::default(),
             groups: Default::default(),
+            buttons: Vec::new(),
             before: None,
             after: None,
+            on_error: None,
+            interceptor: None,
+            unknown_command: None,
+            unhandled_component: None,
+            unhandled_interaction: None,
+            panic_response: None,
+            busy_response: InteractionResponse {
+                kind: InteractionResponseType::ChannelMessageWithSource,
+                data: Some(InteractionResponseData {
+                    content: Some(String::from("This command is busy, try again later")),
+                    flags: Some(MessageFlags::EPHEMERAL),
+                    ..Default::default()
+                }),
+            },
+            retry_policy: RetryPolicy::none(),
+            waiter_limits: WaiterLimits::default(),
+            cooldown_store: None,
+            cooldown_response: cooldown::default_response,
+            allowed_mentions: None,
+            global_concurrency: None,
+            help: None,
+            entry_point: None,
+            observers: Vec::new(),
+            audit: None,
+            dynamic_commands: None,
+            auto_register: None,
+            auto_defer: None,
+            send_responses: true,
+            localizer: None,
+            fallback_locale: None,
         }
     }
 
+    /// Creates a new [Builder](self::FrameworkBuilder), fetching the id of the application the
+    /// token of the given client belongs to instead of requiring it.
+    pub async fn from_current_application(
+        http_client: impl Into<WrappedClient>,
+        data: D,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let http_client = http_client.into();
+        let application_id = http_client
+            .inner()
+            .current_user_application()
+            .await?
+            .model()
+            .await?
+            .id;
+
+        Ok(Self::new(http_client, application_id, data))
+    }
+
     /// Set the hook that will be executed before commands.
     pub fn before(mut self, fun: FnPointer<BeforeHook<D>>) -> Self {
         self.before = Some(fun());
@@ -119,40 +285,291 @@ impl<D: Sized> FrameworkBuilder<D> {
     }
 
     /// Set the hook that will be executed after command's completion.
-    pub fn after(mut self, fun: FnPointer<AfterHook<D>>) -> Self {
+    pub fn after(mut self, fun: FnPointer<AfterHook<D, E>>) -> Self {
         self.after = Some(fun());
         self
     }
 
+    /// Set the hook that will be executed when the framework fails to execute a command, such as
+    /// when the command panics.
+    pub fn on_error(mut self, fun: FnPointer<ErrorHook<D>>) -> Self {
+        self.on_error = Some(fun());
+        self
+    }
+
+    /// Set the hook that will be executed before routing every interaction, which can modify the
+    /// interaction or consume it so the framework doesn't process it.
+    pub fn interceptor(mut self, fun: FnPointer<InterceptorHook<D>>) -> Self {
+        self.interceptor = Some(fun());
+        self
+    }
+
+    /// Set the hook that will be executed when an interaction refers to a command the framework
+    /// doesn't know, receiving the path used to invoke it. This allows answering the users of
+    /// commands which were removed or renamed but are still registered in discord.
+    pub fn unknown_command(mut self, fun: FnPointer<UnknownCommandHook<D>>) -> Self {
+        self.unknown_command = Some(fun());
+        self
+    }
+
+    /// Set the hook that will be executed when a message component is used but neither a waiter
+    /// nor a [button](crate::button::Button) handles it, receiving its custom id. This allows
+    /// answering the users of components which expired, such as the ones of messages sent before
+    /// a restart.
+    pub fn unhandled_component(mut self, fun: FnPointer<UnhandledComponentHook<D>>) -> Self {
+        self.unhandled_component = Some(fun());
+        self
+    }
+
+    /// Set the hook that will be executed when an interaction of a type the framework doesn't
+    /// handle is received, such as a modal submit, instead of silently dropping it.
+    pub fn unhandled_interaction(mut self, fun: FnPointer<UnhandledInteractionHook<D>>) -> Self {
+        self.unhandled_interaction = Some(fun());
+        self
+    }
+
+    /// Set the response sent to the user when a command panics, no response is sent by default.
+    pub fn panic_response(mut self, response: InteractionResponse) -> Self {
+        self.panic_response = Some(response);
+        self
+    }
+
+    /// Set the response sent to the user when a command reaches its
+    /// [concurrency limit](crate::concurrency::ConcurrencyLimit).
+    pub fn busy_response(mut self, response: InteractionResponse) -> Self {
+        self.busy_response = response;
+        self
+    }
+
+    /// Set the [policy](RetryPolicy) used to retry the http requests made by the framework
+    /// itself when they fail with a transient error, such as interaction responses and command
+    /// registration. No request is retried by default.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Set the [limits](WaiterLimits) of the waiters waiting for an interaction at the same
+    /// time, so a command registering waiters repeatedly can't grow them without bound. Waiters
+    /// aren't limited by default.
+    pub fn waiter_limits(mut self, limits: WaiterLimits) -> Self {
+        self.waiter_limits = limits;
+        self
+    }
+
+    /// Set the [storage](CooldownStore) of the active [cooldowns](crate::cooldown::Cooldown),
+    /// which defaults to an [in memory](crate::cooldown::InMemoryCooldownStore) one.
+    ///
+    /// Bots running more than one process should use a store backed by a shared database, so
+    /// every process sees the cooldowns started by the others.
+    pub fn cooldown_store(mut self, store: impl CooldownStore + 'static) -> Self {
+        self.cooldown_store = Some(Box::new(store));
+        self
+    }
+
+    /// Set the function creating the response sent to the user when a command is used while on
+    /// [cooldown](crate::cooldown::Cooldown), which receives the time remaining for it to end.
+    pub fn cooldown_response(mut self, fun: fn(Duration) -> InteractionResponse) -> Self {
+        self.cooldown_response = fun;
+        self
+    }
+
+    /// Set the allowed mentions applied to every response sent by the framework and to the
+    /// followups created using [SlashContext::followup](crate::context::SlashContext::followup),
+    /// unless they set their own ones.
+    ///
+    /// This prevents user provided content from pinging `@everyone` or roles by accident.
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Set the maximum number of concurrent command executions across the framework, rejected
+    /// executions are answered with the [busy response](Self::busy_response).
+    pub fn global_concurrency(mut self, limit: GlobalConcurrencyLimit) -> Self {
+        self.global_concurrency = Some(limit);
+        self
+    }
+
     /// Registers a new command.
-    pub fn command(mut self, fun: FnPointer<Command<D>>) -> Self {
-        let cmd = fun();
-        if self.commands.contains_key(cmd.name) || self.groups.contains_key(cmd.name) {
+    pub fn command(self, fun: FnPointer<Command<D, E>>) -> Self {
+        self.insert_command(fun())
+    }
+
+    /// Registers an already built command, such as one built at runtime using a closure.
+    pub fn insert_command(mut self, cmd: Command<D, E>) -> Self {
+        if self.commands.contains_key(&cmd.name) || self.groups.contains_key(&cmd.name) {
             panic!("{} already registered", cmd.name);
         }
-        self.commands.insert(cmd.name, cmd);
+        cmd.validate(&cmd.name);
+        self.commands.insert(cmd.name.clone(), cmd);
+        self
+    }
+
+    /// Registers all the commands of the given [command set](CommandSet).
+    pub fn command_set<T: CommandSet<D, E>>(mut self) -> Self {
+        for cmd in T::commands() {
+            self = self.insert_command(cmd);
+        }
         self
     }
 
     /// Registers a new group of commands.
     pub fn group<F>(mut self, fun: F) -> Self
     where
-        F: FnOnce(&mut GroupParentBuilder<D>) -> &mut GroupParentBuilder<D>,
+        F: FnOnce(&mut GroupParentBuilder<D, E>) -> &mut GroupParentBuilder<D, E>,
     {
         let mut builder = GroupParentBuilder::new();
         fun(&mut builder);
         let group = builder.build();
 
-        if self.commands.contains_key(group.name) || self.groups.contains_key(group.name) {
+        if self.commands.contains_key(&group.name) || self.groups.contains_key(&group.name) {
             panic!("{} already registered", group.name);
         }
-        self.groups.insert(group.name, group);
+        self.groups.insert(group.name.clone(), group);
+
+        self
+    }
+
+    /// Registers a new button, executed when a component whose custom id matches its template
+    /// is used.
+    pub fn button(self, fun: FnPointer<Button<D, E>>) -> Self {
+        self.insert_button(fun())
+    }
+
+    /// Registers an already built button.
+    pub fn insert_button(mut self, button: Button<D, E>) -> Self {
+        if self.buttons.iter().any(|b| b.template == button.template) {
+            panic!("{} already registered", button.template);
+        }
+        self.buttons.push(button);
+        self
+    }
+
+    /// Enables the [help command](HelpCommand) generated by the framework.
+    pub fn help_command(mut self, help: HelpCommand) -> Self {
+        self.help = Some(help);
+        self
+    }
+
+    /// Sets the [entry point](EntryPoint) command used to launch the application's activity.
+    pub fn entry_point(mut self, entry_point: EntryPoint<D, E>) -> Self {
+        self.entry_point = Some(entry_point);
+        self
+    }
+
+    /// Adds an observer receiving the [events](FrameworkEvent) emitted by the framework at every
+    /// stage of an interaction dispatch.
+    pub fn observer<F>(mut self, fun: F) -> Self
+    where
+        F: Fn(&FrameworkEvent<'_>) + Send + Sync + 'static,
+    {
+        self.observers.push(Box::new(fun));
+        self
+    }
+
+    /// Sets the [audit layer](AuditLog) recording every command invocation.
+    pub fn audit(mut self, audit: AuditLog) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Sets the [source](DynamicCommandSource) of the templated commands added, updated and
+    /// removed at runtime, see [reload_dynamic_commands](Framework::reload_dynamic_commands).
+    pub fn dynamic_commands(mut self, source: impl DynamicCommandSource + 'static) -> Self {
+        self.dynamic_commands = Some(DynamicCommands::new(source));
+        self
+    }
+
+    /// Sets where the commands are registered when the framework is built using
+    /// [build_and_register](Self::build_and_register).
+    pub fn auto_register(mut self, target: Target) -> Self {
+        self.auto_register = Some(target);
+        self
+    }
+
+    /// Makes the framework defer the interactions whose command or button didn't finish within
+    /// the given threshold, so the interaction doesn't fail when the handler is slow. The
+    /// response returned by the handler is then used to edit the deferred response.
+    ///
+    /// Discord requires interactions to be responded within 3 seconds, so the threshold should
+    /// be lower than that, usually around 2 seconds. This requires a tokio runtime with the time
+    /// driver enabled.
+    pub fn auto_defer(mut self, threshold: Duration) -> Self {
+        self.auto_defer = Some(threshold);
+        self
+    }
+
+    /// Sets whether the framework sends the responses returned by commands, which it does by
+    /// default.
+    ///
+    /// Bots responding their interactions manually through the interaction client should disable
+    /// it, as otherwise the interaction is responded twice, discord rejecting the second response.
+    /// Responses sent by the framework on its own, such as the busy or cooldown ones, are still
+    /// sent.
+    pub fn send_responses(mut self, send: bool) -> Self {
+        self.send_responses = send;
+        self
+    }
+
+    /// Sets the [localizer](Localizer) providing the translated strings used by
+    /// [SlashContext::t](crate::context::SlashContext::t).
+    pub fn localizer(mut self, localizer: impl Localizer + 'static) -> Self {
+        self.localizer = Some(Box::new(localizer));
+        self
+    }
 
+    /// Sets the locale used when a string isn't translated to the locale of an interaction,
+    /// which defaults to [en-US](crate::localization::DEFAULT_FALLBACK_LOCALE).
+    pub fn fallback_locale(mut self, locale: impl Into<String>) -> Self {
+        self.fallback_locale = Some(locale.into());
         self
     }
 
     /// Builds the framework, returning a [Framework](crate::framework::Framework).
-    pub fn build(self) -> Framework<D> {
+    ///
+    /// No commands are registered, even if [auto_register](Self::auto_register) was set, use
+    /// [build_and_register](Self::build_and_register) for that.
+    pub fn build(self) -> Framework<D, E> {
+        if let Some(help) = &self.help {
+            if self.commands.contains_key(help.name) || self.groups.contains_key(help.name) {
+                panic!("{} already registered", help.name);
+            }
+        }
+        if self.auto_register.is_some() {
+            warn!("Commands are not registered automatically by build, use build_and_register");
+        }
+
         Framework::from_builder(self)
     }
+
+    /// Builds the framework and registers its commands where set by
+    /// [auto_register](Self::auto_register), if set.
+    pub async fn build_and_register(
+        mut self,
+    ) -> Result<Framework<D, E>, Box<dyn Error + Send + Sync>> {
+        let target = self.auto_register.take();
+        let framework = self.build();
+
+        if let Some(dynamic) = &framework.dynamic_commands {
+            let changes = dynamic.reload().await?;
+            info!("Loaded {} dynamic commands", changes.added.len());
+        }
+
+        match &target {
+            Some(Target::Global) => {
+                let report = framework.register_global_commands().await?;
+                info!("Registered global commands: {}", report);
+            }
+            Some(Target::Guilds(guilds)) => {
+                for guild_id in guilds {
+                    let report = framework.register_guild_commands(*guild_id).await?;
+                    info!("Registered commands in guild {}: {}", guild_id, report);
+                }
+            }
+            None => (),
+        }
+
+        Ok(framework)
+    }
 }
@@ -1,46 +1,353 @@
-use std::collections::HashMap;
-
-use crate::{command::CommandFun, twilight_exports::Permissions};
-/// A map of [buttons](self::Button).
-pub type ButtonMap<D> = HashMap<&'static str, Button<D>>;
-
-/// A button executed by the framework.
-pub struct Button<D> {
-    /// The name of the button.
-    pub name: &'static str,
-    /// The description of the buttons.
-    pub description: &'static str,
-    /// A pointer to this button function.
-    pub fun: CommandFun<D>,
-    /// The required permissions to use this button
-    pub required_permissions: Option<Permissions>,
-}
-
-impl<D> Button<D> {
-    /// Creates a new button.
-    pub fn new(fun: CommandFun<D>) -> Self {
+use crate::{
+    compat,
+    command::{Command, CommandResult, DefaultError},
+    concurrency::ConcurrencyLimit,
+    context::SlashContext,
+    hook::BeforeHook,
+    parse::ParseError,
+    twilight_exports::{
+        Button as ButtonComponent, ButtonStyle, EmojiReactionType, Permissions,
+    },
+    BoxFuture,
+};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+/// A piece of a [custom id template](CustomIdTemplate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Text which must appear as is in the custom id.
+    Literal(String),
+    /// A placeholder matching any non empty text.
+    Placeholder(String),
+}
+
+/// A template of the custom ids handled by a [button](Button), like `role:{role_id}:{action}`.
+///
+/// Every `{name}` is a placeholder matching any non empty text, which ends at the first
+/// occurrence of the text following it. This means the values given to a placeholder can't
+/// contain the text following it in the template, which is usually a separator like `:`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomIdTemplate {
+    template: Cow<'static, str>,
+    segments: Vec<Segment>,
+}
+
+impl CustomIdTemplate {
+    /// Parses the given template.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the template has unbalanced braces, empty or repeated placeholders or two
+    /// placeholders next to each other.
+    pub fn new(template: impl Into<Cow<'static, str>>) -> Self {
+        let template = template.into();
+        let segments = match parse_template(&template) {
+            Ok(segments) => segments,
+            Err(why) => panic!("Invalid custom id template {}: {}", template, why),
+        };
+
+        Self { template, segments }
+    }
+
+    /// Gets the template as a string.
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// Gets the names of the placeholders of the template, in order of appearance.
+    pub fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Placeholder(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Checks whether the given custom id matches this template.
+    pub fn matches(&self, custom_id: &str) -> bool {
+        self.extract(custom_id).is_some()
+    }
+
+    /// Extracts the values given to the placeholders of this template out of the given custom
+    /// id, in the same order as the [placeholders](Self::placeholders), or `None` if the custom
+    /// id doesn't match the template.
+    pub fn extract<'a>(&self, mut custom_id: &'a str) -> Option<Vec<&'a str>> {
+        let mut values = Vec::new();
+        let mut segments = self.segments.iter().peekable();
+
+        while let Some(segment) = segments.next() {
+            match segment {
+                Segment::Literal(literal) => custom_id = custom_id.strip_prefix(literal.as_str())?,
+                Segment::Placeholder(_) => {
+                    let end = match segments.peek() {
+                        Some(Segment::Literal(next)) => custom_id.find(next.as_str())?,
+                        _ => custom_id.len(),
+                    };
+                    if end == 0 {
+                        return None;
+                    }
+                    values.push(&custom_id[..end]);
+                    custom_id = &custom_id[end..];
+                }
+            }
+        }
+
+        if custom_id.is_empty() {
+            Some(values)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for CustomIdTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+/// Splits the given template into its segments.
+fn parse_template(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while !rest.is_empty() {
+        match rest.find(['{', '}']) {
+            Some(start) if rest[start..].starts_with('}') => {
+                return Err(String::from("Unexpected `}`"));
+            }
+            Some(start) => {
+                if start > 0 {
+                    segments.push(Segment::Literal(rest[..start].to_string()));
+                }
+                let end = rest[start..]
+                    .find('}')
+                    .map(|end| start + end)
+                    .ok_or_else(|| String::from("Unclosed `{`"))?;
+                let name = &rest[start + 1..end];
+
+                if name.is_empty() || name.contains('{') {
+                    return Err(String::from("Placeholders must have a name"));
+                }
+                if matches!(segments.last(), Some(Segment::Placeholder(_))) {
+                    return Err(String::from("Placeholders must be separated by text"));
+                }
+                if segments.contains(&Segment::Placeholder(name.to_string())) {
+                    return Err(format!("Placeholder {} is repeated", name));
+                }
+
+                segments.push(Segment::Placeholder(name.to_string()));
+                rest = &rest[end + 1..];
+            }
+            None => {
+                segments.push(Segment::Literal(rest.to_string()));
+                rest = "";
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Parses the value given to a placeholder of a [custom id template](CustomIdTemplate).
+pub fn parse_placeholder<T>(name: &str, value: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse().map_err(|error: T::Err| ParseError::Parsing {
+        argument_name: name.to_string(),
+        required: true,
+        type_: std::any::type_name::<T>().to_string(),
+        error: error.to_string(),
+    })
+}
+
+/// A button executed by the framework when a component whose custom id matches its
+/// [template](CustomIdTemplate) is used.
+pub struct Button<D, E = DefaultError> {
+    /// The template of the custom ids handled by this button.
+    pub template: CustomIdTemplate,
+    /// The command executed when the button is used, named after the template.
+    pub command: Command<D, E>,
+    /// The style of the button.
+    pub style: ButtonStyle,
+    /// The text shown in the button.
+    pub label: Option<Cow<'static, str>>,
+    /// The emoji shown in the button.
+    pub emoji: Option<EmojiReactionType>,
+    /// Whether the button is disabled.
+    pub disabled: bool,
+}
+
+impl<D, E> Debug for Button<D, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Button")
+            .field("template", &self.template.as_str())
+            .field("command", &self.command)
+            .field("style", &self.style)
+            .field("label", &self.label)
+            .field("emoji", &self.emoji)
+            .field("disabled", &self.disabled)
+            .finish()
+    }
+}
+
+impl<D, E> Button<D, E> {
+    /// Creates a new button handling the custom ids matching the given template.
+    pub fn new<F>(template: impl Into<Cow<'static, str>>, fun: F) -> Self
+    where
+        F: for<'a> Fn(&'a SlashContext<'a, D>) -> BoxFuture<'a, CommandResult<E>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let template = CustomIdTemplate::new(template);
+        let command = Command::new(fun).name(template.as_str().to_string());
+
         Self {
-            name: Default::default(),
-            description: Default::default(),
-            fun,
-            required_permissions: Default::default(),
+            template,
+            command,
+            style: ButtonStyle::Primary,
+            label: None,
+            emoji: None,
+            disabled: false,
         }
     }
 
-    /// Sets the button name.
-    pub fn name(mut self, name: &'static str) -> Self {
-        self.name = name;
+    /// Sets the style of the button.
+    pub fn style(mut self, style: ButtonStyle) -> Self {
+        self.style = style;
         self
     }
 
-    /// Sets the button description.
-    pub fn description(mut self, description: &'static str) -> Self {
-        self.description = description;
+    /// Sets the text shown in the button.
+    pub fn label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.label = Some(label.into());
         self
     }
 
+    /// Sets the emoji shown in the button.
+    pub fn emoji(mut self, emoji: EmojiReactionType) -> Self {
+        self.emoji = Some(emoji);
+        self
+    }
+
+    /// Sets whether the button is disabled.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Creates the component shown in discord for this button, using the given custom id, which
+    /// should match the [template](Self::template) of the button.
+    pub fn component(&self, custom_id: impl Into<String>) -> ButtonComponent {
+        let mut button = compat::button(self.style, self.label.as_deref().map(String::from));
+        button.custom_id = Some(custom_id.into());
+        button.emoji = self.emoji.clone();
+        button.disabled = self.disabled;
+        button
+    }
+
+    /// Sets the permissions the member using the button must have, the button is ignored
+    /// otherwise.
     pub fn required_permissions(mut self, permissions: Permissions) -> Self {
-        self.required_permissions = Some(permissions);
+        self.command = self.command.required_permissions(permissions);
+        self
+    }
+
+    /// Sets the checks executed before the button.
+    pub fn checks(mut self, checks: Vec<BeforeHook<D>>) -> Self {
+        self.command = self.command.checks(checks);
+        self
+    }
+
+    /// Sets the maximum number of concurrent executions of the button.
+    pub fn max_concurrency(mut self, limit: ConcurrencyLimit) -> Self {
+        self.command = self.command.max_concurrency(limit);
+        self
+    }
+
+    /// Sets whether the responses sent by the framework for this button should only be seen by
+    /// the user who used it.
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.command = self.command.ephemeral(ephemeral);
+        self
+    }
+
+    /// Attaches the given key-value pair to the button.
+    pub fn meta(mut self, key: &'static str, value: &'static str) -> Self {
+        self.command = self.command.meta(key, value);
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_placeholder, parse_template, CustomIdTemplate};
+    use crate::parse::ParseError;
+
+    #[test]
+    fn extracts_placeholders() {
+        let template = CustomIdTemplate::new("role:{role_id}:{action}");
+        assert_eq!(template.placeholders().collect::<Vec<_>>(), ["role_id", "action"]);
+        assert_eq!(template.extract("role:123:add"), Some(vec!["123", "add"]));
+        assert_eq!(template.extract("role:123:add:more"), Some(vec!["123", "add:more"]));
+        assert!(template.matches("role:1:remove"));
+    }
+
+    #[test]
+    fn placeholders_end_at_the_following_text() {
+        let template = CustomIdTemplate::new("{a}-{b}");
+        assert_eq!(template.extract("-5-10"), None);
+        assert_eq!(template.extract("5-10-15"), Some(vec!["5", "10-15"]));
+
+        let template = CustomIdTemplate::new("page:{n}:end");
+        assert_eq!(template.extract("page:3:end"), Some(vec!["3"]));
+        assert_eq!(template.extract("page:3:end:"), None);
+    }
+
+    #[test]
+    fn literal_templates() {
+        let template = CustomIdTemplate::new("confirm");
+        assert_eq!(template.placeholders().count(), 0);
+        assert_eq!(template.extract("confirm"), Some(vec![]));
+        assert!(!template.matches("confirmed"));
+        assert!(!template.matches("cancel"));
+        assert_eq!(template.to_string(), "confirm");
+    }
+
+    #[test]
+    fn rejects_non_matching_custom_ids() {
+        let template = CustomIdTemplate::new("role:{role_id}:{action}");
+        for custom_id in ["", "role:", "role::add", "role:123:", "role:123", "user:123:add"] {
+            assert!(!template.matches(custom_id), "{:?} should not match", custom_id);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        for template in ["a{", "a}", "{}", "{a}{b}", "{a}:{a}", "{a{b}}", "x:{"] {
+            assert!(parse_template(template).is_err(), "{:?} should be rejected", template);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid custom id template")]
+    fn new_panics_on_malformed_templates() {
+        CustomIdTemplate::new("{a}{b}");
+    }
+
+    #[test]
+    fn parses_placeholder_values() {
+        assert_eq!(parse_placeholder::<u64>("id", "42").unwrap(), 42);
+        assert_eq!(parse_placeholder::<i64>("id", "-5").unwrap(), -5);
+        match parse_placeholder::<u64>("id", "abc") {
+            Err(ParseError::Parsing { argument_name, .. }) => assert_eq!(argument_name, "id"),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::prelude::*;
+use crate::twilight_exports::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::ops::Deref;
+
+macro_rules! channel_id {
+    ($($(#[$meta:meta])* $name:ident => [$($kind:ident),+]),* $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub struct $name(pub Id<ChannelMarker>);
+
+            impl $name {
+                /// The channel types this argument accepts.
+                pub const KINDS: &'static [ChannelType] = &[$(ChannelType::$kind),+];
+
+                /// Gets the id of the channel.
+                pub fn id(self) -> Id<ChannelMarker> {
+                    self.0
+                }
+            }
+
+            impl Deref for $name {
+                type Target = Id<ChannelMarker>;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl Display for $name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                    Display::fmt(&self.0, f)
+                }
+            }
+
+            impl From<$name> for Id<ChannelMarker> {
+                fn from(channel: $name) -> Self {
+                    channel.0
+                }
+            }
+
+            #[async_trait]
+            impl<T: Send + Sync> Parse<T> for $name {
+                async fn parse(
+                    http_client: &WrappedClient,
+                    data: &T,
+                    value: Option<&CommandOptionValue>,
+                ) -> Result<Self, ParseError> {
+                    <Id<ChannelMarker>>::parse(http_client, data, value).await.map(Self)
+                }
+
+                fn kind() -> CommandOptionType {
+                    CommandOptionType::Channel
+                }
+
+                fn channel_types() -> Option<Vec<ChannelType>> {
+                    Some(Self::KINDS.to_vec())
+                }
+            }
+        )*
+    };
+}
+
+channel_id! {
+    /// The id of a text channel, including announcement channels, only allowing those channels
+    /// to be chosen as input.
+    TextChannelId => [GuildText, GuildAnnouncement],
+    /// The id of a voice channel, including stage channels, only allowing those channels to be
+    /// chosen as input.
+    VoiceChannelId => [GuildVoice, GuildStageVoice],
+    /// The id of a thread, only allowing threads to be chosen as input.
+    ThreadId => [PublicThread, PrivateThread, AnnouncementThread],
+    /// The id of a category, only allowing categories to be chosen as input.
+    CategoryId => [GuildCategory],
+}
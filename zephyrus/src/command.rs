@@ -1,54 +1,203 @@
 use crate::{
-    argument::CommandArgument, context::SlashContext, twilight_exports::{Permissions, InteractionResponse}, BoxFuture,
+    argument::{is_sensitive, CommandArgument}, concurrency::ConcurrencyLimit, context::SlashContext, cooldown::Cooldown, twilight_exports::{Id, InteractionResponse, Permissions, SkuMarker}, BoxFuture,
 };
-use std::collections::HashMap;
-use std::error::Error;
-use crate::hook::BeforeHook;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{Debug, Formatter, Result as FmtResult},
+};
+use crate::hook::{BeforeHook, CommandOutcome};
 
+/// The error type used by the framework when no other one is specified.
+#[cfg(not(feature = "anyhow"))]
+pub type DefaultError = Box<dyn std::error::Error + Send + Sync>;
+/// The error type used by the framework when no other one is specified.
+///
+/// [Parse errors](crate::parse::ParseError) are kept as the root cause of the error, so they can
+/// be retrieved using `downcast_ref`.
+#[cfg(feature = "anyhow")]
+pub type DefaultError = anyhow::Error;
 /// The result of a command execution.
-pub type CommandResult = Result<InteractionResponse, Box<dyn Error + Send + Sync>>;
-/// A pointer to a command function.
-pub(crate) type CommandFn<D> = for<'a> fn(&'a SlashContext<'a, D>) -> BoxFuture<'a, CommandResult>;
+pub type CommandResult<E = DefaultError> = Result<InteractionResponse, E>;
+/// A command function, either a plain function or a closure capturing state.
+pub type CommandFn<D, E = DefaultError> = Box<
+    dyn for<'a> Fn(&'a SlashContext<'a, D>) -> BoxFuture<'a, CommandResult<E>> + Send + Sync,
+>;
 /// A map of [commands](self::Command).
-pub type CommandMap<D> = HashMap<&'static str, Command<D>>;
+pub type CommandMap<D, E = DefaultError> = HashMap<Cow<'static, str>, Command<D, E>>;
+/// The maximum number of options a command can have, including subcommands and subcommand
+/// groups.
+pub const MAX_OPTIONS: usize = 25;
+/// A map of arbitrary key-value pairs attached to a [command](self::Command).
+pub type CommandMeta = HashMap<&'static str, &'static str>;
+
+/// The path used to invoke a [command](self::Command), displayed as its names separated by
+/// spaces.
+///
+/// e.g.: parent group subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommandPath<'a> {
+    /// The name of the top level command.
+    pub command: &'a str,
+    /// The name of the subcommand group, if the command is inside of one.
+    pub group: Option<&'a str>,
+    /// The name of the subcommand, if the command is a subcommand.
+    pub subcommand: Option<&'a str>,
+}
+
+impl<'a> CommandPath<'a> {
+    /// Creates a new path pointing to the given top level command.
+    pub fn new(command: &'a str) -> Self {
+        Self {
+            command,
+            group: None,
+            subcommand: None,
+        }
+    }
+
+    /// Gets the name of the command actually executed, this is, the deepest name of the path.
+    pub fn name(&self) -> &'a str {
+        self.subcommand.unwrap_or(self.command)
+    }
+}
+
+impl std::fmt::Display for CommandPath<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.command)?;
+        if let Some(group) = self.group {
+            write!(f, " {}", group)?;
+        }
+        if let Some(subcommand) = self.subcommand {
+            write!(f, " {}", subcommand)?;
+        }
+        Ok(())
+    }
+}
+
+/// The output of a command function, used by the macros to get the error type of a command out
+/// of its return type.
+pub trait CommandOutput {
+    /// The error type returned by the command.
+    type Error;
+}
+
+impl<E> CommandOutput for CommandResult<E> {
+    type Error = E;
+}
+
+impl<E> CommandOutput for CommandOutcome<E> {
+    type Error = E;
+}
+
+/// A set of commands defined as methods of a type, implemented using the `#[commands]` macro.
+pub trait CommandSet<D, E = DefaultError> {
+    /// Creates all the commands of the set.
+    fn commands() -> Vec<Command<D, E>>;
+}
 
 /// A command executed by the framework.
-pub struct Command<D> {
+pub struct Command<D, E = DefaultError> {
     /// The name of the command.
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     /// The description of the commands.
-    pub description: &'static str,
+    pub description: Cow<'static, str>,
     /// All the arguments the command requires.
     pub arguments: Vec<CommandArgument<D>>,
     /// A pointer to this command function.
-    pub fun: CommandFn<D>,
+    pub fun: CommandFn<D, E>,
     /// The required permissions to use this command
     pub required_permissions: Option<Permissions>,
-    pub checks: Vec<BeforeHook<D>>
+    pub checks: Vec<BeforeHook<D>>,
+    /// The category of this command, only used as metadata.
+    pub category: Option<&'static str>,
+    /// Arbitrary key-value pairs attached to this command.
+    pub meta: CommandMeta,
+    /// The maximum number of concurrent executions of this command.
+    pub concurrency: Option<ConcurrencyLimit>,
+    /// The time the command can't be used again after being used.
+    pub cooldown: Option<Cooldown>,
+    /// Whether the responses sent by the framework for this command should only be seen by the
+    /// user who invoked it.
+    pub ephemeral: bool,
+    /// The sku the user invoking the command must have an entitlement to.
+    pub premium_sku: Option<Id<SkuMarker>>,
+}
+
+impl<D, E> Debug for Command<D, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Command")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("arguments", &self.arguments)
+            .field("required_permissions", &self.required_permissions)
+            .field("checks", &self.checks.len())
+            .field("category", &self.category)
+            .field("meta", &self.meta)
+            .field("concurrency", &self.concurrency)
+            .field("cooldown", &self.cooldown)
+            .field("ephemeral", &self.ephemeral)
+            .field("premium_sku", &self.premium_sku)
+            .finish()
+    }
 }
 
-impl<D> Command<D> {
-    /// Creates a new command.
-    pub fn new(fun: CommandFn<D>) -> Self {
+impl<D, E> Command<D, E> {
+    /// Creates a new command executing the given function, which can be a closure capturing
+    /// state as long as it returns a boxed future.
+    pub fn new<F>(fun: F) -> Self
+    where
+        F: for<'a> Fn(&'a SlashContext<'a, D>) -> BoxFuture<'a, CommandResult<E>>
+            + Send
+            + Sync
+            + 'static,
+    {
         Self {
             name: Default::default(),
             description: Default::default(),
             arguments: Default::default(),
-            fun,
+            fun: Box::new(fun),
             required_permissions: Default::default(),
-            checks: Default::default()
+            checks: Default::default(),
+            category: Default::default(),
+            meta: Default::default(),
+            concurrency: Default::default(),
+            cooldown: None,
+            ephemeral: false,
+            premium_sku: None,
+        }
+    }
+
+    /// Checks the command doesn't have more arguments than allowed by discord nor required
+    /// arguments after optional ones, panicking with the given path of the command otherwise.
+    pub(crate) fn validate(&self, path: &str) {
+        if self.arguments.len() > MAX_OPTIONS {
+            panic!(
+                "{} has {} arguments, but at most {} are allowed",
+                path,
+                self.arguments.len(),
+                MAX_OPTIONS
+            );
+        }
+
+        if let Some(optional) = self.arguments.iter().position(|argument| !argument.required) {
+            if let Some(required) = self.arguments[optional..].iter().find(|argument| argument.required) {
+                panic!(
+                    "{} has the required argument {} after the optional argument {}",
+                    path, required.name, self.arguments[optional].name
+                );
+            }
         }
     }
 
     /// Sets the command name.
-    pub fn name(mut self, name: &'static str) -> Self {
-        self.name = name;
+    pub fn name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = name.into();
         self
     }
 
     /// Sets the command description.
-    pub fn description(mut self, description: &'static str) -> Self {
-        self.description = description;
+    pub fn description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.description = description.into();
         self
     }
 
@@ -67,4 +216,47 @@ impl<D> Command<D> {
         self.required_permissions = Some(permissions);
         self
     }
+
+    /// Sets the category of the command.
+    pub fn category(mut self, category: &'static str) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Sets the maximum number of concurrent executions of the command.
+    pub fn max_concurrency(mut self, limit: ConcurrencyLimit) -> Self {
+        self.concurrency = Some(limit);
+        self
+    }
+
+    /// Sets the time the command can't be used again after being used.
+    pub fn cooldown(mut self, cooldown: Cooldown) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Sets whether the responses sent by the framework for this command, such as the ones
+    /// returned by it or the busy response, should only be seen by the user who invoked it.
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    /// Sets the sku the user invoking the command must have an entitlement to. Users without
+    /// it receive a response with a button to purchase the sku instead of executing the command.
+    pub fn premium_required(mut self, sku_id: Id<SkuMarker>) -> Self {
+        self.premium_sku = Some(sku_id);
+        self
+    }
+
+    /// Attaches the given key-value pair to the command.
+    pub fn meta(mut self, key: &'static str, value: &'static str) -> Self {
+        self.meta.insert(key, value);
+        self
+    }
+
+    /// Whether the argument with the given name is [sensitive](CommandArgument::sensitive).
+    pub fn is_sensitive(&self, argument: &str) -> bool {
+        is_sensitive(&self.arguments, argument)
+    }
 }
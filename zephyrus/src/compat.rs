@@ -0,0 +1,152 @@
+//! Helpers used to build twilight's models, isolating the framework from the changes twilight
+//! makes to them between major versions.
+use crate::twilight_exports::{
+    ActionRow, Button, ButtonStyle, CommandOption, CommandOptionType, Component, Container, Id,
+    MediaGallery, MediaGalleryItem, PollMedia, Section, SelectMenu, SelectMenuOption, SelectMenuType, Separator,
+    SeparatorSpacingSize, SkuMarker, TextDisplay, Thumbnail, UnfurledMediaItem,
+};
+
+/// Creates a [command option](CommandOption) of the given kind with the given name and
+/// description, leaving all the other fields unset.
+pub(crate) fn option(kind: CommandOptionType, name: &str, description: &str) -> CommandOption {
+    CommandOption {
+        autocomplete: None,
+        channel_types: None,
+        choices: None,
+        description: description.to_string(),
+        description_localizations: None,
+        kind,
+        max_length: None,
+        max_value: None,
+        min_length: None,
+        min_value: None,
+        name: name.to_string(),
+        name_localizations: None,
+        options: None,
+        required: None,
+    }
+}
+
+/// Creates an [action row](ActionRow) containing the given components.
+pub(crate) fn action_row(components: Vec<Component>) -> Component {
+    Component::ActionRow(ActionRow {
+        id: None,
+        components,
+    })
+}
+
+/// Creates a [button](Button) of the given style and label, leaving all the other fields unset.
+pub(crate) fn button(style: ButtonStyle, label: Option<String>) -> Button {
+    Button {
+        id: None,
+        custom_id: None,
+        disabled: false,
+        emoji: None,
+        label,
+        style,
+        url: None,
+        sku_id: None,
+    }
+}
+
+/// Creates a [premium button](ButtonStyle::Premium) used to purchase the given sku.
+pub(crate) fn premium_button(sku_id: Id<SkuMarker>) -> Button {
+    let mut button = button(ButtonStyle::Premium, None);
+    button.sku_id = Some(sku_id);
+    button
+}
+
+/// Creates a [text display](TextDisplay) with the given content.
+pub(crate) fn text_display(content: String) -> Component {
+    Component::TextDisplay(TextDisplay { id: None, content })
+}
+
+/// Creates an [unfurled media item](UnfurledMediaItem) pointing to the given url, which can be
+/// an `attachment://<filename>` reference.
+pub(crate) fn media(url: String) -> UnfurledMediaItem {
+    UnfurledMediaItem {
+        url,
+        proxy_url: None,
+        height: None,
+        width: None,
+        content_type: None,
+    }
+}
+
+/// Creates a [thumbnail](Thumbnail) of the given media, leaving all the other fields unset.
+pub(crate) fn thumbnail(url: String, description: Option<String>) -> Component {
+    Component::Thumbnail(Thumbnail {
+        id: None,
+        media: media(url),
+        description: description.map(Some),
+        spoiler: None,
+    })
+}
+
+/// Creates an item of a media gallery showing the given media.
+pub(crate) fn gallery_item(url: String, description: Option<String>, spoiler: bool) -> MediaGalleryItem {
+    MediaGalleryItem {
+        media: media(url),
+        description,
+        spoiler: spoiler.then_some(true),
+    }
+}
+
+/// Creates a [media gallery](MediaGallery) with the given items.
+pub(crate) fn media_gallery(items: Vec<MediaGalleryItem>) -> Component {
+    Component::MediaGallery(MediaGallery { id: None, items })
+}
+
+/// Creates a [section](Section) with the given text displays and accessory.
+pub(crate) fn section(components: Vec<Component>, accessory: Component) -> Component {
+    Component::Section(Section {
+        id: None,
+        components,
+        accessory: Box::new(accessory),
+    })
+}
+
+/// Creates a [separator](Separator) with the given divider and spacing.
+pub(crate) fn separator(divider: bool, spacing: SeparatorSpacingSize) -> Component {
+    Component::Separator(Separator {
+        id: None,
+        divider: Some(divider),
+        spacing: Some(spacing),
+    })
+}
+
+/// Creates a [container](Container) with the given components, accent color and spoiler.
+pub(crate) fn container(components: Vec<Component>, accent_color: Option<u32>, spoiler: bool) -> Component {
+    Component::Container(Container {
+        id: None,
+        accent_color: accent_color.map(Some),
+        spoiler: spoiler.then_some(true),
+        components,
+    })
+}
+
+/// Creates a string [select menu](SelectMenu) with the given options, leaving all the other
+/// fields unset.
+pub(crate) fn string_select(custom_id: String, options: Vec<SelectMenuOption>) -> SelectMenu {
+    SelectMenu {
+        id: None,
+        channel_types: None,
+        custom_id,
+        default_values: None,
+        disabled: false,
+        kind: SelectMenuType::Text,
+        max_values: None,
+        min_values: None,
+        options: Some(options),
+        placeholder: None,
+        required: None,
+    }
+}
+
+/// Creates the [media](PollMedia) of a poll question or answer containing only the given text.
+pub(crate) fn poll_media(text: String) -> PollMedia {
+    PollMedia {
+        emoji: None,
+        text: Some(text),
+    }
+}
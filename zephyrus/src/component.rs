@@ -0,0 +1,252 @@
+use crate::{
+    compat,
+    framework::Framework,
+    select::SelectOptions,
+    twilight_exports::{ButtonStyle, Component},
+};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// The maximum number of action rows a message can have.
+pub const MAX_ROWS: usize = 5;
+/// The maximum number of buttons an action row can have.
+pub const MAX_ROW_BUTTONS: usize = 5;
+/// The maximum length of a custom id.
+pub const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+/// The errors produced when validating the components built by a
+/// [components builder](ComponentsBuilder).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ComponentError {
+    /// More than [MAX_ROWS] action rows were provided.
+    TooManyRows(usize),
+    /// The action row at the given index has no components.
+    EmptyRow(usize),
+    /// The action row at the given index has more than [MAX_ROW_BUTTONS] buttons.
+    TooManyButtons(usize),
+    /// The action row at the given index has a select menu alongside other components.
+    SelectMenuNotAlone(usize),
+    /// The given custom id is longer than [MAX_CUSTOM_ID_LENGTH].
+    CustomIdTooLong(String),
+    /// The given custom id is used by more than one component.
+    DuplicateCustomId(String),
+    /// No handler registered in the framework handles the given custom id.
+    UnhandledCustomId(String),
+}
+
+impl Display for ComponentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::TooManyRows(count) => {
+                write!(f, "Too many action rows: {} out of {}", count, MAX_ROWS)
+            }
+            Self::EmptyRow(row) => write!(f, "Action row {} is empty", row),
+            Self::TooManyButtons(row) => {
+                write!(f, "Action row {} has more than {} buttons", row, MAX_ROW_BUTTONS)
+            }
+            Self::SelectMenuNotAlone(row) => {
+                write!(f, "Action row {} has a select menu alongside other components", row)
+            }
+            Self::CustomIdTooLong(id) => write!(f, "Custom id {} is too long", id),
+            Self::DuplicateCustomId(id) => write!(f, "Custom id {} is repeated", id),
+            Self::UnhandledCustomId(id) => write!(f, "Custom id {} has no handler", id),
+        }
+    }
+}
+
+impl Error for ComponentError {}
+
+/// A builder of an action row, see [ComponentsBuilder::row].
+#[derive(Debug, Clone, Default)]
+pub struct ActionRowBuilder {
+    pub(crate) components: Vec<Component>,
+}
+
+impl ActionRowBuilder {
+    /// Adds a button with the given style, label and custom id, usually created by the
+    /// `<button>_custom_id` function generated by the `#[button]` macro.
+    pub fn button(
+        &mut self,
+        style: ButtonStyle,
+        label: impl Into<String>,
+        custom_id: impl Into<String>,
+    ) -> &mut Self {
+        let mut button = compat::button(style, Some(label.into()));
+        button.custom_id = Some(custom_id.into());
+        self.component(Component::Button(button))
+    }
+
+    /// Adds a button linking to the given url.
+    pub fn link(&mut self, label: impl Into<String>, url: impl Into<String>) -> &mut Self {
+        let mut button = compat::button(ButtonStyle::Link, Some(label.into()));
+        button.url = Some(url.into());
+        self.component(Component::Button(button))
+    }
+
+    /// Adds a string select menu with the given custom id, whose options are the ones of the
+    /// given [select options](SelectOptions).
+    pub fn select<T: SelectOptions>(&mut self, custom_id: impl Into<String>) -> &mut Self {
+        let menu = compat::string_select(custom_id.into(), T::options());
+        self.component(Component::SelectMenu(menu))
+    }
+
+    /// Adds the given component, such as a [button](crate::twilight_exports::Button) or
+    /// [select menu](crate::twilight_exports::SelectMenu) built manually or using
+    /// [Button::component](crate::button::Button::component).
+    pub fn component(&mut self, component: impl Into<Component>) -> &mut Self {
+        self.components.push(component.into());
+        self
+    }
+}
+
+/// A builder used to assemble components into action rows, validating them against the limits
+/// set by discord.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentsBuilder {
+    rows: Vec<ActionRowBuilder>,
+    disabled: bool,
+}
+
+impl ComponentsBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an action row.
+    pub fn row<F>(mut self, fun: F) -> Self
+    where
+        F: FnOnce(&mut ActionRowBuilder) -> &mut ActionRowBuilder,
+    {
+        let mut row = ActionRowBuilder::default();
+        fun(&mut row);
+        self.rows.push(row);
+        self
+    }
+
+    /// Sets whether all the components are disabled.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Validates the components and builds them into action rows.
+    pub fn build(self) -> Result<Vec<Component>, ComponentError> {
+        if self.rows.len() > MAX_ROWS {
+            return Err(ComponentError::TooManyRows(self.rows.len()));
+        }
+
+        let mut custom_ids = Vec::new();
+
+        for (index, row) in self.rows.iter().enumerate() {
+            let mut buttons = 0;
+            let mut selects = 0;
+
+            for component in &row.components {
+                let custom_id = match component {
+                    Component::Button(button) => {
+                        buttons += 1;
+                        button.custom_id.as_deref()
+                    }
+                    Component::SelectMenu(menu) => {
+                        selects += 1;
+                        Some(menu.custom_id.as_str())
+                    }
+                    _ => None,
+                };
+
+                if let Some(custom_id) = custom_id {
+                    if custom_id.chars().count() > MAX_CUSTOM_ID_LENGTH {
+                        return Err(ComponentError::CustomIdTooLong(custom_id.to_string()));
+                    }
+                    if custom_ids.contains(&custom_id) {
+                        return Err(ComponentError::DuplicateCustomId(custom_id.to_string()));
+                    }
+                    custom_ids.push(custom_id);
+                }
+            }
+
+            if row.components.is_empty() {
+                return Err(ComponentError::EmptyRow(index));
+            }
+            if buttons > MAX_ROW_BUTTONS {
+                return Err(ComponentError::TooManyButtons(index));
+            }
+            if selects > 0 && row.components.len() > 1 {
+                return Err(ComponentError::SelectMenuNotAlone(index));
+            }
+        }
+
+        let mut components = self
+            .rows
+            .into_iter()
+            .map(|row| compat::action_row(row.components))
+            .collect::<Vec<_>>();
+
+        if self.disabled {
+            disable_all(&mut components);
+        }
+
+        Ok(components)
+    }
+
+    /// Same as [build](Self::build), but also checks every custom id is handled by a
+    /// [button](crate::button::Button) registered in the given framework.
+    pub fn build_for<D, E>(self, framework: &Framework<D, E>) -> Result<Vec<Component>, ComponentError> {
+        let components = self.build()?;
+
+        for custom_id in custom_ids(&components) {
+            if !framework.handles_custom_id(custom_id) {
+                return Err(ComponentError::UnhandledCustomId(custom_id.to_string()));
+            }
+        }
+
+        Ok(components)
+    }
+}
+
+/// Disables the given components and the ones inside of their action rows, containers and
+/// sections, this is useful to
+/// prevent further uses of the components of a message once handled.
+pub fn disable_all(components: &mut [Component]) {
+    for component in components {
+        match component {
+            Component::ActionRow(row) => disable_all(&mut row.components),
+            Component::Container(container) => disable_all(&mut container.components),
+            Component::Section(section) => disable_all(std::slice::from_mut(&mut *section.accessory)),
+            Component::Button(button) => button.disabled = true,
+            Component::SelectMenu(menu) => menu.disabled = true,
+            _ => (),
+        }
+    }
+}
+
+/// Gets the custom ids of the given components and the ones inside of their action rows,
+/// containers and sections.
+fn custom_ids(components: &[Component]) -> Vec<&str> {
+    let mut ids = Vec::new();
+
+    for component in components {
+        match component {
+            Component::ActionRow(row) => ids.extend(custom_ids(&row.components)),
+            Component::Container(container) => ids.extend(custom_ids(&container.components)),
+            Component::Section(section) => ids.extend(custom_ids(std::slice::from_ref(&*section.accessory))),
+            Component::Button(button) => ids.extend(button.custom_id.as_deref()),
+            Component::SelectMenu(menu) => ids.push(menu.custom_id.as_str()),
+            _ => (),
+        }
+    }
+
+    ids
+}
+
+impl<D, E> Framework<D, E> {
+    /// Checks whether a [button](crate::button::Button) registered in the framework handles the
+    /// given custom id.
+    pub fn handles_custom_id(&self, custom_id: &str) -> bool {
+        self.buttons.iter().any(|button| button.template.matches(custom_id))
+    }
+}
@@ -0,0 +1,238 @@
+use crate::twilight_exports::Interaction;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// The scope a [concurrency limit](ConcurrencyLimit) is applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConcurrencyScope {
+    /// The limit applies to all the executions of the command.
+    Global,
+    /// The limit applies separately to every guild.
+    Guild,
+    /// The limit applies separately to every channel.
+    Channel,
+    /// The limit applies separately to every user.
+    User,
+}
+
+impl ConcurrencyScope {
+    /// Gets the id of the scope the given interaction belongs to.
+    pub(crate) fn key(self, interaction: &Interaction) -> u64 {
+        match self {
+            Self::Global => 0,
+            Self::Guild => interaction.guild_id.map(|id| id.get()).unwrap_or(0),
+            Self::Channel => interaction
+                .channel
+                .as_ref()
+                .map(|channel| channel.id.get())
+                .unwrap_or(0),
+            Self::User => interaction.author_id().map(|id| id.get()).unwrap_or(0),
+        }
+    }
+}
+
+/// The maximum number of concurrent executions a command allows inside of a
+/// [scope](ConcurrencyScope).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ConcurrencyLimit {
+    /// The maximum number of concurrent executions.
+    pub max: usize,
+    /// The scope the limit is applied to.
+    pub scope: ConcurrencyScope,
+}
+
+impl ConcurrencyLimit {
+    /// Creates a new concurrency limit.
+    pub fn new(max: usize, scope: ConcurrencyScope) -> Self {
+        Self { max, scope }
+    }
+}
+
+/// Keeps track of the running executions of the commands with a
+/// [concurrency limit](ConcurrencyLimit).
+#[derive(Default)]
+pub(crate) struct ConcurrencyTracker {
+    running: Mutex<HashMap<(String, u64), usize>>,
+}
+
+impl ConcurrencyTracker {
+    /// Tries to start an execution of the command with the given path, returning `None` if the
+    /// limit of the command has been reached.
+    ///
+    /// The execution is finished when the returned guard is dropped.
+    pub(crate) fn acquire(
+        &self,
+        path: &str,
+        limit: ConcurrencyLimit,
+        interaction: &Interaction,
+    ) -> Option<ConcurrencyGuard<'_>> {
+        let key = (path.to_string(), limit.scope.key(interaction));
+        let mut running = self.running.lock();
+        let count = running.get(&key).copied().unwrap_or(0);
+
+        if count >= limit.max {
+            return None;
+        }
+
+        running.insert(key.clone(), count + 1);
+        Some(ConcurrencyGuard { tracker: self, key })
+    }
+}
+
+/// A running execution of a command, which is finished when dropped.
+pub(crate) struct ConcurrencyGuard<'a> {
+    tracker: &'a ConcurrencyTracker,
+    key: (String, u64),
+}
+
+impl Drop for ConcurrencyGuard<'_> {
+    fn drop(&mut self) {
+        let mut running = self.tracker.running.lock();
+        if let Some(count) = running.get_mut(&self.key) {
+            *count -= 1;
+            if *count == 0 {
+                running.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// What to do with a command execution when the
+/// [global concurrency limit](GlobalConcurrencyLimit) has been reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Reject the execution, answering with the busy response.
+    Reject,
+    /// Wait for a running execution to finish, queuing up to the given number of executions and
+    /// rejecting the ones exceeding it.
+    ///
+    /// Executions waiting for longer than
+    /// [QUEUED_DEFER_AFTER](crate::framework::QUEUED_DEFER_AFTER) are deferred, as discord only
+    /// waits 3 seconds for the response.
+    Wait {
+        /// The maximum number of executions waiting at the same time.
+        max_queued: usize,
+    },
+}
+
+/// The maximum number of concurrent command executions across the whole framework.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlobalConcurrencyLimit {
+    /// The maximum number of concurrent executions.
+    pub max: usize,
+    /// What to do when the limit has been reached.
+    pub overflow: OverflowPolicy,
+}
+
+impl GlobalConcurrencyLimit {
+    /// Creates a new global concurrency limit.
+    pub fn new(max: usize, overflow: OverflowPolicy) -> Self {
+        Self { max, overflow }
+    }
+}
+
+/// Enforces a [global concurrency limit](GlobalConcurrencyLimit).
+pub(crate) struct GlobalLimiter {
+    overflow: OverflowPolicy,
+    semaphore: Semaphore,
+    queued: AtomicUsize,
+}
+
+impl GlobalLimiter {
+    /// Creates a new limiter enforcing the given limit.
+    pub(crate) fn new(limit: GlobalConcurrencyLimit) -> Self {
+        Self {
+            overflow: limit.overflow,
+            semaphore: Semaphore::new(limit.max),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Tries to start a command execution right away, returning `None` if the limit has been
+    /// reached.
+    ///
+    /// The execution is finished when the returned permit is dropped.
+    pub(crate) fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.semaphore.try_acquire().ok()
+    }
+
+    /// Queues a command execution waiting for a running one to finish, following the
+    /// [overflow policy](OverflowPolicy), returning `None` if the execution has been rejected.
+    ///
+    /// The execution leaves the queue once the returned slot is dropped, even if it's dropped
+    /// while [waiting](QueueSlot::acquire).
+    pub(crate) fn enqueue(&self) -> Option<QueueSlot<'_>> {
+        match self.overflow {
+            OverflowPolicy::Reject => None,
+            OverflowPolicy::Wait { max_queued } => {
+                self.queued
+                    .fetch_update(Ordering::AcqRel, Ordering::Acquire, |queued| {
+                        (queued < max_queued).then_some(queued + 1)
+                    })
+                    .ok()?;
+
+                Some(QueueSlot { limiter: self })
+            }
+        }
+    }
+}
+
+/// A command execution waiting for the [global concurrency limit](GlobalConcurrencyLimit),
+/// which leaves the queue when dropped.
+pub(crate) struct QueueSlot<'a> {
+    limiter: &'a GlobalLimiter,
+}
+
+impl<'a> QueueSlot<'a> {
+    /// Waits for a running execution to finish, starting this one.
+    ///
+    /// The execution is finished when the returned permit is dropped.
+    pub(crate) async fn acquire(self) -> Option<SemaphorePermit<'a>> {
+        self.limiter.semaphore.acquire().await.ok()
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.limiter.queued.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GlobalConcurrencyLimit, GlobalLimiter, OverflowPolicy};
+    use std::sync::atomic::Ordering;
+
+    fn limiter(max_queued: usize) -> GlobalLimiter {
+        GlobalLimiter::new(GlobalConcurrencyLimit::new(1, OverflowPolicy::Wait { max_queued }))
+    }
+
+    #[test]
+    fn dropped_waiters_leave_the_queue() {
+        let limiter = limiter(1);
+        let _running = limiter.try_acquire().unwrap();
+
+        let slot = limiter.enqueue().unwrap();
+        assert!(limiter.enqueue().is_none());
+
+        // Dropping the future waiting for the permit, as done when the interaction is cancelled
+        drop(slot.acquire());
+        assert_eq!(limiter.queued.load(Ordering::Acquire), 0);
+        assert!(limiter.enqueue().is_some());
+    }
+
+    #[test]
+    fn rejects_without_queueing() {
+        let limiter = GlobalLimiter::new(GlobalConcurrencyLimit::new(1, OverflowPolicy::Reject));
+        let _running = limiter.try_acquire().unwrap();
+
+        assert!(limiter.try_acquire().is_none());
+        assert!(limiter.enqueue().is_none());
+    }
+}
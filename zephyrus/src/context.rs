@@ -1,15 +1,27 @@
-use parking_lot::Mutex;
 use crate::{
+    argument::{is_sensitive, CommandArgument},
     builder::WrappedClient,
-    command::CommandResult,
+    command::{CommandMeta, CommandResult},
+    event::{self, FrameworkEvent, Observer},
+    localization::{fill_placeholders, Translator},
+    message::Message as MessageWrapper,
+    thread::Thread,
+    response,
+    shard::ShardInfo,
     twilight_exports::*,
-    waiter::{InteractionWaiter, WaiterWaker}
+    waiter::{ComponentWaiter, InteractionWaiter, Waiters}
 };
-use crate::framework::Framework;
 
-use crate::iter::DataIterator;
+use crate::argument::check_constraints;
+use crate::iter::TakeOption;
 use crate::parse::{Parse, ParseError};
 use crate::waiter::new_pair;
+use std::{collections::HashMap, error::Error, sync::Arc, time::Duration};
+use tokio::sync::Mutex;
+#[cfg(feature = "cancellation")]
+use crate::waiter::ComponentInteraction;
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
 
 /// The value the user is providing to the argument.
 #[derive(Debug, Clone)]
@@ -18,6 +30,31 @@ pub struct Focused {
     pub kind: CommandOptionType,
 }
 
+/// The errors which can be returned when accessing interaction fields through a
+/// [context](SlashContext).
+#[derive(Debug)]
+pub enum ContextError {
+    /// The interaction was not invoked inside a guild.
+    NotInGuild,
+    /// The given field is not present in the interaction.
+    MissingField(&'static str),
+    /// The http client given to the framework can't be shared with other tasks, see
+    /// [WrappedClient::shared](WrappedClient::shared).
+    ClientNotShared,
+}
+
+impl std::fmt::Display for ContextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotInGuild => write!(f, "Interaction was not invoked in a guild"),
+            Self::MissingField(field) => write!(f, "Interaction field `{}` is missing", field),
+            Self::ClientNotShared => write!(f, "The http client can't be shared with other tasks"),
+        }
+    }
+}
+
+impl std::error::Error for ContextError {}
+
 /// Context given to all functions used to autocomplete arguments.
 pub struct AutocompleteContext<'a, D> {
     /// The http client used by the framework.
@@ -26,7 +63,13 @@ pub struct AutocompleteContext<'a, D> {
     pub data: &'a D,
     /// The user input.
     pub user_input: Focused,
-    /// The interaction itself.
+    /// All the options of the command being autocompleted, including the focused one and the
+    /// ones the user already filled.
+    ///
+    /// The input of the focused option is moved into [user_input](Self::user_input), so it is
+    /// left empty here.
+    pub options: Vec<CommandDataOption>,
+    /// The interaction itself, its options are moved into [options](Self::options).
     pub interaction: &'a mut Interaction,
 }
 
@@ -35,12 +78,14 @@ impl<'a, D> AutocompleteContext<'a, D> {
         http_client: &'a WrappedClient,
         data: &'a D,
         user_input: Focused,
+        options: Vec<CommandDataOption>,
         interaction: &'a mut Interaction,
     ) -> Self {
         Self {
             http_client,
             data,
             user_input,
+            options,
             interaction,
         }
     }
@@ -49,6 +94,69 @@ impl<'a, D> AutocompleteContext<'a, D> {
     pub fn http_client(&self) -> &Client {
         self.http_client.inner()
     }
+
+    /// Gets the raw value of the option with the given name, returning `None` if the user
+    /// didn't fill it yet.
+    pub fn option(&self, name: &str) -> Option<&CommandOptionValue> {
+        self.options
+            .iter()
+            .find(|option| option.name == name)
+            .map(|option| &option.value)
+    }
+
+    /// Parses the already filled option with the given name using its [Parse](Parse)
+    /// implementation.
+    ///
+    /// The focused option can't be parsed this way, as its value is still being typed by the
+    /// user, use [user_input](Self::user_input) instead.
+    pub async fn parse_option<T>(&self, name: &str) -> Result<T, ParseError>
+    where
+        T: Parse<D>,
+        D: Send + Sync
+    {
+        let value = self.option(name);
+        if let Some(CommandOptionValue::Focused(..)) = value {
+            return Err(ParseError::StructureMismatch(format!("{} is the focused option", name)));
+        }
+
+        if value.is_none() && T::required() {
+            return Err(ParseError::StructureMismatch(format!("{} not found", name)));
+        }
+
+        let resolved = match self.interaction.data.as_ref() {
+            Some(InteractionData::ApplicationCommand(data)) => data.resolved.as_ref(),
+            _ => None,
+        };
+
+        let result = match value {
+            Some(value) => check_constraints::<T, D>(value, resolved),
+            None => Ok(()),
+        };
+
+        let result = match result {
+            Ok(()) => T::parse_resolved(self.http_client, self.data, value, resolved).await,
+            Err(why) => Err(why),
+        };
+
+        result.map_err(|mut why| {
+            if let ParseError::Parsing { argument_name, .. } = &mut why {
+                *argument_name = name.to_string();
+            }
+            why
+        })
+    }
+}
+
+/// Whether the interaction of a [context](SlashContext) has been responded, which decides how
+/// [reply](SlashContext::reply) sends its responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseState {
+    /// The interaction hasn't been responded yet.
+    Pending,
+    /// The interaction was deferred, so its original response has to be edited.
+    Deferred,
+    /// The interaction was responded, so further responses are sent as followups.
+    Responded,
 }
 
 /// Framework context given to all command functions, this struct contains all the necessary
@@ -58,12 +166,25 @@ pub struct SlashContext<'a, D> {
     pub http_client: &'a WrappedClient,
     /// The application id provided to the framework.
     pub application_id: Id<ApplicationMarker>,
-    /// An [interaction client](InteractionClient) made out of the framework's [http client](Client)
-    pub interaction_client: InteractionClient<'a>,
+    /// The interaction client borrowed by the followups and updates.
+    interaction_client: InteractionClient<'a>,
     /// The data shared across the framework.
     pub data: &'a D,
-    /// The interaction itself.
-    pub interaction: Interaction,
+    shared_data: &'a Arc<D>,
+    /// The interaction itself, shared between the clones of the context.
+    pub interaction: Arc<Interaction>,
+    /// The shard the interaction was received on, if provided to the framework.
+    pub shard: Option<ShardInfo>,
+    waiters: &'a Waiters<D>,
+    meta: Option<&'a CommandMeta>,
+    arguments: &'a [CommandArgument<D>],
+    observers: &'a [Observer],
+    allowed_mentions: Option<&'a AllowedMentions>,
+    translator: Option<&'a Translator>,
+    ephemeral: bool,
+    /// Whether the interaction has been responded, shared between the clones of the context
+    /// and the framework.
+    pub(crate) response_state: Arc<Mutex<ResponseState>>,
 }
 
 impl<'a, D> Clone for SlashContext<'a, D> {
@@ -72,35 +193,563 @@ impl<'a, D> Clone for SlashContext<'a, D> {
             http_client: self.http_client,
             application_id: self.application_id,
             interaction_client: self.http_client.inner().interaction(self.application_id),
-            data: &self.data,
-            interaction: self.interaction.clone(),
+            data: self.data,
+            shared_data: self.shared_data,
+            interaction: Arc::clone(&self.interaction),
+            shard: self.shard.clone(),
+            waiters: self.waiters,
+            meta: self.meta,
+            arguments: self.arguments,
+            observers: self.observers,
+            allowed_mentions: self.allowed_mentions,
+            translator: self.translator,
+            ephemeral: self.ephemeral,
+            response_state: Arc::clone(&self.response_state),
         }
     }
 }
 
 impl<'a, D> SlashContext<'a, D> {
     /// Creates a new context.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         http_client: &'a WrappedClient,
         application_id: Id<ApplicationMarker>,
-        data: &'a D,
+        data: &'a Arc<D>,
+        waiters: &'a Waiters<D>,
         interaction: Interaction,
+        shard: Option<ShardInfo>,
+        meta: Option<&'a CommandMeta>,
+        arguments: &'a [CommandArgument<D>],
+        observers: &'a [Observer],
+        allowed_mentions: Option<&'a AllowedMentions>,
+        translator: Option<&'a Translator>,
+        ephemeral: bool,
     ) -> Self {
-        let interaction_client = http_client.inner().interaction(application_id);
         Self {
             http_client,
             application_id,
-            interaction_client,
-            data,
-            interaction,
+            interaction_client: http_client.inner().interaction(application_id),
+            data: data.as_ref(),
+            shared_data: data,
+            interaction: Arc::new(interaction),
+            shard,
+            waiters,
+            meta,
+            arguments,
+            observers,
+            allowed_mentions,
+            translator,
+            ephemeral,
+            response_state: Arc::new(Mutex::new(ResponseState::Pending)),
         }
     }
 
+    /// Emits the given event to the observers of the framework.
+    pub(crate) fn emit(&self, event: FrameworkEvent<'_>) {
+        event::emit(self.observers, event);
+    }
+
     /// Gets the http client used by the framework.
     pub fn http_client(&self) -> &Client {
         self.http_client.inner()
     }
 
+    /// Creates an [interaction client](InteractionClient) out of the framework's
+    /// [http client](Client).
+    pub fn interaction_client(&self) -> InteractionClient<'_> {
+        self.http_client.inner().interaction(self.application_id)
+    }
+
+    /// Gets the value attached with the given key to the command being executed, set using the
+    /// `#[meta]` attribute.
+    pub fn meta(&self, key: &str) -> Option<&'static str> {
+        self.meta.and_then(|meta| meta.get(key).copied())
+    }
+
+    /// Whether the argument of the executed command with the given name is
+    /// [sensitive](CommandArgument::sensitive), so its value shouldn't be shown.
+    pub fn is_sensitive(&self, argument: &str) -> bool {
+        is_sensitive(self.arguments, argument)
+    }
+
+    /// Gets the raw values of the options given to the executed command by their name. The
+    /// options of subcommands are already taken out of their subcommand group and subcommand,
+    /// so only the arguments of the command itself are included.
+    ///
+    /// The map is empty if the interaction isn't a slash command, such as for components.
+    pub fn options(&self) -> HashMap<&str, &CommandOptionValue> {
+        match self.interaction.data.as_ref() {
+            Some(InteractionData::ApplicationCommand(data)) => data
+                .options
+                .iter()
+                .map(|option| (option.name.as_str(), &option.value))
+                .collect(),
+            _ => HashMap::new(),
+        }
+    }
+
+    /// Gets the user who invoked the interaction, either from the guild member or, if the
+    /// interaction was invoked in a direct message, from the interaction's user.
+    pub fn author(&self) -> Result<&User, ContextError> {
+        self.interaction
+            .member
+            .as_ref()
+            .and_then(|member| member.user.as_ref())
+            .or(self.interaction.user.as_ref())
+            .ok_or(ContextError::MissingField("user"))
+    }
+
+    /// Gets the guild member who invoked the interaction, returning an error if the interaction
+    /// was not invoked inside a guild.
+    pub fn member(&self) -> Result<&PartialMember, ContextError> {
+        self.interaction.member.as_ref().ok_or(ContextError::NotInGuild)
+    }
+
+    /// Gets the id of the guild the interaction was invoked in, returning an error if the
+    /// interaction was not invoked inside a guild.
+    pub fn guild_id(&self) -> Result<Id<GuildMarker>, ContextError> {
+        self.interaction.guild_id.ok_or(ContextError::NotInGuild)
+    }
+
+    /// Gets the id of the channel the interaction was invoked in.
+    pub fn channel_id(&self) -> Result<Id<ChannelMarker>, ContextError> {
+        self.interaction
+            .channel
+            .as_ref()
+            .map(|channel| channel.id)
+            .ok_or(ContextError::MissingField("channel"))
+    }
+
+    /// Gets the entitlements of the user who invoked the interaction, representing their access
+    /// to the premium skus of the application.
+    pub fn entitlements(&self) -> &[Entitlement] {
+        &self.interaction.entitlements
+    }
+
+    /// Checks whether the user who invoked the interaction has an entitlement to the given sku.
+    pub fn has_entitlement(&self, sku_id: Id<SkuMarker>) -> bool {
+        has_entitlement(&self.interaction, sku_id)
+    }
+
+    /// Gets the permissions the application has in the channel the interaction was invoked in.
+    pub fn app_permissions(&self) -> Result<Permissions, ContextError> {
+        self.interaction.app_permissions.ok_or(ContextError::MissingField("app_permissions"))
+    }
+
+    /// Gets the computed permissions of the member who invoked the interaction in the channel
+    /// the interaction was invoked in, including overwrites.
+    pub fn member_permissions(&self) -> Result<Permissions, ContextError> {
+        self.member()?
+            .permissions
+            .ok_or(ContextError::MissingField("member.permissions"))
+    }
+
+    /// Checks whether the member who invoked the interaction has all the given permissions,
+    /// returning `false` if the permissions are not available.
+    pub fn has_permissions(&self, permissions: Permissions) -> bool {
+        self.member_permissions()
+            .map(|member| member.contains(permissions))
+            .unwrap_or(false)
+    }
+
+    /// Gets the id of the shard the interaction was received on, returning `None` if the
+    /// interaction was not processed using
+    /// [process_with_shard](crate::framework::Framework::process_with_shard).
+    pub fn shard_id(&self) -> Option<u32> {
+        self.shard.as_ref().map(|shard| shard.id)
+    }
+
+    /// Gets the latency of the shard the interaction was received on, returning `None` if the
+    /// shard is unknown or no latency is available.
+    pub fn latency(&self) -> Option<Duration> {
+        self.shard.as_ref().and_then(ShardInfo::latency)
+    }
+
+    /// Waits for a component interaction which satisfies the given predicate, which receives the
+    /// data shared across the framework and the interaction.
+    ///
+    /// The waiter resolves to a [component interaction](crate::waiter::ComponentInteraction)
+    /// with the data of the component already extracted.
+    pub fn wait_for_component<F>(&self, fun: F) -> ComponentWaiter
+    where
+        F: Fn(&D, &Interaction) -> bool + Send + 'static
+    {
+        ComponentWaiter::new(self.wait_for(move |data, interaction| {
+            interaction.kind == InteractionType::MessageComponent
+                && matches!(interaction.data, Some(InteractionData::MessageComponent(_)))
+                && fun(data, interaction)
+        }))
+    }
+
+    /// Waits for an interaction of any type which satisfies the given predicate, such as modal
+    /// submits or application commands, which receives the data shared across the framework and
+    /// the interaction.
+    pub fn wait_for<F>(&self, fun: F) -> InteractionWaiter
+    where
+        F: Fn(&D, &Interaction) -> bool + Send + 'static
+    {
+        let (waker, waiter) = new_pair(fun);
+        self.waiters.register(waker, Some(&self.interaction));
+        waiter
+    }
+
+    /// Waits for a component interaction which satisfies the given predicate until the given
+    /// token is cancelled, which resolves the waiter to
+    /// [WaiterError::Cancelled](crate::waiter::WaiterError::Cancelled) and removes it from the
+    /// framework immediately.
+    #[cfg(feature = "cancellation")]
+    pub async fn wait_for_component_until<F>(
+        &self,
+        token: &CancellationToken,
+        fun: F
+    ) -> Result<ComponentInteraction, Box<dyn Error + Send + Sync>>
+    where
+        F: Fn(&D, &Interaction) -> bool + Send + 'static
+    {
+        let waiter = self.wait_for_component(fun);
+        self.waiters.wait_until_cancelled(waiter, token).await
+    }
+
+    /// Waits for an interaction of any type which satisfies the given predicate until the given
+    /// token is cancelled, which resolves the waiter to
+    /// [WaiterError::Cancelled](crate::waiter::WaiterError::Cancelled) and removes it from the
+    /// framework immediately.
+    #[cfg(feature = "cancellation")]
+    pub async fn wait_for_until<F>(
+        &self,
+        token: &CancellationToken,
+        fun: F
+    ) -> Result<Interaction, Box<dyn Error + Send + Sync>>
+    where
+        F: Fn(&D, &Interaction) -> bool + Send + 'static
+    {
+        let waiter = self.wait_for(fun);
+        self.waiters.wait_until_cancelled(waiter, token).await
+    }
+
+    /// Parses the argument with the given name, taking it out of the given options, usually an
+    /// [option map](crate::iter::OptionMap).
+    pub async fn named_parse<T>(
+        &self,
+        name: &str,
+        iterator: &mut impl TakeOption<'_>
+    ) -> Result<T, ParseError>
+    where
+        T: Parse<D>,
+        D: Send + Sync
+    {
+        let value = iterator.take_option(name).map(|it| &it.value);
+        if value.is_none() && T::required() {
+            return Err(ParseError::StructureMismatch(format!("{} not found", name)));
+        }
+
+        let resolved = match self.interaction.data.as_ref() {
+            Some(InteractionData::ApplicationCommand(data)) => data.resolved.as_ref(),
+            _ => None,
+        };
+
+        let result = match value {
+            Some(value) => check_constraints::<T, D>(value, resolved),
+            None => Ok(()),
+        };
+
+        let result = match result {
+            Ok(()) => T::parse_resolved(self.http_client, self.data, value, resolved).await,
+            Err(why) => Err(why),
+        };
+
+        event::emit(self.observers, FrameworkEvent::ArgumentParsed {
+            argument: name,
+            success: result.is_ok(),
+        });
+
+        result.map_err(|mut why| {
+            if let ParseError::Parsing { argument_name, .. } = &mut why {
+                *argument_name = name.to_string();
+            }
+            if self.is_sensitive(name) {
+                why = why.redact();
+            }
+            why
+        })
+    }
+
+    /// Whether the responses of the executed command are
+    /// [ephemeral](crate::command::Command::ephemeral) by default.
+    pub fn is_ephemeral(&self) -> bool {
+        self.ephemeral
+    }
+
+    /// Creates an [owned context](OwnedSlashContext) which can be moved into spawned tasks to keep
+    /// responding the interaction after the command returns.
+    ///
+    /// Fails if the http client given to the framework isn't stored in an `Arc`.
+    pub fn to_owned(&self) -> Result<OwnedSlashContext<D>, ContextError> {
+        Ok(OwnedSlashContext {
+            http_client: self.http_client.shared().ok_or(ContextError::ClientNotShared)?,
+            application_id: self.application_id,
+            data: Arc::clone(self.shared_data),
+            interaction: Arc::clone(&self.interaction),
+            shard: self.shard.clone(),
+            allowed_mentions: self.allowed_mentions.cloned(),
+            ephemeral: self.ephemeral,
+            response_state: Arc::clone(&self.response_state),
+        })
+    }
+
+    /// Translates the string with the given key to the locale of the user who invoked the
+    /// interaction using the [localizer](crate::localization::Localizer) of the framework,
+    /// replacing every `{name}` placeholder by the value given to it.
+    ///
+    /// When the string isn't translated to the locale of the user, its language without region,
+    /// the locale of the guild and the fallback locale of the framework are tried in order,
+    /// returning the key itself if none of them has it. The [t](crate::t) macro allows giving
+    /// the values of the placeholders as `name = value` pairs.
+    pub fn t(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        match self.translator {
+            Some(translator) => translator.translate(&self.interaction, key, args),
+            None => fill_placeholders(key.to_string(), args),
+        }
+    }
+
+    /// Creates a followup message of the interaction, using the default
+    /// [allowed mentions](AllowedMentions) of the framework and being ephemeral if the command
+    /// is, both of which can be overridden.
+    pub fn followup(&self) -> CreateFollowup<'_> {
+        self.followup_with(&self.interaction_client)
+    }
+
+    /// Same as [followup](Self::followup), borrowing the given
+    /// [interaction client](InteractionClient) instead of the one of the context.
+    pub fn followup_with<'b>(&'b self, client: &'b InteractionClient<'_>) -> CreateFollowup<'b> {
+        self.create_followup(client, MessageFlags::empty())
+    }
+
+    /// Same as [followup](Self::followup), making the message only visible to the user who used
+    /// the interaction.
+    pub fn ephemeral_followup(&self) -> CreateFollowup<'_> {
+        self.create_followup(&self.interaction_client, MessageFlags::EPHEMERAL)
+    }
+
+    /// Same as [followup](Self::followup), making the message not trigger push and desktop
+    /// notifications.
+    pub fn silent_followup(&self) -> CreateFollowup<'_> {
+        self.create_followup(&self.interaction_client, MessageFlags::SUPPRESS_NOTIFICATIONS)
+    }
+
+    /// Same as [followup](Self::followup), making the message not include the embeds of the
+    /// links it contains.
+    pub fn followup_without_embeds(&self) -> CreateFollowup<'_> {
+        self.create_followup(&self.interaction_client, MessageFlags::SUPPRESS_EMBEDS)
+    }
+
+    /// Creates a followup with the given flags, adding the ephemeral one if the command is.
+    fn create_followup<'b>(
+        &'b self,
+        client: &'b InteractionClient<'_>,
+        mut flags: MessageFlags,
+    ) -> CreateFollowup<'b> {
+        let mut followup = client.create_followup(&self.interaction.token);
+        if self.ephemeral {
+            flags |= MessageFlags::EPHEMERAL;
+        }
+        if !flags.is_empty() {
+            followup = followup.flags(flags);
+        }
+        match self.allowed_mentions {
+            Some(allowed_mentions) => followup.allowed_mentions(Some(allowed_mentions)),
+            None => followup,
+        }
+    }
+
+    /// Same as [followup](Self::followup), attaching the given files to the message, which can
+    /// be created using [attachments](crate::response::attachments).
+    pub fn followup_with_files<'b>(&'b self, files: &'b [Attachment]) -> CreateFollowup<'b> {
+        self.followup().attachments(files)
+    }
+
+    /// Updates the original response of the interaction, using the default
+    /// [allowed mentions](AllowedMentions) of the framework, which can be overridden.
+    pub fn update_response(&self) -> UpdateResponse<'_> {
+        self.update_response_with(&self.interaction_client)
+    }
+
+    /// Same as [update_response](Self::update_response), borrowing the given
+    /// [interaction client](InteractionClient) instead of the one of the context.
+    pub fn update_response_with<'b>(
+        &'b self,
+        client: &'b InteractionClient<'_>,
+    ) -> UpdateResponse<'b> {
+        let update = client.update_response(&self.interaction.token);
+        match self.allowed_mentions {
+            Some(allowed_mentions) => update.allowed_mentions(Some(allowed_mentions)),
+            None => update,
+        }
+    }
+
+    /// Parses a `Vec<T>` argument expanded into the numbered options `<name>1..<name>max`, the
+    /// first `min` of them being required.
+    pub async fn parse_varargs<T>(
+        &self,
+        name: &str,
+        min: usize,
+        max: usize,
+        iterator: &mut impl TakeOption<'_>
+    ) -> Result<Vec<T>, ParseError>
+    where
+        T: Parse<D>,
+        D: Send + Sync
+    {
+        let mut items = Vec::with_capacity(max);
+
+        for index in 1..=max {
+            let name = format!("{}{}", name, index);
+            if index <= min {
+                items.push(self.named_parse::<T>(&name, iterator).await?);
+            } else if let Some(item) = self.named_parse::<Option<T>>(&name, iterator).await? {
+                items.push(item);
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Creates a thread with the given name and type in the channel the interaction was invoked
+    /// in, returning a handle to the created thread.
+    pub async fn create_thread(
+        &self,
+        name: &str,
+        kind: ChannelType,
+    ) -> Result<Thread<'_, D>, Box<dyn Error + Send + Sync>> {
+        let channel_id = self.channel_id()?;
+        let thread = self
+            .http_client()
+            .create_thread(channel_id, name, kind)
+            .await?
+            .model()
+            .await?;
+
+        Ok(Thread::new(self, thread.id))
+    }
+
+    /// Creates a thread with the given name out of the response sent to the interaction,
+    /// returning a handle to the created thread.
+    ///
+    /// The interaction must have been responded already, such as by sending the response using
+    /// the [interaction client](Self::interaction_client), since the response returned by the
+    /// command is only sent once it finishes.
+    pub async fn create_thread_from_response(
+        &self,
+        name: &str,
+    ) -> Result<Thread<'_, D>, Box<dyn Error + Send + Sync>> {
+        let message = self
+            .interaction_client()
+            .response(&self.interaction.token)
+            .await?
+            .model()
+            .await?;
+        let thread = self
+            .http_client()
+            .create_thread_from_message(message.channel_id, message.id, name)
+            .await?
+            .model()
+            .await?;
+
+        Ok(Thread::new(self, thread.id))
+    }
+
+    /// Sends a direct message with the given content to the user who invoked the interaction,
+    /// returning the sent message.
+    pub async fn dm_author(
+        &self,
+        content: &str,
+    ) -> Result<MessageWrapper<'_, D>, Box<dyn Error + Send + Sync>> {
+        let channel_id = self.dm_channel().await?;
+        let message = self
+            .http_client()
+            .create_message(channel_id)
+            .content(content)
+            .await?
+            .model()
+            .await?;
+
+        Ok(MessageWrapper::new(self, message))
+    }
+
+    /// Same as [dm_author](Self::dm_author), sending the given embeds instead.
+    pub async fn dm_author_embeds(
+        &self,
+        embeds: &[Embed],
+    ) -> Result<MessageWrapper<'_, D>, Box<dyn Error + Send + Sync>> {
+        let channel_id = self.dm_channel().await?;
+        let message = self
+            .http_client()
+            .create_message(channel_id)
+            .embeds(embeds)
+            .await?
+            .model()
+            .await?;
+
+        Ok(MessageWrapper::new(self, message))
+    }
+
+    /// Opens a direct message channel with the user who invoked the interaction, discord gives
+    /// back the existing channel if it was already opened.
+    async fn dm_channel(&self) -> Result<Id<ChannelMarker>, Box<dyn Error + Send + Sync>> {
+        let user_id = self.author()?.id;
+        let channel = self
+            .http_client()
+            .create_private_channel(user_id)
+            .await?
+            .model()
+            .await?;
+
+        Ok(channel.id)
+    }
+
+    /// Gets whether the interaction has been responded, either by the context or by the
+    /// framework.
+    pub async fn response_state(&self) -> ResponseState {
+        *self.response_state.lock().await
+    }
+
+    /// Defers the interaction if it wasn't responded yet, allowing to [reply](Self::reply)
+    /// later, which then edits the original response.
+    pub async fn defer(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        defer(
+            self.interaction_client(),
+            &self.interaction,
+            &self.response_state,
+            self.ephemeral,
+        )
+        .await
+    }
+
+    /// Sends the given response, choosing how to send it out of whether the interaction has
+    /// been responded: it becomes the response of the interaction if it wasn't responded yet,
+    /// edits the original response if the interaction was [deferred](Self::defer), and is sent
+    /// as a followup message otherwise.
+    ///
+    /// The default [allowed mentions](AllowedMentions) of the framework are used, and messages
+    /// are ephemeral if the command is. Only the data of the response is used once the
+    /// interaction was responded, ignoring deferred responses.
+    pub async fn reply(
+        &self,
+        response: impl Into<InteractionResponse>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        reply(
+            self.http_client(),
+            self.application_id,
+            &self.interaction,
+            &self.response_state,
+            self.allowed_mentions,
+            self.ephemeral,
+            response.into(),
+        )
+        .await
+    }
+
     /// Responds to the interaction with an empty message to allow to respond later.
     ///
     /// When this method is used [update_response](Self::update_response) has to be used to edit the response.
@@ -111,3 +760,234 @@ impl<'a, D> SlashContext<'a, D> {
         })
     }
 }
+
+/// Checks whether the user who invoked the given interaction has an active entitlement to the
+/// given sku.
+pub(crate) fn has_entitlement(interaction: &Interaction, sku_id: Id<SkuMarker>) -> bool {
+    interaction
+        .entitlements
+        .iter()
+        .any(|entitlement| entitlement.sku_id == sku_id && !entitlement.deleted)
+}
+
+/// Defers the given interaction if it wasn't responded yet, see [SlashContext::defer].
+async fn defer(
+    client: InteractionClient<'_>,
+    interaction: &Interaction,
+    response_state: &Mutex<ResponseState>,
+    ephemeral: bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut state = response_state.lock().await;
+    if *state != ResponseState::Pending {
+        return Ok(());
+    }
+
+    let kind = if interaction.kind == InteractionType::MessageComponent {
+        InteractionResponseType::DeferredUpdateMessage
+    } else {
+        InteractionResponseType::DeferredChannelMessageWithSource
+    };
+    let response = InteractionResponse {
+        kind,
+        data: ephemeral.then(|| InteractionResponseData {
+            flags: Some(MessageFlags::EPHEMERAL),
+            ..Default::default()
+        }),
+    };
+
+    client
+        .create_response(interaction.id, &interaction.token, &response)
+        .await?;
+    *state = ResponseState::Deferred;
+    Ok(())
+}
+
+/// Sends the given response to the given interaction, depending on whether it has been
+/// responded, see [SlashContext::reply].
+async fn reply(
+    http: &Client,
+    application_id: Id<ApplicationMarker>,
+    interaction: &Interaction,
+    response_state: &Mutex<ResponseState>,
+    allowed_mentions: Option<&AllowedMentions>,
+    ephemeral: bool,
+    mut response: InteractionResponse,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let message = matches!(
+        response.kind,
+        InteractionResponseType::ChannelMessageWithSource
+            | InteractionResponseType::DeferredChannelMessageWithSource
+    );
+    if let Some(data) = &mut response.data {
+        if data.allowed_mentions.is_none() {
+            data.allowed_mentions = allowed_mentions.cloned();
+        }
+    }
+    if ephemeral && message {
+        let data = response.data.get_or_insert_with(Default::default);
+        let flags = data.flags.unwrap_or_else(MessageFlags::empty);
+        data.flags = Some(flags | MessageFlags::EPHEMERAL);
+    }
+
+    let mut state = response_state.lock().await;
+    let data = match (*state, &response.data) {
+        (ResponseState::Pending, _) => {
+            response::create_response(http, interaction, &response).await?;
+            *state = match response.kind {
+                InteractionResponseType::DeferredChannelMessageWithSource
+                | InteractionResponseType::DeferredUpdateMessage => ResponseState::Deferred,
+                _ => ResponseState::Responded,
+            };
+            return Ok(());
+        }
+        (_, None) => return Ok(()),
+        (_, Some(data)) => data,
+    };
+
+    if matches!(
+        response.kind,
+        InteractionResponseType::DeferredChannelMessageWithSource
+            | InteractionResponseType::DeferredUpdateMessage
+    ) {
+        return Ok(());
+    }
+
+    if *state == ResponseState::Deferred {
+        response::update_response(http, application_id, &interaction.token, data).await?;
+        *state = ResponseState::Responded;
+    } else {
+        response::create_followup(http, application_id, &interaction.token, data).await?;
+    }
+
+    Ok(())
+}
+
+/// An owned version of a [context](SlashContext), created using
+/// [SlashContext::to_owned](SlashContext::to_owned), which can be moved into spawned tasks to
+/// keep responding the interaction after the command returns.
+///
+/// The data of the framework is shared through an `Arc` rather than cloned, and so is the
+/// [response state](ResponseState), so [reply](Self::reply) keeps track of the responses sent by
+/// the command and the framework.
+///
+/// As it doesn't borrow the framework, waiting for interactions, emitting events and translating
+/// responses are not available.
+pub struct OwnedSlashContext<D> {
+    /// The http client used by the framework.
+    pub http_client: Arc<Client>,
+    /// The application id provided to the framework.
+    pub application_id: Id<ApplicationMarker>,
+    /// The data shared across the framework.
+    pub data: Arc<D>,
+    /// The interaction itself, shared with the context it was created from.
+    pub interaction: Arc<Interaction>,
+    /// The shard the interaction was received on, if provided to the framework.
+    pub shard: Option<ShardInfo>,
+    allowed_mentions: Option<AllowedMentions>,
+    ephemeral: bool,
+    response_state: Arc<Mutex<ResponseState>>,
+}
+
+impl<D> OwnedSlashContext<D> {
+    /// Gets the http client used by the framework.
+    pub fn http_client(&self) -> &Client {
+        &self.http_client
+    }
+
+    /// Creates an [interaction client](InteractionClient) out of the framework's
+    /// [http client](Client).
+    pub fn interaction_client(&self) -> InteractionClient<'_> {
+        self.http_client.interaction(self.application_id)
+    }
+
+    /// Whether the responses of the executed command are
+    /// [ephemeral](crate::command::Command::ephemeral) by default.
+    pub fn is_ephemeral(&self) -> bool {
+        self.ephemeral
+    }
+
+    /// Gets whether the interaction has been responded, shared with the context this was
+    /// created from and the framework.
+    pub async fn response_state(&self) -> ResponseState {
+        *self.response_state.lock().await
+    }
+
+    /// Defers the interaction if it wasn't responded yet, same as [SlashContext::defer].
+    pub async fn defer(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        defer(
+            self.interaction_client(),
+            &self.interaction,
+            &self.response_state,
+            self.ephemeral,
+        )
+        .await
+    }
+
+    /// Sends the given response depending on whether the interaction has been responded, same
+    /// as [SlashContext::reply].
+    pub async fn reply(
+        &self,
+        response: impl Into<InteractionResponse>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        reply(
+            self.http_client(),
+            self.application_id,
+            &self.interaction,
+            &self.response_state,
+            self.allowed_mentions.as_ref(),
+            self.ephemeral,
+            response.into(),
+        )
+        .await
+    }
+
+    /// Sends a followup message with the given content, using the default
+    /// [allowed mentions](AllowedMentions) of the framework and being ephemeral if the command
+    /// is.
+    pub async fn followup(&self, content: &str) -> Result<Message, Box<dyn Error + Send + Sync>> {
+        let interaction_client = self.interaction_client();
+        let mut followup = interaction_client
+            .create_followup(&self.interaction.token)
+            .content(content);
+        if self.ephemeral {
+            followup = followup.flags(MessageFlags::EPHEMERAL);
+        }
+        if let Some(allowed_mentions) = &self.allowed_mentions {
+            followup = followup.allowed_mentions(Some(allowed_mentions));
+        }
+
+        Ok(followup.await?.model().await?)
+    }
+
+    /// Replaces the content of the original response of the interaction, using the default
+    /// [allowed mentions](AllowedMentions) of the framework.
+    pub async fn update_response(
+        &self,
+        content: &str,
+    ) -> Result<Message, Box<dyn Error + Send + Sync>> {
+        let interaction_client = self.interaction_client();
+        let mut update = interaction_client
+            .update_response(&self.interaction.token)
+            .content(Some(content));
+        if let Some(allowed_mentions) = &self.allowed_mentions {
+            update = update.allowed_mentions(Some(allowed_mentions));
+        }
+
+        Ok(update.await?.model().await?)
+    }
+}
+
+impl<D> Clone for OwnedSlashContext<D> {
+    fn clone(&self) -> Self {
+        Self {
+            http_client: Arc::clone(&self.http_client),
+            application_id: self.application_id,
+            data: Arc::clone(&self.data),
+            interaction: Arc::clone(&self.interaction),
+            shard: self.shard.clone(),
+            allowed_mentions: self.allowed_mentions.clone(),
+            ephemeral: self.ephemeral,
+            response_state: Arc::clone(&self.response_state),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    concurrency::ConcurrencyScope,
+    twilight_exports::{
+        Interaction, InteractionResponse, InteractionResponseData, InteractionResponseType,
+        MessageFlags,
+    },
+};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The time a command can't be used again after being used inside of a
+/// [scope](ConcurrencyScope).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cooldown {
+    /// The time the command can't be used again.
+    pub duration: Duration,
+    /// The scope the cooldown is applied to.
+    pub scope: ConcurrencyScope,
+}
+
+impl Cooldown {
+    /// Creates a new cooldown.
+    pub fn new(duration: Duration, scope: ConcurrencyScope) -> Self {
+        Self { duration, scope }
+    }
+
+    /// Gets the key identifying the cooldown of the command with the given path for the scope
+    /// the given interaction belongs to.
+    pub(crate) fn key(&self, path: &str, interaction: &Interaction) -> String {
+        format!("{}:{}", path, self.scope.key(interaction))
+    }
+}
+
+/// The storage of the active [cooldowns](Cooldown), which allows sharing them between processes
+/// running the same bot by implementing it on top of a shared database.
+#[async_trait]
+pub trait CooldownStore: Send + Sync {
+    /// Starts the cooldown with the given key for the given duration unless it is already
+    /// active, in which case the time remaining for it to end is returned instead.
+    ///
+    /// Implementations must check and start the cooldown atomically, so concurrent uses of a
+    /// command can't both start it.
+    async fn try_start(&self, key: &str, duration: Duration) -> Option<Duration>;
+}
+
+/// A [cooldown store](CooldownStore) keeping the cooldowns in memory, used by default.
+#[derive(Debug, Default)]
+pub struct InMemoryCooldownStore {
+    cooldowns: Mutex<HashMap<String, Instant>>,
+}
+
+impl InMemoryCooldownStore {
+    /// Creates a new store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CooldownStore for InMemoryCooldownStore {
+    async fn try_start(&self, key: &str, duration: Duration) -> Option<Duration> {
+        let now = Instant::now();
+        let mut cooldowns = self.cooldowns.lock();
+
+        if let Some(end) = cooldowns.get(key) {
+            if *end > now {
+                return Some(*end - now);
+            }
+        }
+
+        cooldowns.retain(|_, end| *end > now);
+        cooldowns.insert(key.to_string(), now + duration);
+        None
+    }
+}
+
+/// A [cooldown store](CooldownStore) keeping the cooldowns in Redis, sharing them between every
+/// process connected to the same server.
+///
+/// Every cooldown is a key set using `SET key NX PX <ms>`, so Redis expires it by itself, and
+/// the time remaining of active cooldowns is read using `PTTL`. If Redis can't be reached, the
+/// error is logged and the command is allowed to run.
+#[cfg(feature = "redis")]
+#[derive(Clone)]
+pub struct RedisCooldownStore {
+    connection: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisCooldownStore {
+    /// Creates a store using the given connection, prefixing the keys with `zephyrus:cooldown:`.
+    pub fn new(connection: redis::aio::ConnectionManager) -> Self {
+        Self {
+            connection,
+            prefix: String::from("zephyrus:cooldown:"),
+        }
+    }
+
+    /// Connects to the Redis server at the given url, such as `redis://127.0.0.1/`.
+    pub async fn connect(url: &str) -> redis::RedisResult<Self> {
+        let client = redis::Client::open(url)?;
+        Ok(Self::new(client.get_connection_manager().await?))
+    }
+
+    /// Sets the prefix of the keys the cooldowns are stored at.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Starts the cooldown unless it is already active, returning the time remaining for it to
+    /// end in that case.
+    async fn start(&self, key: &str, duration: Duration) -> redis::RedisResult<Option<Duration>> {
+        let key = format!("{}{}", self.prefix, key);
+        let mut connection = self.connection.clone();
+        // Redis rejects expiration times of zero
+        let millis = (duration.as_millis() as u64).max(1);
+
+        let started: Option<String> = redis::cmd("SET")
+            .arg(&key)
+            .arg(1)
+            .arg("NX")
+            .arg("PX")
+            .arg(millis)
+            .query_async(&mut connection)
+            .await?;
+        if started.is_some() {
+            return Ok(None);
+        }
+
+        // Negative values mean the key expired or has no expiration, allowing the command
+        let remaining: i64 = redis::cmd("PTTL").arg(&key).query_async(&mut connection).await?;
+        Ok((remaining > 0).then(|| Duration::from_millis(remaining as u64)))
+    }
+}
+
+#[cfg(feature = "redis")]
+impl std::fmt::Debug for RedisCooldownStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisCooldownStore")
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl CooldownStore for RedisCooldownStore {
+    async fn try_start(&self, key: &str, duration: Duration) -> Option<Duration> {
+        match self.start(key, duration).await {
+            Ok(remaining) => remaining,
+            Err(why) => {
+                tracing::warn!("Failed to check the cooldown {} in Redis: {}", key, why);
+                None
+            }
+        }
+    }
+}
+
+/// Creates the response sent by default when a command is used while on cooldown.
+pub(crate) fn default_response(remaining: Duration) -> InteractionResponse {
+    InteractionResponse {
+        kind: InteractionResponseType::ChannelMessageWithSource,
+        data: Some(InteractionResponseData {
+            content: Some(format!(
+                "This command is on cooldown, try again in {} seconds",
+                remaining.as_secs().max(1)
+            )),
+            flags: Some(MessageFlags::EPHEMERAL),
+            ..Default::default()
+        }),
+    }
+}
@@ -0,0 +1,85 @@
+//! Static dispatch of commands, avoiding the boxed future every command execution allocates.
+//!
+//! By default, the framework executes every command through its boxed [function](Command::fun).
+//! Commands defined using `#[commands(static_dispatch)]` additionally implement
+//! [StaticDispatch] for their type, whose [execute](StaticDispatch::execute) calls the
+//! matching command directly, returning a future whose type is known at compile time. Processing
+//! the interactions using [process_static](crate::framework::Framework::process_static) then
+//! executes those commands without boxing their futures.
+//!
+//! Only the command handlers themselves are dispatched statically. The hooks, such as the
+//! before, after, check and error hooks, and the commands not covered by the strategy, such as
+//! subcommands, keep running through their boxed futures.
+use crate::{
+    command::{Command, CommandResult, DefaultError},
+    context::SlashContext,
+};
+use pin_project_lite::pin_project;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A strategy used by the framework to execute commands.
+pub trait StaticDispatch<D, E = DefaultError> {
+    /// Executes the given command, invoked using the given path.
+    fn execute<'a>(
+        command: &'a Command<D, E>,
+        path: &'a str,
+        ctx: &'a SlashContext<'a, D>,
+    ) -> impl Future<Output = CommandResult<E>> + Send + 'a;
+}
+
+/// The default [strategy](StaticDispatch), executing every command through its boxed
+/// [function](Command::fun).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BoxedDispatch;
+
+impl<D, E> StaticDispatch<D, E> for BoxedDispatch {
+    fn execute<'a>(
+        command: &'a Command<D, E>,
+        _: &'a str,
+        ctx: &'a SlashContext<'a, D>,
+    ) -> impl Future<Output = CommandResult<E>> + Send + 'a {
+        (command.fun)(ctx)
+    }
+}
+
+pin_project! {
+    /// One of two futures, used by the code generated by `#[commands(static_dispatch)]` to
+    /// return the future of the command it executes.
+    #[doc(hidden)]
+    #[project = EitherProj]
+    pub enum Either<L, R> {
+        Left { #[pin] left: L },
+        Right { #[pin] right: R },
+    }
+}
+
+impl<L, R> Either<L, R> {
+    /// Creates the left variant.
+    pub fn left(left: L) -> Self {
+        Self::Left { left }
+    }
+
+    /// Creates the right variant.
+    pub fn right(right: R) -> Self {
+        Self::Right { right }
+    }
+}
+
+impl<L, R> Future for Either<L, R>
+where
+    L: Future,
+    R: Future<Output = L::Output>,
+{
+    type Output = L::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            EitherProj::Left { left } => left.poll(cx),
+            EitherProj::Right { right } => right.poll(cx),
+        }
+    }
+}
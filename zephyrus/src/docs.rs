@@ -0,0 +1,230 @@
+use crate::{
+    argument::CommandArgument,
+    framework::Framework,
+    group::GroupParent,
+    tree::CommandEntry,
+    twilight_exports::Permissions,
+};
+use serde::Serialize;
+use std::fmt::{Display, Formatter, Result as FmtResult, Write};
+use twilight_model::application::command::CommandOptionValue;
+
+/// The documentation of every command of the framework, generated by
+/// [docs](Framework::docs) and rendered as Markdown by its [Display] implementation.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CommandDocs {
+    /// The top level groups of the framework, sorted by name.
+    pub groups: Vec<GroupDoc>,
+    /// Every executable command of the framework, including subcommands, sorted by path.
+    pub commands: Vec<CommandDoc>,
+}
+
+/// The documentation of a top level group.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupDoc {
+    /// The name of the group.
+    pub name: String,
+    /// The description of the group.
+    pub description: String,
+}
+
+/// The documentation of an executable command.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandDoc {
+    /// The path used to invoke the command, such as `parent group subcommand`.
+    pub path: String,
+    /// The top level group the command belongs to, if it's a subcommand.
+    pub parent: Option<String>,
+    /// The description of the command.
+    pub description: String,
+    /// The category of the command.
+    pub category: Option<String>,
+    /// The names of the permissions required to use the command.
+    pub required_permissions: Vec<String>,
+    /// The cooldown of the command, in seconds.
+    pub cooldown: Option<f64>,
+    /// Whether the command requires an entitlement to a premium sku.
+    pub premium: bool,
+    /// The arguments of the command.
+    pub arguments: Vec<ArgumentDoc>,
+}
+
+/// The documentation of an argument.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArgumentDoc {
+    /// The name of the argument.
+    pub name: String,
+    /// The description of the argument.
+    pub description: String,
+    /// The type of the argument, such as `String` or `User`.
+    pub kind: String,
+    /// Whether the argument is required.
+    pub required: bool,
+    /// The constraints of the argument, such as its limits, choices and channel types.
+    pub constraints: Vec<String>,
+    /// Whether the argument is autocompleted.
+    pub autocomplete: bool,
+}
+
+impl ArgumentDoc {
+    fn new<D>(argument: &CommandArgument<D>) -> Self {
+        let mut constraints = Vec::new();
+        let value = |value: &CommandOptionValue| match value {
+            CommandOptionValue::Integer(i) => i.to_string(),
+            CommandOptionValue::Number(n) => n.to_string(),
+        };
+
+        if let Some(limits) = &argument.limits {
+            if let Some(min) = &limits.min {
+                constraints.push(format!("min: {}", value(min)));
+            }
+            if let Some(max) = &limits.max {
+                constraints.push(format!("max: {}", value(max)));
+            }
+            if let Some(min) = limits.min_length {
+                constraints.push(format!("min length: {}", min));
+            }
+            if let Some(max) = limits.max_length {
+                constraints.push(format!("max length: {}", max));
+            }
+        }
+
+        if let Some(choices) = &argument.choices {
+            let choices = choices
+                .iter()
+                .map(|choice| format!("`{}`", choice.name))
+                .collect::<Vec<_>>();
+            constraints.push(format!("one of: {}", choices.join(", ")));
+        }
+
+        if let Some(types) = argument.channel_types.as_ref().filter(|types| !types.is_empty()) {
+            let types = types.iter().map(|kind| format!("{:?}", kind)).collect::<Vec<_>>();
+            constraints.push(format!("channels: {}", types.join(", ")));
+        }
+
+        Self {
+            name: argument.name.to_string(),
+            description: argument.description.to_string(),
+            kind: format!("{:?}", argument.kind),
+            required: argument.required,
+            constraints,
+            autocomplete: argument.autocomplete.is_some(),
+        }
+    }
+}
+
+impl CommandDoc {
+    fn new<D, E>(entry: &CommandEntry<'_, D, E>) -> Self {
+        let command = entry.command;
+        Self {
+            path: entry.path.to_string(),
+            parent: entry.parent.map(|parent| parent.name.to_string()),
+            description: command.description.to_string(),
+            category: entry.category().map(String::from),
+            required_permissions: entry
+                .required_permissions()
+                .map(permission_names)
+                .unwrap_or_default(),
+            cooldown: command.cooldown.map(|cooldown| cooldown.duration.as_secs_f64()),
+            premium: command.premium_sku.is_some(),
+            arguments: command.arguments.iter().map(ArgumentDoc::new).collect(),
+        }
+    }
+}
+
+/// Gets the names of the given permissions.
+fn permission_names(permissions: Permissions) -> Vec<String> {
+    permissions.iter_names().map(|(name, _)| name.to_string()).collect()
+}
+
+impl Display for CommandDocs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("# Commands\n")?;
+        let mut parent = None;
+
+        for command in &self.commands {
+            if command.parent != parent {
+                parent = command.parent.clone();
+                if let Some(group) = self.groups.iter().find(|group| Some(&group.name) == parent.as_ref()) {
+                    write!(f, "\n## /{}\n\n{}\n", group.name, group.description)?;
+                }
+            }
+
+            let level = if parent.is_some() { "###" } else { "##" };
+            write!(f, "\n{} /{}\n\n{}\n", level, command.path, command.description)?;
+
+            let mut details = String::new();
+            if let Some(category) = &command.category {
+                writeln!(details, "- Category: {}", category)?;
+            }
+            if !command.required_permissions.is_empty() {
+                writeln!(details, "- Required permissions: {}", command.required_permissions.join(", "))?;
+            }
+            if let Some(cooldown) = command.cooldown {
+                writeln!(details, "- Cooldown: {}s", cooldown)?;
+            }
+            if command.premium {
+                details.push_str("- Premium only\n");
+            }
+            if !details.is_empty() {
+                write!(f, "\n{}", details)?;
+            }
+
+            if command.arguments.is_empty() {
+                continue;
+            }
+
+            f.write_str("\n| Argument | Type | Required | Description | Constraints |\n")?;
+            f.write_str("| --- | --- | --- | --- | --- |\n")?;
+            for argument in &command.arguments {
+                let mut constraints = argument.constraints.clone();
+                if argument.autocomplete {
+                    constraints.push(String::from("autocompleted"));
+                }
+
+                writeln!(
+                    f,
+                    "| `{}` | {} | {} | {} | {} |",
+                    argument.name,
+                    argument.kind,
+                    if argument.required { "Yes" } else { "No" },
+                    escape(&argument.description),
+                    escape(&constraints.join("; "))
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Escapes the pipes of the given text, so it can be placed inside of a table cell.
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+impl<D, E> Framework<D, E> {
+    /// Documents every command, group, argument, constraint and permission requirement of the
+    /// framework, which can be serialized or rendered as Markdown.
+    pub fn docs(&self) -> CommandDocs {
+        let mut groups = self.groups.values().collect::<Vec<&GroupParent<D, E>>>();
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
+
+        CommandDocs {
+            groups: groups
+                .into_iter()
+                .map(|group| GroupDoc {
+                    name: group.name.to_string(),
+                    description: group.description.to_string(),
+                })
+                .collect(),
+            commands: self.iter_commands().map(|entry| CommandDoc::new(&entry)).collect(),
+        }
+    }
+
+    /// Generates the Markdown documentation of every command of the framework, see
+    /// [docs](Self::docs).
+    pub fn generate_docs(&self) -> String {
+        self.docs().to_string()
+    }
+}
@@ -0,0 +1,282 @@
+//! Construction of commands at runtime, without using the macros.
+//!
+//! Commands defined by configuration files or plugins can describe their arguments using
+//! [dynamic arguments](DynamicArgument), which are parsed using boxed closures before calling the
+//! command handler.
+use crate::{
+    argument::{ArgumentLimits, CommandArgument},
+    command::{Command, CommandResult},
+    context::SlashContext,
+    event::FrameworkEvent,
+    parse::ParseError,
+    twilight_exports::{
+        ChannelType, CommandOptionChoice, CommandOptionType, CommandOptionValue, InteractionData,
+    },
+    BoxFuture,
+};
+use std::{any::Any, borrow::Cow, collections::HashMap};
+
+/// A parsed argument value.
+pub type ArgumentValue = Box<dyn Any + Send + Sync>;
+/// A function parsing the raw value of a [dynamic argument](DynamicArgument).
+pub type ArgumentParser = Box<dyn Fn(&CommandOptionValue) -> Result<ArgumentValue, String> + Send + Sync>;
+
+/// An argument described at runtime, parsed using a boxed closure.
+pub struct DynamicArgument {
+    name: Cow<'static, str>,
+    description: Cow<'static, str>,
+    kind: CommandOptionType,
+    required: bool,
+    choices: Option<Vec<CommandOptionChoice>>,
+    limits: Option<ArgumentLimits>,
+    channel_types: Option<Vec<ChannelType>>,
+    sensitive: bool,
+    parser: Option<ArgumentParser>,
+}
+
+impl DynamicArgument {
+    /// Creates a new optional argument of the given type.
+    ///
+    /// If no [parser](Self::parser) is provided, the raw [value](CommandOptionValue) of the
+    /// argument is stored.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
+        kind: CommandOptionType,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            kind,
+            required: false,
+            choices: None,
+            limits: None,
+            channel_types: None,
+            sensitive: false,
+            parser: None,
+        }
+    }
+
+    /// Sets whether the argument is required.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Sets the choices the user can choose from.
+    pub fn choices(mut self, choices: Vec<CommandOptionChoice>) -> Self {
+        self.choices = Some(choices);
+        self
+    }
+
+    /// Sets the input limits of the argument.
+    pub fn limits(mut self, limits: ArgumentLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Sets the channel types allowed as input, only valid for channel arguments.
+    pub fn channel_types(mut self, types: Vec<ChannelType>) -> Self {
+        self.channel_types = Some(types);
+        self
+    }
+
+    /// Sets whether the value of the argument is
+    /// [sensitive](CommandArgument::sensitive), redacting it wherever it's shown.
+    pub fn sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// Sets the function used to parse the argument, returning an error message if the value is
+    /// not valid.
+    pub fn parser<F, T>(mut self, fun: F) -> Self
+    where
+        F: Fn(&CommandOptionValue) -> Result<T, String> + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+    {
+        self.parser = Some(Box::new(move |value| {
+            fun(value).map(|parsed| Box::new(parsed) as ArgumentValue)
+        }));
+        self
+    }
+
+    /// Creates the [command argument](CommandArgument) used to register this argument.
+    fn as_argument<D>(&self) -> CommandArgument<D> {
+        CommandArgument {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            required: self.required,
+            kind: self.kind,
+            choices: self.choices.clone(),
+            limits: self.limits,
+            channel_types: self.channel_types.clone(),
+            autocomplete: None,
+            sensitive: self.sensitive,
+        }
+    }
+
+    /// Parses the given value of this argument.
+    fn parse(&self, value: &CommandOptionValue) -> Result<ArgumentValue, ParseError> {
+        let result = match self.limits {
+            Some(limits) => limits.check(value),
+            None => Ok(()),
+        };
+
+        result
+            .and_then(|_| match &self.parser {
+                Some(parser) => parser(value),
+                None => Ok(Box::new(value.clone()) as ArgumentValue),
+            })
+            .map_err(|error| {
+                let why = ParseError::Parsing {
+                    argument_name: self.name.to_string(),
+                    required: self.required,
+                    type_: format!("{:?}", self.kind),
+                    error,
+                };
+                if self.sensitive {
+                    why.redact()
+                } else {
+                    why
+                }
+            })
+    }
+}
+
+/// The parsed [dynamic arguments](DynamicArgument) of a command invocation.
+#[derive(Default)]
+pub struct Arguments {
+    values: HashMap<String, ArgumentValue>,
+}
+
+impl Arguments {
+    /// Gets a reference to the value of the given argument, returning `None` if the argument
+    /// was not provided or its value is not of type `T`.
+    pub fn get<T: 'static>(&self, name: &str) -> Option<&T> {
+        self.values.get(name)?.downcast_ref()
+    }
+
+    /// Takes the value of the given argument out, returning `None` if the argument was not
+    /// provided or its value is not of type `T`.
+    pub fn take<T: 'static>(&mut self, name: &str) -> Option<T> {
+        if !self.values.get(name)?.is::<T>() {
+            return None;
+        }
+
+        self.values.remove(name)?.downcast().ok().map(|value| *value)
+    }
+
+    /// Checks whether the given argument was provided.
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+}
+
+/// A builder of a [command](Command) whose arguments are described at runtime.
+///
+/// ```rust,ignore
+/// let command = CommandBuilder::new("echo", "Echoes the given text")
+///     .argument(
+///         DynamicArgument::new("text", "The text to echo", CommandOptionType::String)
+///             .required(true)
+///             .parser(|value| match value {
+///                 CommandOptionValue::String(s) => Ok(s.clone()),
+///                 _ => Err(String::from("Expected a string")),
+///             }),
+///     )
+///     .handler(|ctx: &SlashContext<()>, mut args| Box::pin(async move {
+///         let text = args.take::<String>("text").unwrap();
+///         // ...
+///     }));
+/// ```
+pub struct CommandBuilder {
+    name: Cow<'static, str>,
+    description: Cow<'static, str>,
+    arguments: Vec<DynamicArgument>,
+}
+
+impl CommandBuilder {
+    /// Creates a new builder of a command with the given name and description.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            arguments: Vec::new(),
+        }
+    }
+
+    /// Adds an argument to the command.
+    pub fn argument(mut self, argument: DynamicArgument) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+
+    /// Builds the command, which parses its arguments and then calls the given handler.
+    ///
+    /// If any argument fails to parse, the handler is not called and the
+    /// [parse error](ParseError) is returned as the command error.
+    pub fn handler<D, E, F>(self, fun: F) -> Command<D, E>
+    where
+        E: From<ParseError> + 'static,
+        F: for<'a> Fn(&'a SlashContext<'a, D>, Arguments) -> BoxFuture<'a, CommandResult<E>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let registered = self.arguments.iter().map(DynamicArgument::as_argument).collect();
+        let arguments = self.arguments;
+
+        let mut command = Command::new(move |ctx| match parse_arguments(ctx, &arguments) {
+            Ok(parsed) => fun(ctx, parsed),
+            Err(why) => Box::pin(async move { Err(E::from(why)) }),
+        })
+        .name(self.name)
+        .description(self.description);
+
+        command.arguments = registered;
+        command
+    }
+}
+
+/// Parses the given arguments out of the options provided to the command.
+fn parse_arguments<D>(
+    ctx: &SlashContext<'_, D>,
+    arguments: &[DynamicArgument],
+) -> Result<Arguments, ParseError> {
+    let options = match ctx.interaction.data.as_ref() {
+        Some(InteractionData::ApplicationCommand(data)) => data.options.as_slice(),
+        _ => &[],
+    };
+
+    if let Some(unknown) = options
+        .iter()
+        .find(|option| !arguments.iter().any(|argument| argument.name == option.name))
+    {
+        return Err(ParseError::StructureMismatch(format!("Unknown argument {}", unknown.name)));
+    }
+
+    let mut parsed = Arguments::default();
+    for argument in arguments {
+        let value = match options.iter().find(|option| option.name == argument.name) {
+            Some(option) => &option.value,
+            None if argument.required => {
+                return Err(ParseError::StructureMismatch(format!("{} not found", argument.name)))
+            }
+            None => continue,
+        };
+
+        let result = argument.parse(value);
+        ctx.emit(FrameworkEvent::ArgumentParsed {
+            argument: &argument.name,
+            success: result.is_ok(),
+        });
+
+        parsed.values.insert(argument.name.to_string(), result?);
+    }
+
+    Ok(parsed)
+}
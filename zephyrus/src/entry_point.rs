@@ -0,0 +1,136 @@
+use crate::{
+    builder::FnPointer,
+    command::{Command, DefaultError},
+    retry::RetryPolicy,
+    twilight_exports::{ApplicationMarker, Client, Command as TwilightCommand, CommandType, Id},
+};
+use serde::Serialize;
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Formatter, Result as FmtResult},
+};
+use twilight_http::{request::Request, routing::Route};
+
+/// The raw value of the `PRIMARY_ENTRY_POINT` command type.
+pub const ENTRY_POINT_TYPE: u8 = 4;
+
+/// How an [entry point command](EntryPoint) invocation is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryPointHandler {
+    /// The interaction is sent to the application, which handles it using a command.
+    AppHandler,
+    /// Discord launches the activity itself, the application doesn't receive any interaction.
+    DiscordLaunchActivity,
+}
+
+impl EntryPointHandler {
+    /// Gets the raw value of the handler.
+    pub fn value(self) -> u8 {
+        match self {
+            Self::AppHandler => 1,
+            Self::DiscordLaunchActivity => 2,
+        }
+    }
+}
+
+/// The primary entry point command of an application, used to launch its activity.
+///
+/// Entry point commands can only be registered globally, so they are only registered by
+/// [register_global_commands](crate::framework::Framework::register_global_commands).
+pub struct EntryPoint<D, E = DefaultError> {
+    /// The name of the entry point command.
+    pub name: Cow<'static, str>,
+    /// The description of the entry point command.
+    pub description: Cow<'static, str>,
+    /// The command handling the invocations, only present if the
+    /// [handler](EntryPointHandler) is [AppHandler](EntryPointHandler::AppHandler).
+    pub command: Option<Command<D, E>>,
+}
+
+impl<D, E> Debug for EntryPoint<D, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("EntryPoint")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("kind", &self.kind())
+            .field("command", &self.command)
+            .finish()
+    }
+}
+
+impl<D, E> EntryPoint<D, E> {
+    /// Creates an entry point whose invocations are handled by discord, which launches the
+    /// activity without sending an interaction to the application.
+    pub fn launch_activity(
+        name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            command: None,
+        }
+    }
+
+    /// Creates an entry point whose invocations are handled by the given command, the name and
+    /// description of the command are used for the entry point.
+    pub fn handler(fun: FnPointer<Command<D, E>>) -> Self {
+        let command = fun();
+        Self {
+            name: command.name.clone(),
+            description: command.description.clone(),
+            command: Some(command),
+        }
+    }
+
+    /// Gets how the invocations of this entry point are handled.
+    pub fn kind(&self) -> EntryPointHandler {
+        if self.command.is_some() {
+            EntryPointHandler::AppHandler
+        } else {
+            EntryPointHandler::DiscordLaunchActivity
+        }
+    }
+
+    /// Checks whether the given command type is the entry point one.
+    pub(crate) fn is_entry_point(kind: CommandType) -> bool {
+        u8::from(kind) == ENTRY_POINT_TYPE
+    }
+
+    /// Registers the entry point as a global command of the given application.
+    pub(crate) async fn register(
+        &self,
+        client: &Client,
+        application_id: Id<ApplicationMarker>,
+        retry_policy: &RetryPolicy,
+    ) -> Result<TwilightCommand, Box<dyn std::error::Error + Send + Sync>> {
+        let payload = EntryPointPayload {
+            name: &self.name,
+            description: &self.description,
+            kind: ENTRY_POINT_TYPE,
+            handler: self.kind().value(),
+        };
+
+        let request = Request::builder(&Route::CreateGlobalCommand {
+            application_id: application_id.get(),
+        })
+        .json(&payload)
+        .build()?;
+
+        let response = retry_policy
+            .run(|| client.request::<TwilightCommand>(request.clone()))
+            .await?;
+
+        Ok(response.model().await?)
+    }
+}
+
+/// The payload sent to discord to register an [entry point](EntryPoint).
+#[derive(Serialize)]
+struct EntryPointPayload<'a> {
+    name: &'a str,
+    description: &'a str,
+    #[serde(rename = "type")]
+    kind: u8,
+    handler: u8,
+}
@@ -0,0 +1,59 @@
+use crate::hook::HookVerdict;
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The errors produced by the framework itself while executing a command, delivered to the
+/// [error hook](crate::hook::ErrorHook).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FrameworkError {
+    /// The command handler panicked, containing the panic message.
+    Panic(String),
+    /// The command wasn't executed because a [before hook](crate::hook::BeforeHook) or a check
+    /// returned the contained verdict, which is never [continue](HookVerdict::Continue).
+    Skipped(HookVerdict),
+}
+
+impl Display for FrameworkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Panic(message) => write!(f, "Command panicked: {}", message),
+            Self::Skipped(HookVerdict::Skip { reason }) => write!(f, "Command skipped: {}", reason),
+            Self::Skipped(_) => f.write_str("Command skipped"),
+        }
+    }
+}
+
+impl Error for FrameworkError {}
+
+/// A future catching the panics of the inner future, returning the panic message as an error.
+pub(crate) struct CatchUnwind<F>(pub(crate) F);
+
+impl<F: Future + Unpin> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, FrameworkError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match panic::catch_unwind(AssertUnwindSafe(|| Pin::new(&mut self.0).poll(cx))) {
+            Ok(poll) => poll.map(Ok),
+            Err(payload) => Poll::Ready(Err(FrameworkError::Panic(panic_message(payload)))),
+        }
+    }
+}
+
+/// Gets the message of the given panic payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("Box<dyn Any>"),
+        },
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    audit::redact_options,
+    command::CommandPath,
+    error::FrameworkError,
+    twilight_exports::{Id, Interaction, InteractionData, InteractionMarker, InteractionResponseType},
+};
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    time::Duration,
+};
+
+/// A function observing the [events](FrameworkEvent) emitted by the framework.
+pub type Observer = Box<dyn Fn(&FrameworkEvent<'_>) + Send + Sync>;
+
+/// An event emitted by the framework at every stage of an interaction dispatch, received by the
+/// observers registered using [observer](crate::builder::FrameworkBuilder::observer).
+///
+/// The values of [sensitive](crate::argument::CommandArgument::sensitive) arguments are
+/// [redacted](crate::audit::REDACTED) when the event is debug formatted.
+#[non_exhaustive]
+pub enum FrameworkEvent<'a> {
+    /// An interaction was received by the framework.
+    InteractionReceived {
+        /// The received interaction.
+        interaction: &'a Interaction,
+        /// The names of the sensitive arguments of the invoked command, if any.
+        sensitive: &'a [&'a str],
+    },
+    /// The command an interaction refers to was found.
+    CommandResolved {
+        /// The path used to invoke the command.
+        path: CommandPath<'a>,
+    },
+    /// The before hook and the checks of a command were evaluated.
+    ChecksEvaluated {
+        /// The name of the command.
+        command: &'a str,
+        /// Whether the command is going to be executed.
+        passed: bool,
+    },
+    /// An argument of a command was parsed.
+    ArgumentParsed {
+        /// The name of the argument.
+        argument: &'a str,
+        /// Whether the argument was parsed successfully.
+        success: bool,
+    },
+    /// A command handler finished its execution.
+    HandlerCompleted {
+        /// The name of the command.
+        command: &'a str,
+        /// Whether the handler returned successfully.
+        success: bool,
+        /// The time the handler took to complete.
+        elapsed: Duration,
+    },
+    /// An autocomplete handler finished its execution and its suggestions were sent.
+    AutocompleteCompleted {
+        /// The path of the command being autocompleted, as shown in discord.
+        command: &'a str,
+        /// The name of the argument being autocompleted.
+        argument: &'a str,
+        /// The time the handler took to complete.
+        elapsed: Duration,
+        /// The error produced by the handler, if it failed.
+        error: Option<&'a FrameworkError>,
+        /// Whether discord accepted the suggestions.
+        responded: bool,
+    },
+    /// A response to an interaction was sent by the framework.
+    ResponseSent {
+        /// The id of the responded interaction.
+        interaction_id: Id<InteractionMarker>,
+        /// The type of the response.
+        kind: InteractionResponseType,
+        /// Whether discord accepted the response.
+        success: bool,
+    },
+}
+
+impl Debug for FrameworkEvent<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InteractionReceived { interaction, sensitive } => {
+                let mut interaction = (*interaction).clone();
+                if let Some(InteractionData::ApplicationCommand(data)) = &mut interaction.data {
+                    redact_options(&mut data.options, sensitive);
+                }
+                f.debug_struct("InteractionReceived")
+                    .field("interaction", &interaction)
+                    .field("sensitive", sensitive)
+                    .finish()
+            }
+            Self::CommandResolved { path } => f
+                .debug_struct("CommandResolved")
+                .field("path", path)
+                .finish(),
+            Self::ChecksEvaluated { command, passed } => f
+                .debug_struct("ChecksEvaluated")
+                .field("command", command)
+                .field("passed", passed)
+                .finish(),
+            Self::ArgumentParsed { argument, success } => f
+                .debug_struct("ArgumentParsed")
+                .field("argument", argument)
+                .field("success", success)
+                .finish(),
+            Self::HandlerCompleted { command, success, elapsed } => f
+                .debug_struct("HandlerCompleted")
+                .field("command", command)
+                .field("success", success)
+                .field("elapsed", elapsed)
+                .finish(),
+            Self::AutocompleteCompleted { command, argument, elapsed, error, responded } => f
+                .debug_struct("AutocompleteCompleted")
+                .field("command", command)
+                .field("argument", argument)
+                .field("elapsed", elapsed)
+                .field("error", error)
+                .field("responded", responded)
+                .finish(),
+            Self::ResponseSent { interaction_id, kind, success } => f
+                .debug_struct("ResponseSent")
+                .field("interaction_id", interaction_id)
+                .field("kind", kind)
+                .field("success", success)
+                .finish(),
+        }
+    }
+}
+
+/// Emits the given event to all the given observers.
+pub(crate) fn emit(observers: &[Observer], event: FrameworkEvent<'_>) {
+    for observer in observers {
+        observer(&event);
+    }
+}
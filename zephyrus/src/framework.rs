@@ -1,20 +1,55 @@
 use crate::{
+    audit::{AuditLog, AuditOutcome},
     argument::CommandArgument,
     builder::{FrameworkBuilder, WrappedClient},
-    command::{Command, CommandMap},
-    context::{AutocompleteContext, Focused, SlashContext},
-    group::{GroupParent, ParentGroupMap, ParentType},
-    hook::{AfterHook, BeforeHook},
+    button::Button,
+    command::{Command, CommandPath, DefaultError},
+    concurrency::{ConcurrencyTracker, GlobalLimiter},
+    compat,
+    cooldown::{CooldownStore, InMemoryCooldownStore},
+    context::{has_entitlement, AutocompleteContext, Focused, ResponseState, SlashContext},
+    dispatch::{BoxedDispatch, StaticDispatch},
+    entry_point::{EntryPoint, ENTRY_POINT_TYPE},
+    error::{CatchUnwind, FrameworkError},
+    event::{self, FrameworkEvent, Observer},
+    group::{sorted, GroupParent},
+    localization::{Translator, DEFAULT_FALLBACK_LOCALE},
+    payload::CommandPayload,
+    registration::{RegistrationReport, RegistrationStatus},
+    help::HelpCommand,
+    hook::{
+        AfterHook, BeforeHook, CommandOutcome, ErrorHook, HookVerdict, Intercept, InterceptorHook,
+        UnhandledComponentHook, UnhandledInteractionHook, UnknownCommandHook,
+    },
+    parse::ParseError,
+    response,
+    retry::RetryPolicy,
+    route::Routes,
+    shard::ShardInfo,
+    template::{DynamicChanges, DynamicCommands, TemplateCommand},
     twilight_exports::{
-        ApplicationMarker, Client,
-        Command as TwilightCommand, CommandData, CommandDataOption, CommandOption, CommandOptionType,
-        CommandOptionValue, GuildMarker, Id, Interaction, InteractionData, InteractionType, InteractionClient, InteractionResponse,
-        InteractionResponseType, OptionsCommandOptionData,
+        AllowedMentions, ApplicationMarker, Client, Component,
+        Command as TwilightCommand, CommandData, CommandDataOption,
+        CommandOptionValue, CommandType, GuildMarker, Id, Interaction, InteractionData, InteractionType, InteractionClient, InteractionResponse,
+        InteractionResponseData, InteractionResponseType, MessageFlags, Permissions,
     },
-    waiter::WaiterWaker
+    waiter::{new_pair, InteractionWaiter, Waiters}
+};
+#[cfg(feature = "opentelemetry")]
+use crate::telemetry;
+use tokio::sync::SemaphorePermit;
+use tracing::{debug, warn};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    future::IntoFuture,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use tracing::debug;
-use parking_lot::Mutex;
+
+/// The time after which the executions waiting for the
+/// [global concurrency limit](crate::concurrency::GlobalConcurrencyLimit) are deferred.
+pub const QUEUED_DEFER_AFTER: Duration = Duration::from_secs(2);
 
 macro_rules! extract {
     ($expr:expr => $variant:ident) => {
@@ -25,49 +60,138 @@ macro_rules! extract {
     };
 }
 
-macro_rules! focused {
-    ($($tt:tt)*) => {
-        match $($tt)* {
-            CommandOptionValue::Focused(input, kind) => Focused {
-                input: input.clone(),
-                kind: *kind
-            },
-            _ => return None
-        }
-    };
-}
-
 /// The framework used to dispatch slash commands.
-pub struct Framework<D> {
+pub struct Framework<D, E = DefaultError> {
     /// The http client used by the framework.
     pub http_client: WrappedClient,
     /// The application id of the client.
     pub application_id: Id<ApplicationMarker>,
-    /// Data shared across all command and hook invocations.
-    pub data: D,
+    /// Data shared across all command and hook invocations, kept in an `Arc` so
+    /// [owned contexts](crate::context::OwnedSlashContext) can share it.
+    pub data: Arc<D>,
     /// A map of simple commands.
-    pub commands: CommandMap<D>,
+    pub commands: Routes<Command<D, E>>,
     /// A map of command groups including all children.
-    pub groups: ParentGroupMap<D>,
+    pub groups: Routes<GroupParent<D, E>>,
+    /// The buttons handling message components, checked in registration order.
+    pub buttons: Vec<Button<D, E>>,
     /// A hook executed before the command.
     pub before: Option<BeforeHook<D>>,
     /// A hook executed after command's execution.
-    pub after: Option<AfterHook<D>>,
-    pub waiters: Mutex<Vec<WaiterWaker<D>>>
+    pub after: Option<AfterHook<D, E>>,
+    /// A hook executed when the framework fails to execute a command.
+    pub on_error: Option<ErrorHook<D>>,
+    /// A hook executed before routing every interaction.
+    pub interceptor: Option<InterceptorHook<D>>,
+    /// A hook executed when an interaction refers to an unknown command.
+    pub unknown_command: Option<UnknownCommandHook<D>>,
+    /// A hook executed when a message component isn't handled by any button.
+    pub unhandled_component: Option<UnhandledComponentHook<D>>,
+    /// A hook executed when an interaction of a type the framework doesn't handle is received.
+    pub unhandled_interaction: Option<UnhandledInteractionHook<D>>,
+    /// The time after which the interactions not yet responded are deferred.
+    pub auto_defer: Option<Duration>,
+    /// Whether the responses returned by commands are sent.
+    pub send_responses: bool,
+    /// The translator used to localize responses.
+    pub(crate) translator: Option<Translator>,
+    /// The response sent when a command panics.
+    pub panic_response: Option<InteractionResponse>,
+    /// The response sent when a command reaches its concurrency limit.
+    pub busy_response: InteractionResponse,
+    /// The policy used to retry the http requests made by the framework.
+    pub retry_policy: RetryPolicy,
+    /// Creates the response sent when a command is used while on cooldown.
+    pub cooldown_response: fn(Duration) -> InteractionResponse,
+    /// The allowed mentions applied to the responses which don't set their own ones.
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// The help command generated by the framework.
+    pub help: Option<HelpCommand>,
+    /// The primary entry point command of the application.
+    pub entry_point: Option<EntryPoint<D, E>>,
+    /// The observers receiving the events emitted by the framework.
+    pub observers: Vec<Observer>,
+    /// The audit layer recording command invocations.
+    pub audit: Option<AuditLog>,
+    /// The waiters waiting for an interaction.
+    pub waiters: Waiters<D>,
+    /// The commands loaded at runtime out of a source, executed when no other command matches.
+    pub dynamic_commands: Option<DynamicCommands>,
+    concurrency: ConcurrencyTracker,
+    global_concurrency: Option<GlobalLimiter>,
+    cooldown_store: Box<dyn CooldownStore>,
+}
+
+impl<D, E> Debug for Framework<D, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Framework")
+            .field("application_id", &self.application_id)
+            .field("commands", &sorted(&self.commands))
+            .field("groups", &sorted(&self.groups))
+            .field("buttons", &self.buttons)
+            .field("before", &self.before.is_some())
+            .field("after", &self.after.is_some())
+            .field("on_error", &self.on_error.is_some())
+            .field("interceptor", &self.interceptor.is_some())
+            .field("unknown_command", &self.unknown_command.is_some())
+            .field("unhandled_component", &self.unhandled_component.is_some())
+            .field("unhandled_interaction", &self.unhandled_interaction.is_some())
+            .field("auto_defer", &self.auto_defer)
+            .field("send_responses", &self.send_responses)
+            .field("retry_policy", &self.retry_policy)
+            .field("help", &self.help)
+            .field("entry_point", &self.entry_point)
+            .field("observers", &self.observers.len())
+            .field("audit", &self.audit.is_some())
+            .field("waiters", &self.waiters.len())
+            .finish()
+    }
 }
 
-impl<D> Framework<D> {
+impl<D, E> Framework<D, E> {
     /// Creates a new [Framework](self::Framework) from the given builder.
-    pub(crate) fn from_builder(builder: FrameworkBuilder<D>) -> Self {
+    pub(crate) fn from_builder(builder: FrameworkBuilder<D, E>) -> Self {
+        let fallback_locale = builder
+            .fallback_locale
+            .unwrap_or_else(|| DEFAULT_FALLBACK_LOCALE.to_string());
+        let translator = builder.localizer.map(|localizer| Translator {
+            localizer,
+            fallback_locale,
+        });
+
         Self {
             http_client: builder.http_client,
             application_id: builder.application_id,
-            data: builder.data,
-            commands: builder.commands,
-            groups: builder.groups,
+            data: Arc::new(builder.data),
+            commands: Routes::new(builder.commands),
+            groups: Routes::new(builder.groups),
+            buttons: builder.buttons,
             before: builder.before,
             after: builder.after,
-            waiters: Mutex::new(Vec::new())
+            on_error: builder.on_error,
+            interceptor: builder.interceptor,
+            unknown_command: builder.unknown_command,
+            unhandled_component: builder.unhandled_component,
+            unhandled_interaction: builder.unhandled_interaction,
+            auto_defer: builder.auto_defer,
+            send_responses: builder.send_responses,
+            translator,
+            panic_response: builder.panic_response,
+            busy_response: builder.busy_response,
+            retry_policy: builder.retry_policy,
+            cooldown_response: builder.cooldown_response,
+            allowed_mentions: builder.allowed_mentions,
+            help: builder.help,
+            entry_point: builder.entry_point,
+            observers: builder.observers,
+            audit: builder.audit,
+            waiters: Waiters::new(builder.waiter_limits),
+            dynamic_commands: builder.dynamic_commands,
+            concurrency: Default::default(),
+            global_concurrency: builder.global_concurrency.map(GlobalLimiter::new),
+            cooldown_store: builder
+                .cooldown_store
+                .unwrap_or_else(|| Box::new(InMemoryCooldownStore::new())),
         }
     }
 
@@ -77,7 +201,10 @@ impl<D> Framework<D> {
         http_client: impl Into<WrappedClient>,
         application_id: Id<ApplicationMarker>,
         data: D,
-    ) -> FrameworkBuilder<D> {
+    ) -> FrameworkBuilder<D, E>
+    where
+        E: From<ParseError>,
+    {
         FrameworkBuilder::new(http_client, application_id, data)
     }
 
@@ -88,341 +215,1285 @@ impl<D> Framework<D> {
 
     /// Gets the [interaction client](InteractionClient) using this framework's
     /// [http client](Client) and [application id](ApplicationMarker)
-    pub fn interaction_client(&self) -> InteractionClient {
+    pub fn interaction_client(&self) -> InteractionClient<'_> {
         self.http_client().interaction(self.application_id)
     }
 
     /// Processes the given interaction, dispatching commands or waking waiters if necessary.
     pub async fn process(&self, interaction: Interaction) {
+        self.dispatch::<BoxedDispatch>(interaction, None).await
+    }
+
+    /// Processes the given interaction as done by [process](Self::process), executing the
+    /// commands using the given [dispatch strategy](StaticDispatch), such as the one generated by
+    /// `#[commands(static_dispatch)]` to avoid boxing the futures of its commands.
+    ///
+    /// Only the command handlers are dispatched statically, hooks are still called through their
+    /// boxed futures.
+    pub async fn process_static<S: StaticDispatch<D, E>>(
+        &self,
+        interaction: Interaction,
+        shard: Option<ShardInfo>,
+    ) {
+        self.dispatch::<S>(interaction, shard).await
+    }
+
+    /// Processes the given interaction received on the given shard, making the
+    /// [shard info](ShardInfo) available to the executed command.
+    pub async fn process_with_shard(&self, interaction: Interaction, shard: ShardInfo) {
+        self.dispatch::<BoxedDispatch>(interaction, Some(shard)).await
+    }
+
+    /// Processes an interaction received through an HTTP endpoint instead of the gateway,
+    /// returning the response which has to be sent as the body of the HTTP response.
+    ///
+    /// [Ping](InteractionType::Ping) interactions are answered with a
+    /// [pong](InteractionResponseType::Pong), while other interactions are dispatched as usual
+    /// and responded using the http client, returning `None`.
+    pub async fn process_http(&self, interaction: Interaction) -> Option<InteractionResponse> {
+        if interaction.kind == InteractionType::Ping {
+            debug!("Received ping interaction");
+            return Some(Self::pong());
+        }
+
+        self.dispatch::<BoxedDispatch>(interaction, None).await;
+        None
+    }
+
+    /// Processes an interaction given as the raw JSON body of an HTTP request, as done by
+    /// [process_http](Self::process_http), returning the serialized response which has to be sent
+    /// as the body of the HTTP response, if any.
+    ///
+    /// The body is deserialized using `simd-json` if the `simd-json` feature is enabled.
+    pub async fn process_raw(
+        &self,
+        body: &[u8],
+    ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error + Send + Sync>> {
+        let interaction = deserialize_interaction(body)?;
+        match self.process_http(interaction).await {
+            Some(response) => Ok(Some(serde_json::to_vec(&response)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Creates the response used to acknowledge a [ping](InteractionType::Ping).
+    fn pong() -> InteractionResponse {
+        InteractionResponse {
+            kind: InteractionResponseType::Pong,
+            data: None,
+        }
+    }
+
+    /// Dispatches the given interaction, inside of an OpenTelemetry compatible span if the
+    /// `opentelemetry` feature is enabled.
+    async fn dispatch<S: StaticDispatch<D, E>>(&self, interaction: Interaction, shard: Option<ShardInfo>) {
+        #[cfg(feature = "opentelemetry")]
+        let span = telemetry::dispatch_span(&interaction);
+        let dispatch = self.dispatch_interaction::<S>(interaction, shard);
+        #[cfg(feature = "opentelemetry")]
+        let dispatch = tracing::Instrument::instrument(dispatch, span);
+
+        dispatch.await
+    }
+
+    async fn dispatch_interaction<S: StaticDispatch<D, E>>(
+        &self,
+        mut interaction: Interaction,
+        shard: Option<ShardInfo>,
+    ) {
+        let sensitive = self.sensitive_arguments(&interaction);
+        self.emit(FrameworkEvent::InteractionReceived {
+            interaction: &interaction,
+            sensitive: &sensitive,
+        });
+
+        // Pongs can only be sent as the body of an HTTP response, see `process_http`
+        if interaction.kind == InteractionType::Ping {
+            debug!("Received ping interaction outside of an HTTP endpoint, ignoring it");
+            return;
+        }
+
+        if let Some(interceptor) = &self.interceptor {
+            match (interceptor.0)(&self.data, &mut interaction).await {
+                Intercept::Continue => (),
+                Intercept::Consume => {
+                    debug!("Interaction {} consumed by the interceptor", interaction.id);
+                    return;
+                }
+                Intercept::Reply(response) => {
+                    self.respond(&interaction, &response).await;
+                    return;
+                }
+            }
+        }
+
+        let interaction = match self.try_wake(interaction) {
+            Some(interaction) => interaction,
+            None => return,
+        };
+
         match interaction.kind {
-            InteractionType::ApplicationCommand => self.try_execute(interaction).await,
+            InteractionType::ApplicationCommand => self.try_execute::<S>(interaction, shard).await,
             InteractionType::ApplicationCommandAutocomplete => self.try_autocomplete(interaction).await,
-            InteractionType::MessageComponent => {
-                let mut lock = self.waiters.lock();
-                if let Some(position) = lock.iter().position(|waker| waker.check(self, &interaction)) {
-                    lock.remove(position).wake(interaction);
+            InteractionType::MessageComponent => self.try_button(interaction, shard).await,
+            _ => self.try_unhandled_interaction(interaction, shard).await,
+        }
+    }
+
+    /// Executes the [unhandled interaction hook](UnhandledInteractionHook), if any, for the given
+    /// interaction, whose type the framework doesn't handle.
+    async fn try_unhandled_interaction(&self, interaction: Interaction, shard: Option<ShardInfo>) {
+        let kind = interaction.kind;
+        debug!("Received unhandled interaction of type {:?}", kind);
+
+        if let Some(hook) = &self.unhandled_interaction {
+            let context = self.fallback_context(interaction, shard);
+            (hook.0)(&context, kind).await;
+        }
+    }
+
+    /// Waits for an interaction which satisfies the given predicate, of any
+    /// [type](InteractionType) except [pings](InteractionType::Ping).
+    ///
+    /// The matching interaction is given to the waiter instead of being processed by the
+    /// framework.
+    pub fn wait_for<F>(&self, fun: F) -> InteractionWaiter
+    where
+        F: Fn(&Interaction) -> bool + Send + 'static
+    {
+        let (waker, waiter) = new_pair(move |_: &D, interaction: &Interaction| fun(interaction));
+        self.waiters.register(waker, None);
+        waiter
+    }
+
+    /// Emits the given event to the observers of the framework.
+    fn emit(&self, event: FrameworkEvent<'_>) {
+        event::emit(&self.observers, event);
+    }
+
+    /// Sends the given response to the given interaction, applying the default
+    /// [allowed mentions](AllowedMentions) if it doesn't set its own ones.
+    ///
+    /// Returns whether discord accepted the response.
+    async fn respond(&self, interaction: &Interaction, response: &InteractionResponse) -> bool {
+        let response = self.with_defaults(response);
+        let result = self
+            .retry_policy
+            .run(|| response::create_response(self.http_client(), interaction, &response))
+            .await;
+
+        self.emit(FrameworkEvent::ResponseSent {
+            interaction_id: interaction.id,
+            kind: response.kind,
+            success: result.is_ok(),
+        });
+
+        result.is_ok()
+    }
+
+    /// Sends the given response to an invocation of the given command, making it
+    /// [ephemeral](Command::ephemeral) if the command requires so.
+    ///
+    /// Returns whether discord accepted the response.
+    async fn respond_command(
+        &self,
+        cmd: &Command<D, E>,
+        interaction: &Interaction,
+        response: &InteractionResponse,
+    ) -> bool {
+        let message = matches!(
+            response.kind,
+            InteractionResponseType::ChannelMessageWithSource
+                | InteractionResponseType::DeferredChannelMessageWithSource
+        );
+
+        if cmd.ephemeral && message {
+            let mut response = response.clone();
+            let data = response.data.get_or_insert_with(Default::default);
+            let flags = data.flags.unwrap_or_else(MessageFlags::empty);
+            data.flags = Some(flags | MessageFlags::EPHEMERAL);
+            self.respond(interaction, &response).await
+        } else {
+            self.respond(interaction, response).await
+        }
+    }
+
+    /// Defers the given interaction, invoking the given command, returning whether discord
+    /// accepted the deferred response.
+    async fn defer(&self, cmd: &Command<D, E>, interaction: &Interaction) -> bool {
+        let kind = if interaction.kind == InteractionType::MessageComponent {
+            InteractionResponseType::DeferredUpdateMessage
+        } else {
+            InteractionResponseType::DeferredChannelMessageWithSource
+        };
+        let response = InteractionResponse { kind, data: None };
+
+        self.respond_command(cmd, interaction, &response).await
+    }
+
+    /// Defers the interaction of the given context if it wasn't responded yet.
+    async fn defer_pending(&self, cmd: &Command<D, E>, context: &SlashContext<'_, D>) {
+        // The lock is only held while the handler is replying, which is already a response, so
+        // there's no need to wait for it
+        if let Ok(mut state) = context.response_state.try_lock() {
+            if *state == ResponseState::Pending && self.defer(cmd, &context.interaction).await {
+                *state = ResponseState::Deferred;
+            }
+        }
+    }
+
+    /// Starts an execution of the given command within the
+    /// [global concurrency limit](crate::concurrency::GlobalConcurrencyLimit), returning `None`
+    /// if it has been rejected.
+    ///
+    /// Discord only waits 3 seconds for the response, so executions waiting for longer than
+    /// [QUEUED_DEFER_AFTER] are deferred, or after the [auto defer](Self::auto_defer) threshold
+    /// if it's shorter.
+    async fn acquire_global<'l>(
+        &self,
+        limiter: &'l GlobalLimiter,
+        cmd: &Command<D, E>,
+        context: &SlashContext<'_, D>,
+    ) -> Option<SemaphorePermit<'l>> {
+        if let Some(permit) = limiter.try_acquire() {
+            return Some(permit);
+        }
+
+        let mut acquire = std::pin::pin!(limiter.enqueue()?.acquire());
+        let threshold = self
+            .auto_defer
+            .map_or(QUEUED_DEFER_AFTER, |threshold| threshold.min(QUEUED_DEFER_AFTER));
+        match tokio::time::timeout(threshold, &mut acquire).await {
+            Ok(permit) => permit,
+            Err(_) => {
+                debug!("Deferring command {} while queued", cmd.name);
+                self.defer_pending(cmd, context).await;
+                acquire.await
+            }
+        }
+    }
+
+    /// Sends the response returned by the given command, editing the original response instead
+    /// if the interaction was already [deferred](FrameworkBuilder::auto_defer) by the framework.
+    ///
+    /// Message flags can't be changed once deferred, so whether the response is ephemeral
+    /// depends only on the [command](Command::ephemeral).
+    async fn send_result(
+        &self,
+        cmd: &Command<D, E>,
+        interaction: &Interaction,
+        response: &InteractionResponse,
+        deferred: bool,
+    ) {
+        if !deferred {
+            self.respond_command(cmd, interaction, response).await;
+            return;
+        }
+
+        match response.kind {
+            InteractionResponseType::ChannelMessageWithSource
+            | InteractionResponseType::UpdateMessage => {
+                let response = self.with_defaults(response);
+                let empty = InteractionResponseData::default();
+                let data = response.data.as_ref().unwrap_or(&empty);
+                let result = self
+                    .retry_policy
+                    .run(|| {
+                        response::update_response(
+                            self.http_client(),
+                            self.application_id,
+                            &interaction.token,
+                            data,
+                        )
+                    })
+                    .await;
+                self.emit(FrameworkEvent::ResponseSent {
+                    interaction_id: interaction.id,
+                    kind: response.kind,
+                    success: result.is_ok(),
+                });
+            }
+            InteractionResponseType::DeferredChannelMessageWithSource
+            | InteractionResponseType::DeferredUpdateMessage => (),
+            kind => debug!("Responses of type {:?} can't be sent once deferred", kind),
+        }
+    }
+
+    /// Sends the response returned by the given command depending on the
+    /// [state](ResponseState) of the interaction: it responds the interaction if it wasn't
+    /// responded yet, edits the deferred response, and is sent as a followup message if the
+    /// command already [replied](SlashContext::reply), unless it carries no message.
+    async fn finish(
+        &self,
+        cmd: &Command<D, E>,
+        context: &SlashContext<'_, D>,
+        response: &InteractionResponse,
+    ) {
+        let mut state = context.response_state.lock().await;
+        match *state {
+            ResponseState::Pending | ResponseState::Deferred => {
+                let deferred = *state == ResponseState::Deferred;
+                self.send_result(cmd, &context.interaction, response, deferred).await;
+                *state = ResponseState::Responded;
+            }
+            ResponseState::Responded => {
+                drop(state);
+                if response.data.is_none()
+                    || response.kind != InteractionResponseType::ChannelMessageWithSource
+                {
+                    return;
+                }
+
+                if let Err(why) = context.reply(response.clone()).await {
+                    debug!("Failed to send the response of {} as a followup: {}", cmd.name, why);
                 }
             }
-            _ => ()
         }
     }
 
+    /// Applies the defaults of the framework to the given response.
+    fn with_defaults<'r>(&self, response: &'r InteractionResponse) -> Cow<'r, InteractionResponse> {
+        match (&self.allowed_mentions, &response.data) {
+            (Some(allowed_mentions), Some(data)) if data.allowed_mentions.is_none() => {
+                let mut response = response.clone();
+                if let Some(data) = &mut response.data {
+                    data.allowed_mentions = Some(allowed_mentions.clone());
+                }
+                Cow::Owned(response)
+            }
+            _ => Cow::Borrowed(response),
+        }
+    }
+
+    /// Records the invocation of the given command into the audit layer, if any.
+    async fn record_audit(
+        &self,
+        cmd: &Command<D, E>,
+        path: String,
+        interaction: &Interaction,
+        outcome: AuditOutcome,
+    ) {
+        if let Some(audit) = &self.audit {
+            audit.record(path, interaction, &cmd.arguments, outcome).await;
+        }
+    }
+
+    /// Wakes the first waiter satisfied by the given interaction, giving the interaction back if
+    /// no waiter was woken.
+    fn try_wake(&self, interaction: Interaction) -> Option<Interaction> {
+        self.waiters.wake(&self.data, interaction)
+    }
+
     /// Tries to execute a command based on the given
     /// [ApplicationCommand](ApplicationCommand).
-    async fn try_execute(&self, mut interaction: Interaction) {
-        if let Some(command) = self.get_command(&mut interaction) {
-            self.execute(command, interaction).await;
+    async fn try_execute<S: StaticDispatch<D, E>>(
+        &self,
+        mut interaction: Interaction,
+        shard: Option<ShardInfo>,
+    ) {
+        if EntryPoint::<D>::is_entry_point(extract!(interaction.data.as_ref().unwrap() => ApplicationCommand).kind) {
+            if let Some(command) = self.entry_point.as_ref().and_then(|entry| entry.command.as_ref()) {
+                self.execute::<S>(command, command.name.to_string(), interaction, shard).await;
+            }
+            return;
+        }
+
+        if let Some(help) = &self.help {
+            if extract!(interaction.data.as_ref().unwrap() => ApplicationCommand).name == help.name {
+                let response = help.response(self, &interaction);
+                self.respond(&interaction, &response).await;
+                return;
+            }
+        }
+
+        match self.get_command(&mut interaction) {
+            Some((path, command)) => self.execute::<S>(command, path, interaction, shard).await,
+            None => self.try_dynamic_command(interaction, shard).await,
         }
     }
 
+    /// Executes the [dynamic command](DynamicCommands) referred by the given interaction as any
+    /// other command, falling back to the [unknown command hook](UnknownCommandHook) if there is
+    /// none.
+    async fn try_dynamic_command(&self, interaction: Interaction, shard: Option<ShardInfo>) {
+        let data = extract!(interaction.data.as_ref().unwrap() => ApplicationCommand);
+        let command = self
+            .dynamic_commands
+            .as_ref()
+            .and_then(|dynamic| dynamic.get(&data.name));
+
+        match command {
+            Some(command) => {
+                debug!("Executing dynamic command {}", command.name);
+                let command = command.into_command::<D, E>();
+                let path = command.name.to_string();
+                self.execute::<BoxedDispatch>(&command, path, interaction, shard).await;
+            }
+            None => self.try_unknown_command(interaction, shard).await,
+        }
+    }
+
+    /// Executes the [unknown command hook](UnknownCommandHook), if any, for the given
+    /// interaction, which refers to a command the framework doesn't know.
+    async fn try_unknown_command(&self, interaction: Interaction, shard: Option<ShardInfo>) {
+        let path = invoked_path(extract!(interaction.data.as_ref().unwrap() => ApplicationCommand));
+        debug!("Received unknown command {}", path);
+
+        if let Some(hook) = &self.unknown_command {
+            let context = self.fallback_context(interaction, shard);
+            (hook.0)(&context, &path).await;
+        }
+    }
+
+    /// Creates the context given to the hooks handling the interactions no command or button
+    /// handles.
+    fn fallback_context(
+        &self,
+        interaction: Interaction,
+        shard: Option<ShardInfo>,
+    ) -> SlashContext<'_, D> {
+        SlashContext::new(
+            &self.http_client,
+            self.application_id,
+            &self.data,
+            &self.waiters,
+            interaction,
+            shard,
+            None,
+            &[],
+            &self.observers,
+            self.allowed_mentions.as_ref(),
+            self.translator.as_ref(),
+            false,
+        )
+    }
+
+    /// Tries to execute the [button](Button) whose template matches the custom id of the given
+    /// message component interaction.
+    async fn try_button(&self, interaction: Interaction, shard: Option<ShardInfo>) {
+        let custom_id = &extract!(interaction.data.as_ref().unwrap() => MessageComponent).custom_id;
+        let button = match self.buttons.iter().find(|button| button.template.matches(custom_id)) {
+            Some(button) => button,
+            None => {
+                debug!("Received unhandled component {}", custom_id);
+                if let Some(hook) = &self.unhandled_component {
+                    let custom_id = custom_id.clone();
+                    let context = self.fallback_context(interaction, shard);
+                    (hook.0)(&context, &custom_id).await;
+                }
+                return;
+            }
+        };
+
+        if let Some(required) = button.command.required_permissions {
+            let permissions = interaction
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions)
+                .unwrap_or_else(Permissions::empty);
+
+            if !permissions.contains(required) {
+                debug!("Missing permissions to use button {}", button.template);
+                let path = button.template.to_string();
+                self.record_audit(&button.command, path, &interaction, AuditOutcome::Rejected).await;
+                return;
+            }
+        }
+
+        self.execute::<BoxedDispatch>(&button.command, button.template.to_string(), interaction, shard)
+            .await;
+    }
+
     async fn try_autocomplete(&self, mut interaction: Interaction) {
-        if let Some((argument, value)) = self.get_autocomplete_argument(extract!(interaction.data.as_ref().unwrap() => ApplicationCommand)) {
+        if let Some((path, argument, value, options)) = self.get_autocomplete_argument(extract!(interaction.data.as_mut().unwrap() => ApplicationCommand)) {
             if let Some(fun) = &argument.autocomplete {
                 let context = AutocompleteContext::new(
                     &self.http_client,
-                    &self.data,
+                    self.data.as_ref(),
                     value,
+                    options,
                     &mut interaction,
                 );
-                let data = (fun.0)(context).await;
-
-                let _ = self
-                    .interaction_client()
-                    .create_response(
-                        interaction.id,
-                        &interaction.token,
-                        &InteractionResponse {
-                            kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
-                            data,
-                        },
-                    )
-                    .exec()
-                    .await;
+                let start = Instant::now();
+                let (data, error) = match CatchUnwind((fun.0)(context)).await {
+                    Ok(data) => (data, None),
+                    Err(why) => {
+                        debug!("Autocomplete of {} in {} failed: {}", argument.name, path, why);
+                        let data = InteractionResponseData {
+                            choices: Some(Vec::new()),
+                            ..Default::default()
+                        };
+                        (Some(data), Some(why))
+                    }
+                };
+                let elapsed = start.elapsed();
+                let response = InteractionResponse {
+                    kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+                    data,
+                };
+
+                let responded = self.respond(&interaction, &response).await;
+                self.emit(FrameworkEvent::AutocompleteCompleted {
+                    command: &path,
+                    argument: &argument.name,
+                    elapsed,
+                    error: error.as_ref(),
+                    responded,
+                });
             }
         }
     }
 
+    /// Gets the argument being autocompleted, moving the user input and the options provided to
+    /// the command out of the given data.
     fn get_autocomplete_argument(
         &self,
-        data: &CommandData,
-    ) -> Option<(&CommandArgument<D>, Focused)> {
-        if !data.options.is_empty() {
-            let outer = data.options.get(0)?;
-            match &outer.value {
-                CommandOptionValue::SubCommandGroup(sc_group) => {
-                    if !sc_group.is_empty() {
-                        let map = self
-                            .groups
-                            .get(data.name.as_str())?
-                            .kind
-                            .as_group()?;
-                        let group = map.get(outer.name.as_str())?;
-                        let next = sc_group.get(0)?;
-                        if let CommandOptionValue::SubCommand(options) = &next.value {
-                            let focused = self.get_focus(options)?;
-                            let command = group.subcommands.get(next.name.as_str())?;
-                            let position = command
-                                .arguments
-                                .iter()
-                                .position(|arg| arg.name == focused.name)?;
-                            return Some((command.arguments.get(position)?, focused!(&focused.value)));
-                        }
-                    }
-                }
-                CommandOptionValue::SubCommand(sc) => {
-                    if !sc.is_empty() {
-                        let group = self.groups.get(data.name.as_str())?
-                            .kind
-                            .as_simple()?;
-                        let focused = self.get_focus(sc)?;
-                        let command = group.get(outer.name.as_str())?;
-                        let position = command
-                            .arguments
-                            .iter()
-                            .position(|arg| arg.name == focused.name)?;
-                        return Some((command.arguments.get(position)?, focused!(&focused.value)));
-                    }
-                }
-                _ => {
-                    let focused = self.get_focus(&data.options)?;
-                    let command = self.commands.get(data.name.as_str())?;
-                    let position = command
-                        .arguments
-                        .iter()
-                        .position(|arg| arg.name == focused.name)?;
-                    return Some((command.arguments.get(position)?, focused!(&focused.value)));
-                }
-            }
-        }
+        data: &mut CommandData,
+    ) -> Option<(String, &CommandArgument<D>, Focused, Vec<CommandDataOption>)> {
+        let (path, command, options) = self.resolve(data)?;
+        let focused = self.get_focus(options)?;
+        let argument = command
+            .arguments
+            .iter()
+            .find(|arg| arg.name == focused.name)?;
+        let path = path.to_string();
 
-        None
+        let mut options = leaf_options(&mut data.options);
+        let focused = options.iter_mut().find_map(|item| match &mut item.value {
+            CommandOptionValue::Focused(input, kind) => Some(Focused {
+                input: std::mem::take(input),
+                kind: *kind,
+            }),
+            _ => None,
+        })?;
+
+        Some((path, argument, focused, options))
     }
 
-    fn get_focus<'a>(&self, data: &'a Vec<CommandDataOption>) -> Option<&'a CommandDataOption> {
-        for item in data {
-            if let CommandOptionValue::Focused(..) = &item.value {
-                return Some(item);
-            }
-        }
-        None
+    fn get_focus<'a>(&self, data: &'a [CommandDataOption]) -> Option<&'a CommandDataOption> {
+        data.iter()
+            .find(|item| matches!(item.value, CommandOptionValue::Focused(..)))
     }
 
-    /// Gets the command matching the given
-    /// [ApplicationCommand](ApplicationCommand),
-    /// returning `None` if no command matches the given interaction.
-    fn get_command(&self, interaction: &mut Interaction) -> Option<&Command<D>> {
-        let data = interaction.data.as_mut()?;
-        let interaction_data = extract!(data => ApplicationCommand);
-        if let Some(next) = self.get_next(&mut interaction_data.options) {
-            let group = self.groups.get(&*interaction_data.name)?;
-            match next.value.kind() {
-                CommandOptionType::SubCommand => {
-                    let subcommands = group.kind.as_simple()?;
-                    let options = match next.value {
-                        CommandOptionValue::SubCommand(s) => s,
-                        _ => unreachable!(),
+    /// Resolves the [command](Command) the given [command data](CommandData) refers to, walking
+    /// through subcommand groups and subcommands.
+    ///
+    /// Returns the [path](CommandPath) used to invoke the command, the command itself and the
+    /// options provided to it, or `None` if no command matches the given data.
+    pub fn resolve<'a, 'b>(
+        &'a self,
+        data: &'b CommandData,
+    ) -> Option<(CommandPath<'b>, &'a Command<D, E>, &'b [CommandDataOption])> {
+        let mut path = CommandPath::new(&data.name);
+
+        match data.options.first() {
+            Some(outer) => match &outer.value {
+                CommandOptionValue::SubCommandGroup(options) => {
+                    let group = self
+                        .groups
+                        .get(data.name.as_str())?
+                        .kind
+                        .as_group()?
+                        .get(outer.name.as_str())?;
+                    let next = options.first()?;
+                    let options = match &next.value {
+                        CommandOptionValue::SubCommand(options) => options,
+                        _ => return None,
                     };
-                    interaction_data.options = options;
-                    subcommands.get(&*next.name)
+                    let command = group.subcommands.get(next.name.as_str())?;
+                    path.group = Some(&outer.name);
+                    path.subcommand = Some(&next.name);
+
+                    Some((path, command, options))
                 }
-                CommandOptionType::SubCommandGroup => {
-                    let mut options = match next.value {
-                        CommandOptionValue::SubCommandGroup(s) => s,
-                        _ => unreachable!(),
-                    };
-                    let subcommand = self.get_next(&mut options)?;
-                    let subgroups = group.kind.as_group()?;
-                    let group = subgroups.get(&*next.name)?;
-                    let options = match subcommand.value {
-                        CommandOptionValue::SubCommand(s) => s,
-                        _ => unreachable!(),
-                    };
-                    interaction_data.options = options;
-                    group.subcommands.get(&*subcommand.name)
+                CommandOptionValue::SubCommand(options) => {
+                    let command = self
+                        .groups
+                        .get(data.name.as_str())?
+                        .kind
+                        .as_simple()?
+                        .get(outer.name.as_str())?;
+                    path.subcommand = Some(&outer.name);
+
+                    Some((path, command, options))
                 }
-                _ => None,
-            }
-        } else {
-            self.commands.get(&*interaction_data.name)
+                _ => Some((path, self.commands.get(data.name.as_str())?, &data.options)),
+            },
+            None => Some((path, self.commands.get(data.name.as_str())?, &data.options)),
         }
     }
 
-    /// Gets the next [option](CommandDataOption)
-    /// only if it corresponds to a subcommand or a subcommand group.
-    fn get_next(&self, interaction: &mut Vec<CommandDataOption>) -> Option<CommandDataOption> {
-        if !interaction.is_empty()
-            && (interaction[0].value.kind() == CommandOptionType::SubCommand
-                || interaction[0].value.kind() == CommandOptionType::SubCommandGroup)
-        {
-            Some(interaction.remove(0))
-        } else {
-            None
+    /// Gets the checks of the groups the command with the given path is inside of, from the
+    /// outermost one.
+    fn group_checks(&self, path: &str) -> Vec<&BeforeHook<D>> {
+        let mut names = path.split(' ');
+        let parent = match names.next().and_then(|name| self.groups.get(name)) {
+            Some(parent) => parent,
+            None => return Vec::new(),
+        };
+
+        let mut checks = parent.checks.iter().collect::<Vec<_>>();
+        if let (Some(group), Some(_)) = (names.next(), names.next()) {
+            if let Some(group) = parent.kind.as_group().and_then(|map| map.get(group)) {
+                checks.extend(&group.checks);
+            }
         }
+
+        checks
+    }
+
+    /// Gets the command matching the given
+    /// [ApplicationCommand](ApplicationCommand),
+    /// returning `None` if no command matches the given interaction.
+    ///
+    /// The options of the interaction are replaced by the ones provided to the command, and the
+    /// path used to invoke the command is returned along with it.
+    fn get_command(&self, interaction: &mut Interaction) -> Option<(String, &Command<D, E>)> {
+        let data = extract!(interaction.data.as_mut()? => ApplicationCommand);
+        let (path, command, _) = self.resolve(data)?;
+        self.emit(FrameworkEvent::CommandResolved { path });
+        let path = path.to_string();
+        let options = leaf_options(&mut data.options);
+        data.options = options;
+        Some((path, command))
     }
 
     /// Executes the given [command](crate::command::Command) and the hooks.
-    async fn execute(&self, cmd: &Command<D>, interaction: Interaction) {
+    async fn execute<S: StaticDispatch<D, E>>(
+        &self,
+        cmd: &Command<D, E>,
+        path: String,
+        interaction: Interaction,
+        shard: Option<ShardInfo>,
+    ) {
         let context = SlashContext::new(
             &self.http_client,
             self.application_id,
             &self.data,
+            &self.waiters,
             interaction,
+            shard,
+            Some(&cmd.meta),
+            &cmd.arguments,
+            &self.observers,
+            self.allowed_mentions.as_ref(),
+            self.translator.as_ref(),
+            cmd.ephemeral,
         );
 
-        let execute = if let Some(before) = &self.before {
-            (before.0)(&context, cmd.name).await
+        if let Some(sku_id) = cmd.premium_sku {
+            if !has_entitlement(&context.interaction, sku_id) {
+                debug!("Command {} requires an entitlement to the sku {}", path, sku_id);
+                let response = InteractionResponse {
+                    kind: InteractionResponseType::ChannelMessageWithSource,
+                    data: Some(InteractionResponseData {
+                        components: Some(vec![compat::action_row(vec![Component::Button(
+                            compat::premium_button(sku_id),
+                        )])]),
+                        ..Default::default()
+                    }),
+                };
+                self.respond_command(cmd, &context.interaction, &response).await;
+                self.record_audit(cmd, path, &context.interaction, AuditOutcome::Rejected).await;
+                return;
+            }
+        }
+
+        let mut verdict = if let Some(before) = &self.before {
+            (before.0)(&context, &cmd.name).await
         } else {
-            true
+            HookVerdict::Continue
         };
 
-        if execute {
-            let result = (cmd.fun)(&context).await;
+        for check in self.group_checks(&path).into_iter().chain(&cmd.checks) {
+            if !verdict.is_continue() {
+                break;
+            }
+            verdict = (check.0)(&context, &cmd.name).await;
+        }
+
+        self.emit(FrameworkEvent::ChecksEvaluated {
+            command: &cmd.name,
+            passed: verdict.is_continue(),
+        });
 
+        if !verdict.is_continue() {
+            debug!("Command {} skipped by its hooks: {:?}", path, verdict);
+            if let HookVerdict::Reply(response) = &verdict {
+                self.respond_command(cmd, &context.interaction, response).await;
+            }
+            self.record_audit(cmd, path, &context.interaction, AuditOutcome::Rejected).await;
+            if let Some(on_error) = &self.on_error {
+                (on_error.0)(&context, &cmd.name, FrameworkError::Skipped(verdict.clone())).await;
+            }
             if let Some(after) = &self.after {
-                (after.0)(&context, cmd.name, result).await;
+                (after.0)(&context, &cmd.name, CommandOutcome::Skipped(verdict)).await;
+            }
+            return;
+        }
+
+        let _guard = match cmd.concurrency {
+            Some(limit) => match self.concurrency.acquire(&path, limit, &context.interaction) {
+                Some(guard) => Some(guard),
+                None => {
+                    debug!("Command {} reached its concurrency limit", path);
+                    self.respond_command(cmd, &context.interaction, &self.busy_response).await;
+                    self.record_audit(cmd, path, &context.interaction, AuditOutcome::Rejected).await;
+                    return;
+                }
+            },
+            None => None,
+        };
+
+        let _permit = match &self.global_concurrency {
+            Some(limiter) => match self.acquire_global(limiter, cmd, &context).await {
+                Some(permit) => Some(permit),
+                None => {
+                    debug!("Command {} rejected by the global concurrency limit", path);
+                    // The interaction may have been deferred while queued
+                    self.finish(cmd, &context, &self.busy_response).await;
+                    self.record_audit(cmd, path, &context.interaction, AuditOutcome::Rejected).await;
+                    return;
+                }
+            },
+            None => None,
+        };
+
+        // Started only once the command is sure to run, so busy invocations don't start it
+        if let Some(cooldown) = cmd.cooldown {
+            let key = cooldown.key(&path, &context.interaction);
+            if let Some(remaining) = self.cooldown_store.try_start(&key, cooldown.duration).await {
+                debug!("Command {} is on cooldown for {:?}", path, remaining);
+                let response = (self.cooldown_response)(remaining);
+                self.respond_command(cmd, &context.interaction, &response).await;
+                self.record_audit(cmd, path, &context.interaction, AuditOutcome::Rejected).await;
+                return;
+            }
+        }
+
+        let start = Instant::now();
+        // The handler borrows the path, so it's dropped before the path is moved
+        let outcome = {
+            let handler = S::execute(cmd, &path, &context);
+            #[cfg(feature = "opentelemetry")]
+            let handler = tracing::Instrument::instrument(handler, telemetry::handler_span(&path));
+            let mut handler = CatchUnwind(std::pin::pin!(handler));
+            match self.auto_defer {
+                Some(threshold) => match tokio::time::timeout(threshold, &mut handler).await {
+                    Ok(outcome) => outcome,
+                    Err(_) => {
+                        debug!("Deferring command {} after {:?}", path, threshold);
+                        self.defer_pending(cmd, &context).await;
+                        handler.await
+                    }
+                },
+                None => handler.await,
+            }
+        };
+
+        let result = match outcome {
+            Ok(result) => result,
+            Err(error) => {
+                self.emit(FrameworkEvent::HandlerCompleted {
+                    command: &cmd.name,
+                    success: false,
+                    elapsed: start.elapsed(),
+                });
+                #[cfg(feature = "opentelemetry")]
+                telemetry::record_handler(&path, start.elapsed(), false);
+
+                if let Some(response) = &self.panic_response {
+                    self.finish(cmd, &context, response).await;
+                }
+
+                self.record_audit(cmd, path, &context.interaction, AuditOutcome::Failed).await;
+
+                if let Some(on_error) = &self.on_error {
+                    (on_error.0)(&context, &cmd.name, error).await;
+                }
+                return;
             }
+        };
+
+        self.emit(FrameworkEvent::HandlerCompleted {
+            command: &cmd.name,
+            success: result.is_ok(),
+            elapsed: start.elapsed(),
+        });
+        #[cfg(feature = "opentelemetry")]
+        telemetry::record_handler(&path, start.elapsed(), result.is_ok());
+
+        if let (true, Ok(response)) = (self.send_responses, &result) {
+            self.finish(cmd, &context, response).await;
+        }
+
+        let outcome = if result.is_ok() {
+            AuditOutcome::Success
+        } else {
+            AuditOutcome::Failed
+        };
+        self.record_audit(cmd, path, &context.interaction, outcome).await;
+
+        if let Some(after) = &self.after {
+            (after.0)(&context, &cmd.name, CommandOutcome::Executed(result)).await;
         }
     }
 
     /// Registers the commands provided to the framework in the specified guild.
+    ///
+    /// Commands already registered as they are provided to the framework are left unchanged.
     pub async fn register_guild_commands(
         &self,
         guild_id: Id<GuildMarker>,
-    ) -> Result<Vec<TwilightCommand>, Box<dyn std::error::Error + Send + Sync>> {
-        let mut commands = Vec::new();
+    ) -> Result<RegistrationReport, Box<dyn std::error::Error + Send + Sync>> {
+        self.register_guild_payloads(guild_id, self.command_payloads()).await
+    }
 
-        for cmd in self.commands.values() {
-            let mut options = Vec::new();
+    /// Registers the commands matching the given filter in the specified guild.
+    ///
+    /// Only top level commands are registered, use
+    /// [register_guild_groups_filtered](Self::register_guild_groups_filtered) to register groups.
+    pub async fn register_guild_commands_filtered<F>(
+        &self,
+        guild_id: Id<GuildMarker>,
+        filter: F,
+    ) -> Result<RegistrationReport, Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: Fn(&Command<D, E>) -> bool,
+    {
+        let payloads = self
+            .commands
+            .values()
+            .filter(|command| filter(command))
+            .map(CommandPayload::from_command)
+            .collect::<Vec<_>>();
 
-            for i in &cmd.arguments {
-                options.push(i.as_option());
-            }
-            let interaction_client = self.interaction_client();
-            let mut command = interaction_client
-                .create_guild_command(guild_id)
-                .chat_input(cmd.name, cmd.description)?
-                .command_options(&options)?;
-
-            if let Some(permissions) = &cmd.required_permissions {
-                command = command.default_member_permissions(*permissions);
-            }
+        self.register_guild_payloads(guild_id, payloads).await
+    }
 
-            commands.push(command.exec().await?.model().await?);
-        }
+    /// Registers the groups matching the given filter in the specified guild.
+    ///
+    /// Only groups are registered, use
+    /// [register_guild_commands_filtered](Self::register_guild_commands_filtered) to register
+    /// top level commands.
+    pub async fn register_guild_groups_filtered<F>(
+        &self,
+        guild_id: Id<GuildMarker>,
+        filter: F,
+    ) -> Result<RegistrationReport, Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: Fn(&GroupParent<D, E>) -> bool,
+    {
+        let payloads = self
+            .groups
+            .values()
+            .filter(|group| filter(group))
+            .map(CommandPayload::from_group)
+            .collect::<Vec<_>>();
 
-        for group in self.groups.values() {
-            let options = self.create_group(group);
-            let interaction_client = self.interaction_client();
-            let mut command = interaction_client
-                .create_guild_command(guild_id)
-                .chat_input(group.name, group.description)?
-                .command_options(&options)?;
+        self.register_guild_payloads(guild_id, payloads).await
+    }
 
-            if let Some(permissions) = &group.required_permissions {
-                command = command.default_member_permissions(*permissions);
-            }
+    /// Registers the commands provided to the framework in the specified guild, deleting every
+    /// remote guild command not provided to the framework, such as renamed or removed commands.
+    pub async fn sync_guild(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<RegistrationReport, Box<dyn std::error::Error + Send + Sync>> {
+        let interaction_client = self.interaction_client();
+        let remote = self.remote_commands(Some(guild_id)).await?;
+        let mut report = RegistrationReport::default();
+        self.register_payloads(Some(guild_id), self.command_payloads(), &remote, &mut report)
+            .await;
 
-            commands.push(command.exec().await?.model().await?);
+        for stale in Self::stale_commands(&report, remote) {
+            debug!("Deleting stale guild command {}", stale.name);
+            if let Some(id) = stale.id {
+                let result = self
+                    .retry_policy
+                    .run(|| interaction_client.delete_guild_command(guild_id, id).into_future())
+                    .await;
+                Self::push_deletion(&mut report, stale, result);
+            }
         }
 
-        Ok(commands)
+        Ok(report)
+    }
+
+    /// Registers the given payloads in the specified guild.
+    async fn register_guild_payloads(
+        &self,
+        guild_id: Id<GuildMarker>,
+        payloads: Vec<CommandPayload>,
+    ) -> Result<RegistrationReport, Box<dyn std::error::Error + Send + Sync>> {
+        let remote = self.remote_commands(Some(guild_id)).await?;
+        let mut report = RegistrationReport::default();
+        self.register_payloads(Some(guild_id), payloads, &remote, &mut report)
+            .await;
+
+        Ok(report)
     }
 
     /// Registers the commands provided to the framework globally.
+    ///
+    /// Commands already registered as they are provided to the framework are left unchanged.
     pub async fn register_global_commands(
         &self,
-    ) -> Result<Vec<TwilightCommand>, Box<dyn std::error::Error + Send + Sync>> {
-        let mut commands = Vec::new();
+    ) -> Result<RegistrationReport, Box<dyn std::error::Error + Send + Sync>> {
+        let remote = self.remote_commands(None).await?;
+        let mut report = RegistrationReport::default();
+        self.register_globals(&remote, &mut report).await;
 
-        for cmd in self.commands.values() {
-            let mut options = Vec::new();
+        Ok(report)
+    }
 
-            for i in &cmd.arguments {
-                options.push(i.as_option());
-            }
-            let interaction_client = self.interaction_client();
-            let mut command = interaction_client
-                .create_global_command()
-                .chat_input(cmd.name, cmd.description)?
-                .command_options(&options)?;
-
-            if let Some(permissions) = &cmd.required_permissions {
-                command = command.default_member_permissions(*permissions);
+    /// Registers the commands provided to the framework globally, deleting every remote global
+    /// command not provided to the framework, such as renamed or removed commands.
+    pub async fn sync_global(
+        &self,
+    ) -> Result<RegistrationReport, Box<dyn std::error::Error + Send + Sync>> {
+        let interaction_client = self.interaction_client();
+        let remote = self.remote_commands(None).await?;
+        let mut report = RegistrationReport::default();
+        self.register_globals(&remote, &mut report).await;
+
+        for stale in Self::stale_commands(&report, remote) {
+            debug!("Deleting stale global command {}", stale.name);
+            if let Some(id) = stale.id {
+                let result = self
+                    .retry_policy
+                    .run(|| interaction_client.delete_global_command(id).into_future())
+                    .await;
+                Self::push_deletion(&mut report, stale, result);
             }
+        }
+
+        Ok(report)
+    }
 
-            commands.push(command.exec().await?.model().await?);
+    /// Registers the slash commands and the entry point of the framework globally, given the
+    /// commands already registered.
+    async fn register_globals(&self, remote: &[TwilightCommand], report: &mut RegistrationReport) {
+        self.register_payloads(None, self.command_payloads(), remote, report)
+            .await;
+
+        if let Some(entry_point) = &self.entry_point {
+            let status = if remote.iter().any(|command| {
+                EntryPoint::<D>::is_entry_point(command.kind) && command.name == entry_point.name
+            }) {
+                RegistrationStatus::Updated
+            } else {
+                RegistrationStatus::Created
+            };
+            let result = entry_point
+                .register(self.http_client(), self.application_id, &self.retry_policy)
+                .await;
+
+            report.push_result(
+                entry_point.name.to_string(),
+                CommandType::from(ENTRY_POINT_TYPE),
+                status,
+                result,
+            );
         }
+    }
 
-        for group in self.groups.values() {
-            let options = self.create_group(group);
-            let interaction_client = self.interaction_client();
-            let mut command = interaction_client
-                .create_global_command()
-                .chat_input(group.name, group.description)?
-                .command_options(&options)?;
+    /// Loads the [dynamic commands](DynamicCommands) out of their source again, registering only
+    /// the ones which were added or changed and deleting the ones which were removed, in the given
+    /// guild or globally if no guild is given.
+    ///
+    /// Dynamic commands named like another command of the framework are ignored.
+    ///
+    /// Only the changes which were synchronized with discord are applied to the dynamic
+    /// commands, so the ones whose request failed are tried again by the next reload.
+    pub async fn reload_dynamic_commands(
+        &self,
+        guild_id: Option<Id<GuildMarker>>,
+    ) -> Result<RegistrationReport, Box<dyn std::error::Error + Send + Sync>> {
+        let mut report = RegistrationReport::default();
+        let dynamic = match &self.dynamic_commands {
+            Some(dynamic) => dynamic,
+            None => return Ok(report),
+        };
+        let changes = dynamic.changes().await?;
 
-            if let Some(permissions) = &group.required_permissions {
-                command = command.default_member_permissions(*permissions);
-            }
+        if changes.is_empty() {
+            return Ok(report);
+        }
+
+        debug!(
+            "Dynamic commands changed: {} added, {} updated, {} removed",
+            changes.added.len(),
+            changes.updated.len(),
+            changes.removed.len()
+        );
+
+        let DynamicChanges { mut added, mut updated, mut removed } = changes;
+        let payloads = added
+            .iter()
+            .chain(updated.iter())
+            .filter(|command| !self.is_static_command(&command.name))
+            .map(|command| command.payload())
+            .collect();
 
-            commands.push(command.exec().await?.model().await?);
+        let remote = self.remote_commands(guild_id).await?;
+        self.register_payloads(guild_id, payloads, &remote, &mut report)
+            .await;
+
+        let interaction_client = self.interaction_client();
+        for command in removed.iter().filter(|command| !self.is_static_command(&command.name)) {
+            let stale = remote.iter().find(|remote| {
+                remote.kind == CommandType::ChatInput && remote.name == command.name
+            });
+            let (stale, id) = match stale.and_then(|stale| Some((stale, stale.id?))) {
+                Some(stale) => stale,
+                None => continue,
+            };
+
+            let result = self
+                .retry_policy
+                .run(|| match guild_id {
+                    Some(guild_id) => interaction_client.delete_guild_command(guild_id, id).into_future(),
+                    None => interaction_client.delete_global_command(id).into_future(),
+                })
+                .await;
+            Self::push_deletion(&mut report, stale.clone(), result);
         }
 
-        Ok(commands)
+        let synced = |command: &TemplateCommand| {
+            !report.entries.iter().any(|entry| {
+                entry.kind == CommandType::ChatInput
+                    && entry.name == command.name
+                    && entry.status.is_failed()
+            })
+        };
+        added.retain(synced);
+        updated.retain(synced);
+        removed.retain(synced);
+        dynamic.apply(&DynamicChanges { added, updated, removed });
+
+        Ok(report)
     }
 
-    fn arg_options(&self, arguments: &Vec<CommandArgument<D>>) -> Vec<CommandOption> {
-        let mut options = Vec::with_capacity(arguments.len());
+    /// Reloads the [dynamic commands](DynamicCommands) every time the given interval elapses,
+    /// as done by [reload_dynamic_commands](Self::reload_dynamic_commands), starting right away.
+    ///
+    /// This never returns, so it should be spawned in its own task.
+    pub async fn watch_dynamic_commands(
+        &self,
+        guild_id: Option<Id<GuildMarker>>,
+        interval: Duration,
+    ) {
+        loop {
+            match self.reload_dynamic_commands(guild_id).await {
+                Ok(report) if !report.entries.is_empty() => {
+                    debug!("Reloaded dynamic commands: {}", report)
+                }
+                Ok(_) => (),
+                Err(error) => warn!("Failed to reload dynamic commands: {}", error),
+            }
 
-        for arg in arguments {
-            options.push(arg.as_option());
+            tokio::time::sleep(interval).await;
         }
+    }
 
-        options
+    /// Gets the names of the sensitive arguments of the command invoked by the given interaction,
+    /// so observers can redact them, skipping the lookup if there are no observers.
+    fn sensitive_arguments(&self, interaction: &Interaction) -> Vec<&str> {
+        if self.observers.is_empty() {
+            return Vec::new();
+        }
+        let data = match &interaction.data {
+            Some(InteractionData::ApplicationCommand(data)) => data,
+            _ => return Vec::new(),
+        };
+
+        match self.resolve(data) {
+            Some((_, command, _)) => command
+                .arguments
+                .iter()
+                .filter(|argument| argument.sensitive)
+                .map(|argument| argument.name.as_ref())
+                .collect(),
+            None => Vec::new(),
+        }
     }
 
-    fn create_group(&self, parent: &GroupParent<D>) -> Vec<CommandOption> {
-        debug!("Registering group {}", parent.name);
+    /// Whether the given name belongs to a command, group or help command of the framework,
+    /// which take precedence over the dynamic commands.
+    pub(crate) fn is_static_command(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+            || self.groups.contains_key(name)
+            || self.help.as_ref().is_some_and(|help| help.name == name)
+    }
 
-        if let ParentType::Group(map) = &parent.kind {
-            let mut subgroups = Vec::new();
-            for group in map.values() {
-                debug!("Registering subgroup {} of {}", group.name, parent.name);
+    /// Gets the commands registered in the given guild, or globally if no guild is given.
+    async fn remote_commands(
+        &self,
+        guild_id: Option<Id<GuildMarker>>,
+    ) -> Result<Vec<TwilightCommand>, Box<dyn std::error::Error + Send + Sync>> {
+        let interaction_client = self.interaction_client();
+        let response = match guild_id {
+            Some(guild_id) => {
+                self.retry_policy
+                    .run(|| interaction_client.guild_commands(guild_id).into_future())
+                    .await?
+            }
+            None => {
+                self.retry_policy
+                    .run(|| interaction_client.global_commands().into_future())
+                    .await?
+            }
+        };
+
+        Ok(response.models().await?)
+    }
+
+    /// Registers the given payloads in the given guild, or globally if no guild is given,
+    /// skipping the ones already registered as they are.
+    async fn register_payloads(
+        &self,
+        guild_id: Option<Id<GuildMarker>>,
+        payloads: Vec<CommandPayload>,
+        remote: &[TwilightCommand],
+        report: &mut RegistrationReport,
+    ) {
+        let interaction_client = self.interaction_client();
 
-                let mut subcommands = Vec::new();
-                for sub in group.subcommands.values() {
-                    subcommands.push(self.create_subcommand(sub))
+        for payload in payloads {
+            let existing = remote.iter().find(|command| {
+                command.kind == CommandType::ChatInput && command.name == payload.name
+            });
+
+            let status = match existing {
+                Some(existing) if payload.matches(existing) => {
+                    debug!("Command {} is up to date", payload.name);
+                    report.push(
+                        payload.name,
+                        CommandType::ChatInput,
+                        RegistrationStatus::Unchanged,
+                        Some(existing.clone()),
+                    );
+                    continue;
                 }
+                Some(_) => RegistrationStatus::Updated,
+                None => RegistrationStatus::Created,
+            };
+
+            let response = self
+                .retry_policy
+                .run(|| match guild_id {
+                    Some(guild_id) => {
+                        let mut command = interaction_client
+                            .create_guild_command(guild_id)
+                            .chat_input(&payload.name, &payload.description)
+                            .command_options(&payload.options);
+
+                        if let Some(permissions) = payload.default_member_permissions {
+                            command = command.default_member_permissions(permissions);
+                        }
+
+                        command.into_future()
+                    }
+                    None => {
+                        let mut command = interaction_client
+                            .create_global_command()
+                            .chat_input(&payload.name, &payload.description)
+                            .command_options(&payload.options);
+
+                        if let Some(permissions) = payload.default_member_permissions {
+                            command = command.default_member_permissions(permissions);
+                        }
+
+                        command.into_future()
+                    }
+                })
+                .await;
+
+            let result = match response {
+                Ok(response) => response.model().await.map_err(Into::into),
+                Err(error) => Err(error.into()),
+            };
 
-                subgroups.push(CommandOption::SubCommandGroup(OptionsCommandOptionData {
-                    name: group.name.to_string(),
-                    description: group.description.to_string(),
-                    options: subcommands,
-                    ..Default::default()
-                }));
+            if let Err(error) = &result {
+                warn!("Failed to register command {}: {}", payload.name, error);
             }
-            subgroups
-        } else if let ParentType::Simple(map) = &parent.kind {
-            let mut subcommands = Vec::new();
-            for sub in map.values() {
-                subcommands.push(self.create_subcommand(sub));
+            report.push_result(payload.name, CommandType::ChatInput, status, result);
+        }
+    }
+
+    /// Gets the remote commands which are not part of the registered ones.
+    fn stale_commands(
+        report: &RegistrationReport,
+        remote: Vec<TwilightCommand>,
+    ) -> Vec<TwilightCommand> {
+        remote
+            .into_iter()
+            .filter(|command| !report.contains(&command.name, command.kind))
+            .collect()
+    }
+
+    /// Adds the deletion of the given stale command to the given report.
+    fn push_deletion<T>(
+        report: &mut RegistrationReport,
+        stale: TwilightCommand,
+        result: Result<T, twilight_http::Error>,
+    ) {
+        let status = match result {
+            Ok(_) => RegistrationStatus::Deleted,
+            Err(error) => {
+                warn!("Failed to delete stale command {}: {}", stale.name, error);
+                RegistrationStatus::Failed(Box::new(error))
             }
+        };
 
-            subcommands
-        } else {
-            unreachable!()
-        }
+        report.push(stale.name.clone(), stale.kind, status, Some(stale));
     }
+}
 
-    /// Creates a subcommand at the given scope.
-    fn create_subcommand(&self, cmd: &Command<D>) -> CommandOption {
-        debug!("Registering {} subcommand", cmd.name);
+/// Deserializes the given raw interaction.
+#[cfg(not(feature = "simd-json"))]
+fn deserialize_interaction(
+    body: &[u8],
+) -> Result<Interaction, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(serde_json::from_slice(body)?)
+}
 
-        CommandOption::SubCommand(OptionsCommandOptionData {
-            name: cmd.name.to_string(),
-            description: cmd.description.to_string(),
-            options: self.arg_options(&cmd.arguments),
-            ..Default::default()
-        })
+/// Deserializes the given raw interaction using `simd-json`, which parses a mutable copy of it.
+#[cfg(feature = "simd-json")]
+fn deserialize_interaction(
+    body: &[u8],
+) -> Result<Interaction, Box<dyn std::error::Error + Send + Sync>> {
+    let mut body = body.to_vec();
+    Ok(simd_json::serde::from_slice(&mut body)?)
+}
+
+/// Takes the options provided to the invoked command out of the given ones, walking through the
+/// subcommand group and subcommand, if any, without cloning them.
+fn leaf_options(options: &mut Vec<CommandDataOption>) -> Vec<CommandDataOption> {
+    match options.first_mut().map(|option| &mut option.value) {
+        Some(CommandOptionValue::SubCommandGroup(inner))
+        | Some(CommandOptionValue::SubCommand(inner)) => leaf_options(inner),
+        _ => std::mem::take(options),
     }
 }
+
+/// Gets the path used to invoke a command out of the given data, including the names of the
+/// subcommand group and subcommand, if any.
+fn invoked_path(data: &CommandData) -> String {
+    let mut path = data.name.clone();
+    let mut options = data.options.as_slice();
+
+    while let Some(option) = options.first() {
+        match &option.value {
+            CommandOptionValue::SubCommandGroup(inner) | CommandOptionValue::SubCommand(inner) => {
+                path.push(' ');
+                path.push_str(&option.name);
+                options = inner;
+            }
+            _ => break,
+        }
+    }
+
+    path
+}
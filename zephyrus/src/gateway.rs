@@ -0,0 +1,75 @@
+use crate::{framework::Framework, shard::ShardInfo};
+use std::sync::Arc;
+use tracing::{debug, warn};
+use twilight_gateway::{CloseFrame, Config, Event, EventTypeFlags, Intents, Shard, StreamExt};
+
+impl<D, E> Framework<D, E>
+where
+    D: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    /// Connects to the gateway using the recommended number of shards and processes every
+    /// received interaction until `ctrl-c` is pressed, closing all the shards before returning.
+    ///
+    /// This requires the `gateway` feature and is not available when using the `rc` feature.
+    pub async fn run(
+        self: Arc<Self>,
+        token: String,
+        intents: Intents,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let config = Config::new(token, intents);
+        let shards =
+            twilight_gateway::create_recommended(self.http_client(), config, |_, builder| {
+                builder.build()
+            })
+            .await?;
+
+        let mut senders = Vec::with_capacity(shards.len());
+        let mut tasks = Vec::with_capacity(shards.len());
+
+        for shard in shards {
+            senders.push(shard.sender());
+            tasks.push(tokio::spawn(Arc::clone(&self).run_shard(shard)));
+        }
+
+        tokio::signal::ctrl_c().await?;
+        debug!("Shutting down {} shards", senders.len());
+
+        for sender in senders {
+            // The shard has already been closed if this fails.
+            let _ = sender.close(CloseFrame::NORMAL);
+        }
+
+        for task in tasks {
+            task.await?;
+        }
+
+        Ok(())
+    }
+
+    /// Receives the events of the given shard, processing the interactions until it is closed.
+    async fn run_shard(self: Arc<Self>, mut shard: Shard) {
+        let id = shard.id().number();
+
+        while let Some(item) = shard.next_event(EventTypeFlags::INTERACTION_CREATE).await {
+            let interaction = match item {
+                Ok(Event::InteractionCreate(interaction)) => interaction.0,
+                Ok(_) => continue,
+                Err(why) => {
+                    warn!("Error receiving event on shard {}: {}", id, why);
+                    continue;
+                }
+            };
+
+            let latency = shard.latency().recent().first().copied();
+            let info = ShardInfo::new(id).latency_getter(move || latency);
+            let framework = Arc::clone(&self);
+
+            tokio::spawn(async move {
+                framework.process_with_shard(interaction, info).await;
+            });
+        }
+
+        debug!("Shard {} closed", id);
+    }
+}
@@ -1,28 +1,33 @@
 use crate::{
     builder::FnPointer,
-    command::{Command, CommandMap},
+    command::{Command, CommandMap, DefaultError, MAX_OPTIONS},
+    hook::BeforeHook,
     twilight_exports::Permissions,
 };
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Formatter, Result as FmtResult},
+};
 
 /// A map of [parent groups](self::GroupParent).
-pub type ParentGroupMap<D> = HashMap<&'static str, GroupParent<D>>;
+pub type ParentGroupMap<D, E = DefaultError> = HashMap<Cow<'static, str>, GroupParent<D, E>>;
 /// A map of [command groups](self::CommandGroup).
-pub type GroupMap<D> = HashMap<&'static str, CommandGroup<D>>;
+pub type GroupMap<D, E = DefaultError> = HashMap<Cow<'static, str>, CommandGroup<D, E>>;
 
 /// Types a [group parent](self::GroupParent) can be.
-pub enum ParentType<D> {
+pub enum ParentType<D, E = DefaultError> {
     /// Simple, the group only has subcommands.
-    Simple(CommandMap<D>),
+    Simple(CommandMap<D, E>),
     /// Group, the group has other groups inside of it.
-    Group(GroupMap<D>),
+    Group(GroupMap<D, E>),
 }
 
-impl<D> ParentType<D> {
+impl<D, E> ParentType<D, E> {
     /// Tries to get the [`map`](crate::command::CommandMap) of the given
     /// [parent type](self::ParentType), returning `Some` if the parent variant is
     /// [`simple`](self::ParentType::Simple).
-    pub fn as_simple(&self) -> Option<&CommandMap<D>> {
+    pub fn as_simple(&self) -> Option<&CommandMap<D, E>> {
         match self {
             Self::Simple(map) => Some(map),
             _ => None,
@@ -31,41 +36,113 @@ impl<D> ParentType<D> {
 
     /// Tries to get the [`group`](self::GroupMap) of the given [parent type](self::ParentType),
     /// returning `Some` if the parent variant is a [`group`](self::ParentType::Group).
-    pub fn as_group(&self) -> Option<&GroupMap<D>> {
+    pub fn as_group(&self) -> Option<&GroupMap<D, E>> {
         match self {
             Self::Group(group) => Some(group),
             _ => None,
         }
     }
+
+    /// Gets the number of direct children, either subcommands or subcommand groups.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Simple(map) => map.len(),
+            Self::Group(map) => map.len(),
+        }
+    }
+
+    /// Whether there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<D, E> Debug for ParentType<D, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Simple(map) => f.debug_tuple("Simple").field(&sorted(map)).finish(),
+            Self::Group(map) => f.debug_tuple("Group").field(&sorted(map)).finish(),
+        }
+    }
+}
+
+/// Sorts the given map by its keys, so it is always printed in the same order.
+pub(crate) fn sorted<'a, V>(map: &'a HashMap<Cow<'static, str>, V>) -> BTreeMap<&'a str, &'a V> {
+    map.iter().map(|(key, value)| (key.as_ref(), value)).collect()
 }
 
 /// A parent of a group of sub commands, either a
 /// map of [commands](crate::command::Command) referred by discord as `SubCommand`
 /// or a map of [groups](self::CommandGroup) referred by discord as `SubCommandGroup`.
-pub struct GroupParent<D> {
+pub struct GroupParent<D, E = DefaultError> {
     /// The name of the upper command
     ///
     /// e.g.: /parent/<subcommand..>
     ///
     /// where `parent` is `name`.
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     /// The description of the upper command.
-    pub description: &'static str,
+    pub description: Cow<'static, str>,
     /// This parent group child commands.
-    pub kind: ParentType<D>,
+    pub kind: ParentType<D, E>,
     /// The required permissions to execute commands inside this group
     pub required_permissions: Option<Permissions>,
+    /// The category of this group, only used as metadata.
+    pub category: Option<&'static str>,
+    /// The checks executed before every command inside this group, including the ones of its
+    /// subcommand groups.
+    pub checks: Vec<BeforeHook<D>>,
+}
+
+impl<D, E> GroupParent<D, E> {
+    /// Gets the number of executable commands inside of this group, including the ones of its
+    /// subcommand groups.
+    pub fn len(&self) -> usize {
+        self.commands().count()
+    }
+
+    /// Whether this group has no commands.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the executable commands inside of this group, including the ones of its
+    /// subcommand groups.
+    pub fn commands(&self) -> Box<dyn Iterator<Item = &Command<D, E>> + '_> {
+        match &self.kind {
+            ParentType::Simple(map) => Box::new(map.values()),
+            ParentType::Group(map) => Box::new(map.values().flat_map(CommandGroup::commands)),
+        }
+    }
+}
+
+impl<D, E> Debug for GroupParent<D, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("GroupParent")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("kind", &self.kind)
+            .field("required_permissions", &self.required_permissions)
+            .field("category", &self.category)
+            .field("checks", &self.checks.len())
+            .finish()
+    }
 }
 
 /// A builder of a [group parent](self::GroupParent), see it for documentation.
-pub struct GroupParentBuilder<D> {
-    name: Option<&'static str>,
-    description: Option<&'static str>,
-    kind: ParentType<D>,
+pub struct GroupParentBuilder<D, E = DefaultError> {
+    name: Option<Cow<'static, str>>,
+    description: Option<Cow<'static, str>>,
+    kind: ParentType<D, E>,
     required_permissions: Option<Permissions>,
+    category: Option<&'static str>,
+    checks: Vec<BeforeHook<D>>,
+    /// The first child which couldn't be added because the group already had children of the
+    /// other kind.
+    conflict: Option<Cow<'static, str>>,
 }
 
-impl<D> GroupParentBuilder<D> {
+impl<D, E> GroupParentBuilder<D, E> {
     /// Creates a new builder.
     pub(crate) fn new() -> Self {
         Self {
@@ -73,18 +150,21 @@ impl<D> GroupParentBuilder<D> {
             description: None,
             kind: ParentType::Group(Default::default()),
             required_permissions: None,
+            category: None,
+            checks: Vec::new(),
+            conflict: None,
         }
     }
 
     /// Sets the name of this parent group.
-    pub fn name(&mut self, name: &'static str) -> &mut Self {
-        self.name = Some(name);
+    pub fn name(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.name = Some(name.into());
         self
     }
 
     /// Sets the description of this parent group.
-    pub fn description(&mut self, description: &'static str) -> &mut Self {
-        self.description = Some(description);
+    pub fn description(&mut self, description: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.description = Some(description.into());
         self
     }
 
@@ -93,101 +173,269 @@ impl<D> GroupParentBuilder<D> {
         self
     }
 
+    /// Sets the category of this parent group.
+    pub fn category(&mut self, category: &'static str) -> &mut Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Sets the checks executed before every command inside this parent group, before the
+    /// checks of its subcommand groups and the commands themselves.
+    pub fn checks(&mut self, checks: Vec<BeforeHook<D>>) -> &mut Self {
+        self.checks = checks;
+        self
+    }
+
     /// Sets this parent group as a [group](self::ParentType::Group),
     /// allowing to create subcommand groups inside of it.
+    ///
+    /// A parent group can't have both subcommands and subcommand groups.
     pub fn group<F>(&mut self, fun: F) -> &mut Self
     where
-        F: FnOnce(&mut CommandGroupBuilder<D>) -> &mut CommandGroupBuilder<D>,
+        F: FnOnce(&mut CommandGroupBuilder<D, E>) -> &mut CommandGroupBuilder<D, E>,
     {
         let mut builder = CommandGroupBuilder::new();
         fun(&mut builder);
         let built = builder.build();
 
-        if let ParentType::Group(map) = &mut self.kind {
-            assert!(!map.contains_key(built.name));
-            map.insert(built.name, built);
-        } else {
-            let mut map = GroupMap::new();
-            map.insert(built.name, built);
-            self.kind = ParentType::Group(map);
+        match &mut self.kind {
+            ParentType::Group(map) => {
+                if map.contains_key(&built.name) {
+                    panic!("{} {} already registered", self.display_name(), built.name);
+                }
+                map.insert(built.name.clone(), built);
+            }
+            ParentType::Simple(map) if map.is_empty() => {
+                let mut map = GroupMap::new();
+                map.insert(built.name.clone(), built);
+                self.kind = ParentType::Group(map);
+            }
+            ParentType::Simple(_) => {
+                self.conflict.get_or_insert(built.name);
+            }
         }
         self
     }
 
     /// Sets this parent group as [simple](self::ParentType::Simple), only allowing subcommands.
-    pub fn add_command(&mut self, fun: FnPointer<Command<D>>) -> &mut Self {
-        let command = fun();
-        if let ParentType::Simple(map) = &mut self.kind {
-            map.insert(command.name, command);
-        } else {
-            let mut map = CommandMap::new();
-            map.insert(command.name, command);
-            self.kind = ParentType::Simple(map);
+    pub fn add_command(&mut self, fun: FnPointer<Command<D, E>>) -> &mut Self {
+        self.insert_command(fun())
+    }
+
+    /// Same as [add_command](Self::add_command), but using an already built command, such as one
+    /// built at runtime using a closure.
+    pub fn insert_command(&mut self, command: Command<D, E>) -> &mut Self {
+        match &mut self.kind {
+            ParentType::Simple(map) => {
+                if map.contains_key(&command.name) {
+                    panic!("{} {} already registered", self.display_name(), command.name);
+                }
+                map.insert(command.name.clone(), command);
+            }
+            ParentType::Group(map) if map.is_empty() => {
+                let mut map = CommandMap::new();
+                map.insert(command.name.clone(), command);
+                self.kind = ParentType::Simple(map);
+            }
+            ParentType::Group(_) => {
+                self.conflict.get_or_insert(command.name);
+            }
         }
         self
     }
 
+    /// Gets the name of the group being built, used in validation errors.
+    fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("<unnamed group>")
+    }
+
     /// Builds this parent group, returning an [group parent](self::GroupParent).
-    pub fn build(self) -> GroupParent<D> {
-        assert!(self.name.is_some() && self.description.is_some());
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group doesn't have a name, a description or children, if it has both
+    /// subcommands and subcommand groups or if it has more children than allowed by discord.
+    pub fn build(self) -> GroupParent<D, E> {
+        let name = match self.name {
+            Some(name) => name,
+            None => panic!("A group is missing its name"),
+        };
+        let description = match self.description {
+            Some(description) => description,
+            None => panic!("Group {} is missing its description", name),
+        };
+
+        if let Some(child) = self.conflict {
+            panic!(
+                "Group {} can't have both subcommands and subcommand groups, found {}",
+                name, child
+            );
+        }
+
+        let children = match &self.kind {
+            ParentType::Simple(map) => {
+                for command in map.values() {
+                    command.validate(&format!("{} {}", name, command.name));
+                }
+                map.len()
+            }
+            ParentType::Group(map) => {
+                for group in map.values() {
+                    group.validate(&name);
+                }
+                map.len()
+            }
+        };
+
+        if children == 0 {
+            panic!("Group {} has no subcommands", name);
+        }
+        if children > MAX_OPTIONS {
+            panic!(
+                "Group {} has {} children, but at most {} are allowed",
+                name, children, MAX_OPTIONS
+            );
+        }
+
         GroupParent {
-            name: self.name.unwrap(),
-            description: self.description.unwrap(),
+            name,
+            description,
             kind: self.kind,
             required_permissions: self.required_permissions,
+            category: self.category,
+            checks: self.checks,
         }
     }
 }
 
 /// A group of commands, referred by discord as `SubCommandGroup`.
-pub struct CommandGroup<D> {
+pub struct CommandGroup<D, E = DefaultError> {
     /// The upper command
     ///
     /// e.g.: /parent/command/<subcommand..>/<options..>
     ///
     /// where `command` is `name`.
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     /// The description of this group.
-    pub description: &'static str,
+    pub description: Cow<'static, str>,
     /// The commands this group has as children.
-    pub subcommands: CommandMap<D>,
+    pub subcommands: CommandMap<D, E>,
+    /// The checks executed before every command of this group.
+    pub checks: Vec<BeforeHook<D>>,
+}
+
+impl<D, E> Debug for CommandGroup<D, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("CommandGroup")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("subcommands", &sorted(&self.subcommands))
+            .field("checks", &self.checks.len())
+            .finish()
+    }
+}
+
+impl<D, E> CommandGroup<D, E> {
+    /// Gets the number of subcommands of this group.
+    pub fn len(&self) -> usize {
+        self.subcommands.len()
+    }
+
+    /// Whether this group has no subcommands.
+    pub fn is_empty(&self) -> bool {
+        self.subcommands.is_empty()
+    }
+
+    /// Iterates over the subcommands of this group.
+    pub fn commands(&self) -> impl Iterator<Item = &Command<D, E>> {
+        self.subcommands.values()
+    }
+
+    /// Checks the group is valid inside of the parent group with the given name, panicking
+    /// otherwise.
+    fn validate(&self, parent: &str) {
+        let path = format!("{} {}", parent, self.name);
+
+        if self.subcommands.is_empty() {
+            panic!("Subcommand group {} has no subcommands", path);
+        }
+        if self.subcommands.len() > MAX_OPTIONS {
+            panic!(
+                "Subcommand group {} has {} subcommands, but at most {} are allowed",
+                path,
+                self.subcommands.len(),
+                MAX_OPTIONS
+            );
+        }
+
+        for command in self.subcommands.values() {
+            command.validate(&format!("{} {}", path, command.name));
+        }
+    }
 }
 
 /// A builder for a [CommandGroup](self::CommandGroup), see it for documentation.
-pub struct CommandGroupBuilder<D> {
-    name: Option<&'static str>,
-    description: Option<&'static str>,
-    subcommands: CommandMap<D>,
+pub struct CommandGroupBuilder<D, E = DefaultError> {
+    name: Option<Cow<'static, str>>,
+    description: Option<Cow<'static, str>>,
+    subcommands: CommandMap<D, E>,
+    checks: Vec<BeforeHook<D>>,
 }
 
-impl<D> CommandGroupBuilder<D> {
+impl<D, E> CommandGroupBuilder<D, E> {
     /// Sets the upper command of this group.
-    pub fn name(&mut self, name: &'static str) -> &mut Self {
-        self.name = Some(name);
+    pub fn name(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.name = Some(name.into());
         self
     }
 
     /// Sets the description of this group.
-    pub fn description(&mut self, description: &'static str) -> &mut Self {
-        self.description = Some(description);
+    pub fn description(&mut self, description: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the checks executed before every command of this group, after the checks of its
+    /// parent group and before the ones of the commands themselves.
+    pub fn checks(&mut self, checks: Vec<BeforeHook<D>>) -> &mut Self {
+        self.checks = checks;
         self
     }
 
     /// Adds a command to this group.
-    pub fn add_command(&mut self, fun: FnPointer<Command<D>>) -> &mut Self {
-        let command = fun();
-        self.subcommands.insert(command.name, command);
+    pub fn add_command(&mut self, fun: FnPointer<Command<D, E>>) -> &mut Self {
+        self.insert_command(fun())
+    }
+
+    /// Adds an already built command to this group, such as one built at runtime using a closure.
+    pub fn insert_command(&mut self, command: Command<D, E>) -> &mut Self {
+        if self.subcommands.contains_key(&command.name) {
+            panic!(
+                "{} {} already registered",
+                self.name.as_deref().unwrap_or("<unnamed group>"),
+                command.name
+            );
+        }
+        self.subcommands.insert(command.name.clone(), command);
         self
     }
 
     /// Builds the builder into a [group](self::CommandGroup).
-    pub(crate) fn build(self) -> CommandGroup<D> {
-        assert!(self.name.is_some() && self.description.is_some());
+    pub(crate) fn build(self) -> CommandGroup<D, E> {
+        let name = match self.name {
+            Some(name) => name,
+            None => panic!("A subcommand group is missing its name"),
+        };
+        let description = match self.description {
+            Some(description) => description,
+            None => panic!("Subcommand group {} is missing its description", name),
+        };
 
         CommandGroup {
-            name: self.name.unwrap(),
-            description: self.description.unwrap(),
+            name,
+            description,
             subcommands: self.subcommands,
+            checks: self.checks,
         }
     }
 
@@ -197,6 +445,7 @@ impl<D> CommandGroupBuilder<D> {
             name: None,
             description: None,
             subcommands: Default::default(),
+            checks: Vec::new(),
         }
     }
 }
@@ -0,0 +1,312 @@
+use crate::{
+    compat,
+    framework::Framework,
+    tree::CommandEntry,
+    twilight_exports::*,
+};
+use std::collections::BTreeMap;
+
+/// The maximum number of fields an embed can have.
+const MAX_FIELDS: usize = 25;
+/// The maximum length of an embed field value.
+const MAX_FIELD_LENGTH: usize = 1024;
+/// The maximum length of all the texts of an embed combined.
+const MAX_EMBED_LENGTH: usize = 6000;
+/// The length kept free for the footer telling how many sections were left out.
+const FOOTER_LENGTH: usize = 64;
+
+/// An opt-in help command generated by the framework, listing all the commands available to the
+/// user who invokes it.
+///
+/// The command is registered alongside the other commands of the framework and handled by the
+/// framework itself, optionally accepting a `command` argument to show the details of a single
+/// command.
+#[derive(Debug, Clone)]
+pub struct HelpCommand {
+    /// The name of the help command.
+    pub name: &'static str,
+    /// The description of the help command.
+    pub description: &'static str,
+    /// The color of the embeds sent by the help command.
+    pub color: Option<u32>,
+    /// Whether the help command responses should only be seen by the user who invoked it.
+    pub ephemeral: bool,
+}
+
+impl Default for HelpCommand {
+    fn default() -> Self {
+        Self {
+            name: "help",
+            description: "Shows the available commands",
+            color: None,
+            ephemeral: true,
+        }
+    }
+}
+
+impl HelpCommand {
+    /// The name of the argument used to show the details of a single command.
+    pub const ARGUMENT: &'static str = "command";
+
+    /// Creates a new help command with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the help command.
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the description of the help command.
+    pub fn description(mut self, description: &'static str) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Sets the color of the embeds sent by the help command.
+    pub fn color(mut self, color: u32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets whether the help command responses should only be seen by the invoker.
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    /// Gets the options the help command is registered with.
+    pub(crate) fn options(&self) -> Vec<CommandOption> {
+        let mut option = compat::option(
+            CommandOptionType::String,
+            Self::ARGUMENT,
+            "The command to show the details of",
+        );
+        option.required = Some(false);
+        vec![option]
+    }
+
+    /// Creates the response of the help command for the given interaction, showing only the
+    /// commands the invoker is allowed to use.
+    pub fn response<D, E>(&self, framework: &Framework<D, E>, interaction: &Interaction) -> InteractionResponse {
+        let permissions = interaction.member.as_ref().and_then(|member| member.permissions);
+        let entries = framework
+            .iter_commands()
+            .filter(|entry| match (entry.required_permissions(), permissions) {
+                (None, _) => true,
+                (Some(required), Some(permissions)) => permissions.contains(required),
+                (Some(_), None) => false,
+            })
+            .collect::<Vec<_>>();
+
+        let embed = match self.requested_command(interaction) {
+            Some(name) => {
+                let name = name.trim_start_matches('/');
+                match entries.iter().find(|entry| entry.path.to_string() == name) {
+                    Some(entry) => self.command_embed(entry),
+                    None => self.embed(
+                        String::from("Command not found"),
+                        format!("No command named `/{}` is available", name),
+                        Vec::new(),
+                    ),
+                }
+            }
+            None => self.list_embed(&entries),
+        };
+
+        InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(InteractionResponseData {
+                embeds: Some(vec![embed]),
+                flags: self.ephemeral.then_some(MessageFlags::EPHEMERAL),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Gets the command the user asked the details of, if any.
+    fn requested_command<'a>(&self, interaction: &'a Interaction) -> Option<&'a str> {
+        match interaction.data.as_ref()? {
+            InteractionData::ApplicationCommand(data) => data
+                .options
+                .iter()
+                .find(|option| option.name == Self::ARGUMENT)
+                .and_then(|option| match &option.value {
+                    CommandOptionValue::String(s) => Some(s.as_str()),
+                    _ => None,
+                }),
+            _ => None,
+        }
+    }
+
+    /// Creates an embed listing all the given commands, grouped by their category or, if they
+    /// don't have one, by their parent.
+    fn list_embed<D, E>(&self, entries: &[CommandEntry<'_, D, E>]) -> Embed {
+        let mut sections = BTreeMap::<&str, Vec<String>>::new();
+
+        for entry in entries {
+            let section = entry
+                .category()
+                .or_else(|| entry.parent.map(|parent| parent.name.as_ref()))
+                .unwrap_or("General");
+            sections
+                .entry(section)
+                .or_default()
+                .push(format!("`/{}` - {}", entry.path, entry.command.description));
+        }
+
+        self.sections_embed(sections)
+    }
+
+    /// Creates an embed with a field for each of the given sections, leaving out the sections
+    /// which don't fit into the limits of an embed and telling how many were left out in its
+    /// footer.
+    fn sections_embed(&self, sections: BTreeMap<&str, Vec<String>>) -> Embed {
+        let title = String::from("Commands");
+        let description = format!(
+            "Use `/{} {}:<name>` to see the details of a command",
+            self.name,
+            Self::ARGUMENT
+        );
+
+        let total = sections.len();
+        let mut length = title.chars().count() + description.chars().count() + FOOTER_LENGTH;
+        let mut fields = Vec::new();
+        for (name, lines) in sections {
+            let field = EmbedField {
+                inline: false,
+                name: name.to_string(),
+                value: truncate(lines.join("\n")),
+            };
+            length += field.name.chars().count() + field.value.chars().count();
+            if fields.len() == MAX_FIELDS || length > MAX_EMBED_LENGTH {
+                break;
+            }
+            fields.push(field);
+        }
+
+        let hidden = total - fields.len();
+        let mut embed = self.embed(title, description, fields);
+        if hidden > 0 {
+            embed.footer = Some(EmbedFooter {
+                icon_url: None,
+                proxy_icon_url: None,
+                text: format!("...and {} more", hidden),
+            });
+        }
+        embed
+    }
+
+    /// Creates an embed showing the details of the given command.
+    fn command_embed<D, E>(&self, entry: &CommandEntry<'_, D, E>) -> Embed {
+        let fields = entry
+            .command
+            .arguments
+            .iter()
+            .take(MAX_FIELDS)
+            .map(|argument| EmbedField {
+                inline: false,
+                name: format!(
+                    "{} ({}{:?})",
+                    argument.name,
+                    if argument.required { "" } else { "optional " },
+                    argument.kind
+                ),
+                value: truncate(argument.description.to_string()),
+            })
+            .collect();
+
+        self.embed(
+            format!("/{}", entry.path),
+            entry.command.description.to_string(),
+            fields,
+        )
+    }
+
+    fn embed(&self, title: String, description: String, fields: Vec<EmbedField>) -> Embed {
+        Embed {
+            author: None,
+            color: self.color,
+            description: Some(description),
+            fields,
+            footer: None,
+            image: None,
+            kind: String::from("rich"),
+            provider: None,
+            thumbnail: None,
+            timestamp: None,
+            title: Some(title),
+            url: None,
+            video: None,
+        }
+    }
+}
+
+/// Truncates the given string to fit into an embed field.
+fn truncate(mut value: String) -> String {
+    if value.len() > MAX_FIELD_LENGTH {
+        let mut end = MAX_FIELD_LENGTH - 3;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+        value.push_str("...");
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HelpCommand, MAX_EMBED_LENGTH, MAX_FIELDS};
+    use crate::twilight_exports::Embed;
+    use std::collections::BTreeMap;
+
+    fn length(embed: &Embed) -> usize {
+        let footer = embed.footer.as_ref().map(|footer| footer.text.as_str());
+        let texts = [embed.title.as_deref(), embed.description.as_deref(), footer];
+        let fields = embed
+            .fields
+            .iter()
+            .map(|field| field.name.chars().count() + field.value.chars().count());
+
+        texts.iter().flatten().map(|text| text.chars().count()).chain(fields).sum()
+    }
+
+    #[test]
+    fn caps_the_number_of_fields() {
+        let names = (0..30).map(|i| format!("Category {:02}", i)).collect::<Vec<_>>();
+        let sections = names
+            .iter()
+            .map(|name| (name.as_str(), vec![String::from("`/ping` - Pong")]))
+            .collect::<BTreeMap<_, _>>();
+
+        let embed = HelpCommand::new().sections_embed(sections);
+        assert_eq!(embed.fields.len(), MAX_FIELDS);
+        assert_eq!(embed.footer.unwrap().text, "...and 5 more");
+    }
+
+    #[test]
+    fn caps_the_length_of_the_embed() {
+        let names = (0..10).map(|i| format!("Category {}", i)).collect::<Vec<_>>();
+        let sections = names
+            .iter()
+            .map(|name| (name.as_str(), vec!["a".repeat(1000)]))
+            .collect::<BTreeMap<_, _>>();
+
+        let embed = HelpCommand::new().sections_embed(sections);
+        assert!(length(&embed) <= MAX_EMBED_LENGTH);
+        assert_eq!(embed.fields.len(), 5);
+        assert_eq!(embed.footer.unwrap().text, "...and 5 more");
+    }
+
+    #[test]
+    fn lists_everything_that_fits() {
+        let sections = BTreeMap::from([("General", vec![String::from("`/ping` - Pong")])]);
+
+        let embed = HelpCommand::new().sections_embed(sections);
+        assert_eq!(embed.fields.len(), 1);
+        assert!(embed.footer.is_none());
+    }
+}
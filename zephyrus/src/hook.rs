@@ -1,19 +1,151 @@
 use crate::context::AutocompleteContext;
 use crate::{
-    command::CommandResult, context::SlashContext, twilight_exports::InteractionResponseData,
+    command::{CommandResult, DefaultError}, context::SlashContext, error::FrameworkError,
+    twilight_exports::{Interaction, InteractionResponse, InteractionResponseData, InteractionType},
     BoxFuture,
 };
 
+/// The verdict of a [before hook](BeforeHook), deciding whether the command is executed.
+///
+/// When a command is not executed, the verdict is delivered to the [error hook](ErrorHook) as a
+/// [skipped](FrameworkError::Skipped) error, so it knows why the command didn't run.
+#[derive(Debug, Clone)]
+pub enum HookVerdict {
+    /// The command is executed, unless a later hook decides otherwise.
+    Continue,
+    /// The command is not executed for the given reason.
+    Skip {
+        /// Why the command was skipped.
+        reason: String,
+    },
+    /// The command is not executed, answering the interaction with the given response instead.
+    Reply(Box<InteractionResponse>),
+}
+
+impl HookVerdict {
+    /// Creates a verdict skipping the command for the given reason.
+    pub fn skip(reason: impl Into<String>) -> Self {
+        Self::Skip {
+            reason: reason.into(),
+        }
+    }
+
+    /// Creates a verdict skipping the command and answering the interaction with the given
+    /// response, which can be built using a [response builder](crate::response::ResponseBuilder).
+    pub fn reply(response: impl Into<InteractionResponse>) -> Self {
+        Self::Reply(Box::new(response.into()))
+    }
+
+    /// Whether the command is executed.
+    pub fn is_continue(&self) -> bool {
+        matches!(self, Self::Continue)
+    }
+}
+
+/// Converts the `bool` returned by hooks which don't give a reason, `false` skipping the command.
+impl From<bool> for HookVerdict {
+    fn from(execute: bool) -> Self {
+        if execute {
+            Self::Continue
+        } else {
+            Self::skip("A check failed")
+        }
+    }
+}
+
+/// The outcome of a command invocation, given to the [after hook](AfterHook).
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum CommandOutcome<E = DefaultError> {
+    /// The command was executed, returning the given result.
+    Executed(CommandResult<E>),
+    /// The command was not executed because of the given verdict of its hooks or checks.
+    Skipped(HookVerdict),
+}
+
+impl<E> CommandOutcome<E> {
+    /// Gets the result of the command, if it was executed.
+    pub fn result(&self) -> Option<&CommandResult<E>> {
+        match self {
+            Self::Executed(result) => Some(result),
+            Self::Skipped(_) => None,
+        }
+    }
+
+    /// Gets the verdict which skipped the command, if it wasn't executed.
+    pub fn verdict(&self) -> Option<&HookVerdict> {
+        match self {
+            Self::Executed(_) => None,
+            Self::Skipped(verdict) => Some(verdict),
+        }
+    }
+}
+
+/// The verdict of an [interceptor](InterceptorHook), deciding whether the framework keeps
+/// processing the interaction.
+#[derive(Debug, Clone)]
+pub enum Intercept {
+    /// The interaction, as modified by the interceptor, is processed as usual.
+    Continue,
+    /// The interaction is consumed by the interceptor, so the framework doesn't process it.
+    Consume,
+    /// The interaction is not processed, answering it with the given response instead.
+    Reply(Box<InteractionResponse>),
+}
+
+impl Intercept {
+    /// Creates a verdict answering the interaction with the given response instead of processing
+    /// it, which can be built using a [response builder](crate::response::ResponseBuilder).
+    pub fn reply(response: impl Into<InteractionResponse>) -> Self {
+        Self::Reply(Box::new(response.into()))
+    }
+}
+
+/// A pointer to a function used by [interceptor hook](InterceptorHook).
+pub(crate) type InterceptorFn<D> =
+    for<'a> fn(&'a D, &'a mut Interaction) -> BoxFuture<'a, Intercept>;
+/// A hook executed before routing every interaction, which can modify or consume it, such as
+/// for A/B testing, shadow traffic or global killswitches.
+pub struct InterceptorHook<D>(pub InterceptorFn<D>);
+
 /// A pointer to a function used by [before hook](BeforeHook).
-pub(crate) type BeforeFn<D> = for<'a> fn(&'a SlashContext<'a, D>, &'a str) -> BoxFuture<'a, bool>;
+pub(crate) type BeforeFn<D> =
+    for<'a> fn(&'a SlashContext<'a, D>, &'a str) -> BoxFuture<'a, HookVerdict>;
 /// A hook executed before command execution.
 pub struct BeforeHook<D>(pub BeforeFn<D>);
 
 /// A pointer to a function used by [after hook](AfterHook).
-pub(crate) type AfterFn<D> =
-    for<'a> fn(&'a SlashContext<'a, D>, &'a str, CommandResult) -> BoxFuture<'a, ()>;
-/// A hook executed after command execution.
-pub struct AfterHook<D>(pub AfterFn<D>);
+pub(crate) type AfterFn<D, E> =
+    for<'a> fn(&'a SlashContext<'a, D>, &'a str, CommandOutcome<E>) -> BoxFuture<'a, ()>;
+/// A hook executed after command execution, or after the command was skipped by its hooks or
+/// checks.
+pub struct AfterHook<D, E = DefaultError>(pub AfterFn<D, E>);
+
+/// A pointer to a function used by [error hook](ErrorHook).
+pub(crate) type ErrorFn<D> =
+    for<'a> fn(&'a SlashContext<'a, D>, &'a str, FrameworkError) -> BoxFuture<'a, ()>;
+/// A hook executed when the framework fails to execute a command, such as when it panics.
+pub struct ErrorHook<D>(pub ErrorFn<D>);
+
+/// A pointer to a function used by [unknown command hook](UnknownCommandHook).
+pub(crate) type UnknownCommandFn<D> = for<'a> fn(&'a SlashContext<'a, D>, &'a str) -> BoxFuture<'a, ()>;
+/// A hook executed when an interaction refers to a command the framework doesn't know, such as a
+/// command removed or renamed which is still registered in discord.
+pub struct UnknownCommandHook<D>(pub UnknownCommandFn<D>);
+
+/// A pointer to a function used by [unhandled component hook](UnhandledComponentHook).
+pub(crate) type UnhandledComponentFn<D> =
+    for<'a> fn(&'a SlashContext<'a, D>, &'a str) -> BoxFuture<'a, ()>;
+/// A hook executed when a message component is used but no [button](crate::button::Button)
+/// handles its custom id, such as the components of a message sent before a restart.
+pub struct UnhandledComponentHook<D>(pub UnhandledComponentFn<D>);
+
+/// A pointer to a function used by [unhandled interaction hook](UnhandledInteractionHook).
+pub(crate) type UnhandledInteractionFn<D> =
+    for<'a> fn(&'a SlashContext<'a, D>, InteractionType) -> BoxFuture<'a, ()>;
+/// A hook executed when the framework receives an interaction of a type it doesn't handle, such
+/// as modal submits or types added to discord after this version of the framework.
+pub struct UnhandledInteractionHook<D>(pub UnhandledInteractionFn<D>);
 
 /// A pointer to a function used by [autocomplete hook](AutocompleteHook)
 pub(crate) type AutocompleteFn<D> =
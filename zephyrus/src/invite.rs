@@ -0,0 +1,158 @@
+use crate::parse_impl::error;
+use crate::prelude::*;
+use crate::twilight_exports::*;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+/// The prefixes of the links discord uses for invites.
+const INVITE_HOSTS: &[&str] = &[
+    "discord.gg/",
+    "discord.com/invite/",
+    "discordapp.com/invite/",
+    "canary.discord.com/invite/",
+    "ptb.discord.com/invite/",
+];
+/// The maximum length of an invite code, which vanity codes can reach.
+const MAX_CODE_LENGTH: usize = 32;
+
+/// The code of a discord invite, extracted out of either a full invite link, such as
+/// `https://discord.gg/twilight`, or the bare code.
+///
+/// Used as an argument, it is a string option rejecting the input which isn't an invite. The
+/// invite itself is only fetched when calling [resolve](Self::resolve).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InviteCode(String);
+
+impl InviteCode {
+    /// Extracts the invite code out of the given link or code, returning `None` if it isn't a
+    /// valid one.
+    pub fn new(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let link = input
+            .strip_prefix("https://")
+            .or_else(|| input.strip_prefix("http://"))
+            .unwrap_or(input);
+        let link = link.strip_prefix("www.").unwrap_or(link);
+
+        let code = INVITE_HOSTS
+            .iter()
+            .find_map(|host| link.strip_prefix(host))
+            .unwrap_or(link);
+        let code = code.split(['/', '?', '#']).next().unwrap_or_default();
+
+        let valid = (2..=MAX_CODE_LENGTH).contains(&code.len())
+            && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+        valid.then(|| Self(code.to_string()))
+    }
+
+    /// Gets the code of the invite.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Gets the link of the invite.
+    pub fn url(&self) -> String {
+        format!("https://discord.gg/{}", self.0)
+    }
+
+    /// Fetches the invite from discord, including its approximate member counts, failing if
+    /// the invite doesn't exist or has expired.
+    pub async fn resolve(&self, http_client: &Client) -> Result<Invite, Box<dyn Error + Send + Sync>> {
+        Ok(http_client.invite(&self.0).with_counts().await?.model().await?)
+    }
+}
+
+impl FromStr for InviteCode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s).ok_or(())
+    }
+}
+
+impl Display for InviteCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.0)
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for InviteCode {
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let input = String::parse(http_client, data, value).await?;
+        Self::new(&input)
+            .ok_or_else(|| error("InviteCode", true, &format!("{} is not a discord invite", input)))
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InviteCode;
+
+    fn code(input: &str) -> Option<String> {
+        InviteCode::new(input).map(|code| code.as_str().to_string())
+    }
+
+    #[test]
+    fn accepted_forms() {
+        let links = [
+            "twilight",
+            "  twilight  ",
+            "discord.gg/twilight",
+            "https://discord.gg/twilight",
+            "http://discord.gg/twilight",
+            "https://www.discord.gg/twilight",
+            "https://discord.com/invite/twilight",
+            "https://discordapp.com/invite/twilight",
+            "https://canary.discord.com/invite/twilight",
+            "https://ptb.discord.com/invite/twilight",
+            "https://discord.gg/twilight/",
+            "https://discord.gg/twilight?event=1",
+            "https://discord.gg/twilight#top",
+        ];
+
+        for link in links {
+            assert_eq!(code(link).as_deref(), Some("twilight"), "{:?}", link);
+        }
+        assert_eq!(code("a-b-c").as_deref(), Some("a-b-c"));
+    }
+
+    #[test]
+    fn url_and_display() {
+        let invite: InviteCode = "https://discord.gg/abc123".parse().unwrap();
+        assert_eq!(invite.url(), "https://discord.gg/abc123");
+        assert_eq!(invite.to_string(), "abc123");
+    }
+
+    #[test]
+    fn malformed_input() {
+        let long = "a".repeat(33);
+        let inputs = [
+            "",
+            "a",
+            "https://discord.gg/",
+            "https://example.com/invite",
+            "not an invite",
+            "abc_def",
+            "https://discord.gg/a b",
+            long.as_str(),
+        ];
+
+        for input in inputs {
+            assert_eq!(code(input), None, "{:?} should be rejected", input);
+        }
+        assert!(code(&"a".repeat(32)).is_some());
+    }
+}
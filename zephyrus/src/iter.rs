@@ -1,10 +1,75 @@
 use crate::twilight_exports::CommandDataOption;
+use std::collections::HashMap;
+
+/// A source of the options of a slash command, out of which the arguments are taken by name
+/// while parsing them, see [SlashContext::named_parse](crate::context::SlashContext::named_parse).
+pub trait TakeOption<'a> {
+    /// Takes the option with the given name out of the source.
+    fn take_option(&mut self, name: &str) -> Option<&'a CommandDataOption>;
+}
+
+/// The options of a slash command indexed by their name, allowing to look them up and take them
+/// out in constant time.
+#[derive(Debug, Clone, Default)]
+pub struct OptionMap<'a> {
+    options: HashMap<&'a str, &'a CommandDataOption>,
+}
+
+impl<'a> OptionMap<'a> {
+    /// Creates a map of the given options.
+    pub fn new(options: &'a [CommandDataOption]) -> Self {
+        Self {
+            options: options
+                .iter()
+                .map(|option| (option.name.as_str(), option))
+                .collect(),
+        }
+    }
+
+    /// Gets the option with the given name, leaving it in the map.
+    pub fn get(&self, name: &str) -> Option<&'a CommandDataOption> {
+        self.options.get(name).copied()
+    }
+
+    /// Takes the option with the given name out of the map.
+    pub fn take(&mut self, name: &str) -> Option<&'a CommandDataOption> {
+        self.options.remove(name)
+    }
+
+    /// Whether the map has an option with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    /// Gets the number of options left in the map.
+    pub fn len(&self) -> usize {
+        self.options.len()
+    }
+
+    /// Whether every option was taken out of the map.
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+
+    /// Iterates over the options left in the map, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a CommandDataOption> + '_ {
+        self.options.values().copied()
+    }
+}
+
+impl<'a> TakeOption<'a> for OptionMap<'a> {
+    fn take_option(&mut self, name: &str) -> Option<&'a CommandDataOption> {
+        self.take(name)
+    }
+}
 
 /// An iterator used to iterate through slash command options.
+#[deprecated(note = "use `OptionMap`, which looks up options by name in constant time")]
 pub struct DataIterator<'a> {
     src: Vec<&'a CommandDataOption>,
 }
 
+#[allow(deprecated)]
 impl<'a> DataIterator<'a> {
     /// Creates a new [iterator](self::DataIterator) at the given source.
     pub fn new(src: Vec<&'a CommandDataOption>) -> Self {
@@ -12,41 +77,26 @@ impl<'a> DataIterator<'a> {
     }
 }
 
+#[allow(deprecated)]
 impl<'a> DataIterator<'a> {
     /// Gets the first value which satisfies the given predicate.
     pub fn get<F>(&mut self, predicate: F) -> Option<&'a CommandDataOption>
     where
         F: Fn(&CommandDataOption) -> bool,
     {
-        let i = {
-            let mut idx = 0;
-            let mut found = false;
-
-            while idx < self.src.len() && !found {
-                if predicate(self.src[idx]) {
-                    found = true;
-                }
-
-                if !found {
-                    idx += 1;
-                }
-            }
-
-            if found {
-                Some(idx)
-            } else {
-                None
-            }
-        };
-
-        if let Some(i) = i {
-            Some(self.src.remove(i))
-        } else {
-            None
-        }
+        let i = self.src.iter().position(|option| predicate(option))?;
+        Some(self.src.remove(i))
+    }
+}
+
+#[allow(deprecated)]
+impl<'a> TakeOption<'a> for DataIterator<'a> {
+    fn take_option(&mut self, name: &str) -> Option<&'a CommandDataOption> {
+        self.get(|option| option.name == name)
     }
 }
 
+#[allow(deprecated)]
 impl<'a> std::ops::Deref for DataIterator<'a> {
     type Target = Vec<&'a CommandDataOption>;
 
@@ -55,6 +105,7 @@ impl<'a> std::ops::Deref for DataIterator<'a> {
     }
 }
 
+#[allow(deprecated)]
 impl std::ops::DerefMut for DataIterator<'_> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.src
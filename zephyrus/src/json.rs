@@ -0,0 +1,86 @@
+//! Support for JSON command arguments, enabled by the `json` feature.
+//!
+//! Both [Value] and [Json] are string options parsed as JSON, rejecting malformed input with
+//! the position of the error.
+
+use crate::parse_impl::error;
+use crate::prelude::*;
+use crate::twilight_exports::*;
+use serde::de::DeserializeOwned;
+pub use serde_json::Value;
+use std::ops::{Deref, DerefMut};
+
+/// An argument deserialized out of a string option containing JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    /// Gets the deserialized value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Deserializes the given string option.
+async fn parse_json<D, T>(
+    http_client: &WrappedClient,
+    data: &D,
+    value: Option<&CommandOptionValue>,
+    type_name: &str,
+) -> Result<T, ParseError>
+where
+    D: Send + Sync,
+    T: DeserializeOwned,
+{
+    let input = String::parse(http_client, data, value).await?;
+    serde_json::from_str(&input)
+        .map_err(|why| error(type_name, true, &format!("Invalid JSON: {}", why)))
+}
+
+#[async_trait]
+impl<D: Send + Sync> Parse<D> for Value {
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &D,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        parse_json(http_client, data, value, "Value").await
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+}
+
+#[async_trait]
+impl<D, T> Parse<D> for Json<T>
+where
+    D: Send + Sync,
+    T: DeserializeOwned + Send,
+{
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &D,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        parse_json(http_client, data, value, "Json").await.map(Json)
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+}
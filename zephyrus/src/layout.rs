@@ -0,0 +1,400 @@
+//! Builders of the layout components, also known as components V2, which replace the content
+//! and embeds of a message with text displays, sections, media galleries and containers.
+//!
+//! Messages using them must be sent with the
+//! [IS_COMPONENTS_V2](crate::twilight_exports::MessageFlags::IS_COMPONENTS_V2) flag, which
+//! [ResponseBuilder::layout](crate::response::ResponseBuilder::layout) sets.
+use crate::{
+    compat,
+    component::ActionRowBuilder,
+    twilight_exports::{ButtonStyle, Component, MediaGalleryItem, SeparatorSpacingSize},
+};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// The maximum number of components a message can have, including nested ones.
+pub const MAX_LAYOUT_COMPONENTS: usize = 40;
+/// The maximum number of text displays a section can have.
+pub const MAX_SECTION_TEXTS: usize = 3;
+/// The maximum number of items a media gallery can have.
+pub const MAX_GALLERY_ITEMS: usize = 10;
+/// The maximum length of all the text displays of a message combined.
+pub const MAX_LAYOUT_TEXT_LENGTH: usize = 4000;
+
+/// The errors produced when validating the components built by a
+/// [layout builder](LayoutBuilder).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// The layout has no components.
+    Empty,
+    /// The layout has more than [MAX_LAYOUT_COMPONENTS] components, including nested ones.
+    TooManyComponents(usize),
+    /// The text displays of the layout are longer than [MAX_LAYOUT_TEXT_LENGTH] combined.
+    TextTooLong(usize),
+    /// A section has no text displays or more than [MAX_SECTION_TEXTS].
+    SectionTexts(usize),
+    /// A section has no accessory.
+    MissingAccessory,
+    /// A media gallery has no items or more than [MAX_GALLERY_ITEMS].
+    GalleryItems(usize),
+    /// A container or an action row has no components.
+    EmptyComponent,
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Empty => f.write_str("The layout has no components"),
+            Self::TooManyComponents(count) => write!(
+                f,
+                "Too many components: {} out of {}",
+                count, MAX_LAYOUT_COMPONENTS
+            ),
+            Self::TextTooLong(length) => write!(
+                f,
+                "The text is too long: {} out of {} characters",
+                length, MAX_LAYOUT_TEXT_LENGTH
+            ),
+            Self::SectionTexts(count) => write!(
+                f,
+                "A section has {} text displays, expected between 1 and {}",
+                count, MAX_SECTION_TEXTS
+            ),
+            Self::MissingAccessory => f.write_str("A section has no accessory"),
+            Self::GalleryItems(count) => write!(
+                f,
+                "A media gallery has {} items, expected between 1 and {}",
+                count, MAX_GALLERY_ITEMS
+            ),
+            Self::EmptyComponent => f.write_str("A container or action row is empty"),
+        }
+    }
+}
+
+impl Error for LayoutError {}
+
+/// A builder of a section, see [ContainerBuilder::section].
+#[derive(Debug, Clone, Default)]
+pub struct SectionBuilder {
+    texts: Vec<Component>,
+    accessory: Option<Component>,
+}
+
+impl SectionBuilder {
+    /// Adds a text display with the given markdown content.
+    pub fn text(&mut self, content: impl Into<String>) -> &mut Self {
+        self.texts.push(compat::text_display(content.into()));
+        self
+    }
+
+    /// Sets a thumbnail showing the given media as the accessory.
+    pub fn thumbnail(&mut self, url: impl Into<String>) -> &mut Self {
+        self.accessory = Some(compat::thumbnail(url.into(), None));
+        self
+    }
+
+    /// Sets a thumbnail showing the given media with the given description, used as alt text,
+    /// as the accessory.
+    pub fn thumbnail_with_description(
+        &mut self,
+        url: impl Into<String>,
+        description: impl Into<String>,
+    ) -> &mut Self {
+        self.accessory = Some(compat::thumbnail(url.into(), Some(description.into())));
+        self
+    }
+
+    /// Sets a button with the given style, label and custom id as the accessory.
+    pub fn button(
+        &mut self,
+        style: ButtonStyle,
+        label: impl Into<String>,
+        custom_id: impl Into<String>,
+    ) -> &mut Self {
+        let mut button = compat::button(style, Some(label.into()));
+        button.custom_id = Some(custom_id.into());
+        self.accessory = Some(Component::Button(button));
+        self
+    }
+
+    /// Sets a button linking to the given url as the accessory.
+    pub fn link(&mut self, label: impl Into<String>, url: impl Into<String>) -> &mut Self {
+        let mut button = compat::button(ButtonStyle::Link, Some(label.into()));
+        button.url = Some(url.into());
+        self.accessory = Some(Component::Button(button));
+        self
+    }
+}
+
+/// A builder of a media gallery, see [ContainerBuilder::gallery].
+#[derive(Debug, Clone, Default)]
+pub struct MediaGalleryBuilder {
+    items: Vec<MediaGalleryItem>,
+}
+
+impl MediaGalleryBuilder {
+    /// Adds an item showing the given media, which can be an `attachment://<filename>`
+    /// reference to a file attached to the message.
+    pub fn item(&mut self, url: impl Into<String>) -> &mut Self {
+        self.items.push(compat::gallery_item(url.into(), None, false));
+        self
+    }
+
+    /// Adds an item showing the given media with the given description, used as alt text.
+    pub fn item_with_description(
+        &mut self,
+        url: impl Into<String>,
+        description: impl Into<String>,
+    ) -> &mut Self {
+        self.items
+            .push(compat::gallery_item(url.into(), Some(description.into()), false));
+        self
+    }
+
+    /// Adds an item showing the given media blurred until clicked.
+    pub fn spoiler(&mut self, url: impl Into<String>) -> &mut Self {
+        self.items.push(compat::gallery_item(url.into(), None, true));
+        self
+    }
+}
+
+/// A builder of the components of a container, also used for the top level components of a
+/// [layout](LayoutBuilder).
+#[derive(Debug, Clone, Default)]
+pub struct ContainerBuilder {
+    components: Vec<Component>,
+    accent_color: Option<u32>,
+    spoiler: bool,
+    error: Option<LayoutError>,
+}
+
+impl ContainerBuilder {
+    /// Adds a text display with the given markdown content.
+    pub fn text(&mut self, content: impl Into<String>) -> &mut Self {
+        self.component(compat::text_display(content.into()))
+    }
+
+    /// Adds a section, made of up to [MAX_SECTION_TEXTS] text displays next to an accessory.
+    pub fn section<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnOnce(&mut SectionBuilder) -> &mut SectionBuilder,
+    {
+        let mut section = SectionBuilder::default();
+        fun(&mut section);
+        match section.accessory {
+            Some(accessory) => self.component(compat::section(section.texts, accessory)),
+            None => {
+                self.error.get_or_insert(LayoutError::MissingAccessory);
+                self
+            }
+        }
+    }
+
+    /// Adds a media gallery.
+    pub fn gallery<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnOnce(&mut MediaGalleryBuilder) -> &mut MediaGalleryBuilder,
+    {
+        let mut gallery = MediaGalleryBuilder::default();
+        fun(&mut gallery);
+        self.component(compat::media_gallery(gallery.items))
+    }
+
+    /// Adds a separator, with a visible divider if `divider` is true.
+    pub fn separator(&mut self, divider: bool) -> &mut Self {
+        self.component(compat::separator(divider, SeparatorSpacingSize::Small))
+    }
+
+    /// Adds a separator with large spacing, with a visible divider if `divider` is true.
+    pub fn large_separator(&mut self, divider: bool) -> &mut Self {
+        self.component(compat::separator(divider, SeparatorSpacingSize::Large))
+    }
+
+    /// Adds an action row.
+    pub fn row<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnOnce(&mut ActionRowBuilder) -> &mut ActionRowBuilder,
+    {
+        let mut row = ActionRowBuilder::default();
+        fun(&mut row);
+        self.component(compat::action_row(row.components))
+    }
+
+    /// Adds the given component.
+    pub fn component(&mut self, component: impl Into<Component>) -> &mut Self {
+        self.components.push(component.into());
+        self
+    }
+
+    /// Sets the color of the left border of the container.
+    pub fn accent_color(&mut self, color: u32) -> &mut Self {
+        self.accent_color = Some(color);
+        self
+    }
+
+    /// Blurs the contents of the container until clicked.
+    pub fn spoiler(&mut self) -> &mut Self {
+        self.spoiler = true;
+        self
+    }
+}
+
+/// A builder used to assemble the layout components of a message, validating them against the
+/// limits set by discord.
+///
+/// ```rust,ignore
+/// let components = LayoutBuilder::new()
+///     .text("# Now playing")
+///     .container(|container| {
+///         container
+///             .accent_color(0x5865F2)
+///             .section(|section| section.text("**Song**\nArtist").thumbnail(cover_url))
+///             .separator(true)
+///             .row(|row| row.button(ButtonStyle::Primary, "Skip", skip_custom_id()))
+///     })
+///     .build()?;
+///
+/// Ok(ResponseBuilder::message().layout(components).build())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LayoutBuilder {
+    inner: ContainerBuilder,
+}
+
+impl LayoutBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a text display with the given markdown content.
+    pub fn text(mut self, content: impl Into<String>) -> Self {
+        self.inner.text(content);
+        self
+    }
+
+    /// Adds a section, see [ContainerBuilder::section].
+    pub fn section<F>(mut self, fun: F) -> Self
+    where
+        F: FnOnce(&mut SectionBuilder) -> &mut SectionBuilder,
+    {
+        self.inner.section(fun);
+        self
+    }
+
+    /// Adds a media gallery.
+    pub fn gallery<F>(mut self, fun: F) -> Self
+    where
+        F: FnOnce(&mut MediaGalleryBuilder) -> &mut MediaGalleryBuilder,
+    {
+        self.inner.gallery(fun);
+        self
+    }
+
+    /// Adds a separator, see [ContainerBuilder::separator].
+    pub fn separator(mut self, divider: bool) -> Self {
+        self.inner.separator(divider);
+        self
+    }
+
+    /// Adds an action row.
+    pub fn row<F>(mut self, fun: F) -> Self
+    where
+        F: FnOnce(&mut ActionRowBuilder) -> &mut ActionRowBuilder,
+    {
+        self.inner.row(fun);
+        self
+    }
+
+    /// Adds a container grouping the components added by the given function.
+    pub fn container<F>(mut self, fun: F) -> Self
+    where
+        F: FnOnce(&mut ContainerBuilder) -> &mut ContainerBuilder,
+    {
+        let mut container = ContainerBuilder::default();
+        fun(&mut container);
+        if let Some(error) = container.error {
+            self.inner.error.get_or_insert(error);
+        }
+        self.inner.component(compat::container(
+            container.components,
+            container.accent_color,
+            container.spoiler,
+        ));
+        self
+    }
+
+    /// Adds the given component.
+    pub fn component(mut self, component: impl Into<Component>) -> Self {
+        self.inner.component(component);
+        self
+    }
+
+    /// Validates the components and builds them.
+    pub fn build(self) -> Result<Vec<Component>, LayoutError> {
+        if let Some(error) = self.inner.error {
+            return Err(error);
+        }
+
+        let components = self.inner.components;
+        if components.is_empty() {
+            return Err(LayoutError::Empty);
+        }
+
+        let mut totals = (0, 0);
+        validate(&components, &mut totals)?;
+
+        let (count, length) = totals;
+        if count > MAX_LAYOUT_COMPONENTS {
+            return Err(LayoutError::TooManyComponents(count));
+        }
+        if length > MAX_LAYOUT_TEXT_LENGTH {
+            return Err(LayoutError::TextTooLong(length));
+        }
+
+        Ok(components)
+    }
+}
+
+/// Validates the given components and the nested ones, adding their number and the length of
+/// their text to the given totals.
+fn validate(components: &[Component], totals: &mut (usize, usize)) -> Result<(), LayoutError> {
+    for component in components {
+        totals.0 += 1;
+        match component {
+            Component::TextDisplay(text) => totals.1 += text.content.chars().count(),
+            Component::Section(section) => {
+                let texts = section.components.len();
+                if texts == 0 || texts > MAX_SECTION_TEXTS {
+                    return Err(LayoutError::SectionTexts(texts));
+                }
+                validate(&section.components, totals)?;
+                validate(std::slice::from_ref(&section.accessory), totals)?;
+            }
+            Component::MediaGallery(gallery) => {
+                let items = gallery.items.len();
+                if items == 0 || items > MAX_GALLERY_ITEMS {
+                    return Err(LayoutError::GalleryItems(items));
+                }
+            }
+            Component::Container(container) => {
+                if container.components.is_empty() {
+                    return Err(LayoutError::EmptyComponent);
+                }
+                validate(&container.components, totals)?;
+            }
+            Component::ActionRow(row) => {
+                if row.components.is_empty() {
+                    return Err(LayoutError::EmptyComponent);
+                }
+                validate(&row.components, totals)?;
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
@@ -1,15 +1,62 @@
+mod compat;
 mod parse_impl;
 
 pub mod argument;
+pub mod attachment;
+pub mod audit;
 pub mod builder;
+pub mod button;
+pub mod channel;
 pub mod command;
+pub mod component;
+pub mod concurrency;
 pub mod context;
+pub mod cooldown;
+pub mod dispatch;
+pub mod docs;
+pub mod dynamic;
+pub mod entry_point;
+pub mod error;
+pub mod event;
 pub mod framework;
+#[cfg(all(feature = "gateway", not(feature = "rc")))]
+mod gateway;
 pub mod group;
+pub mod help;
 pub mod hook;
+pub mod invite;
 pub mod iter;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod layout;
+pub mod locale;
+pub mod localization;
+pub mod mentionable;
+pub mod message;
+pub mod metadata;
 pub mod parse;
+pub mod payload;
+#[cfg(feature = "wasm")]
+pub mod plugin;
 pub mod range;
+pub mod registration;
+pub mod report;
+pub mod response;
+pub mod retry;
+pub mod route;
+#[cfg(feature = "rhai")]
+pub mod script;
+pub mod select;
+pub mod shard;
+#[cfg(feature = "opentelemetry")]
+mod telemetry;
+pub mod template;
+pub mod thread;
+#[cfg(feature = "timezone")]
+pub mod timezone;
+pub mod tree;
+#[cfg(feature = "semver")]
+mod version;
 mod waiter;
 
 pub use zephyrus_macros as macros;
@@ -20,51 +67,103 @@ type BoxFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> +
 pub mod prelude {
     pub use crate::{
         argument::ArgumentLimits,
-        builder::{FrameworkBuilder, WrappedClient},
+        audit::{AuditLog, AuditSink},
+        builder::{FrameworkBuilder, Target, WrappedClient},
+        channel::{CategoryId, TextChannelId, ThreadId, VoiceChannelId},
         command::CommandResult,
-        context::{AutocompleteContext, Focused, SlashContext},
+        component::ComponentsBuilder,
+        layout::LayoutBuilder,
+        concurrency::{ConcurrencyLimit, ConcurrencyScope, GlobalConcurrencyLimit, OverflowPolicy},
+        cooldown::{Cooldown, CooldownStore, InMemoryCooldownStore},
+        context::{
+            AutocompleteContext, ContextError, Focused, OwnedSlashContext, ResponseState,
+            SlashContext,
+        },
+        docs::CommandDocs,
+        metadata::FrameworkMetadata,
+        dynamic::{Arguments, CommandBuilder, DynamicArgument},
+        entry_point::EntryPoint,
+        error::FrameworkError,
+        event::FrameworkEvent,
         framework::Framework,
+        hook::{CommandOutcome, HookVerdict, Intercept},
+        help::HelpCommand,
+        invite::InviteCode,
+        locale::Locale,
+        mentionable::Mentionable,
         parse::{Parse, ParseError},
-        range::Range,
+        payload::CommandPayload,
+        range::{BoundedF64, Range, RangeArg},
+        registration::{RegistrationReport, RegistrationStatus},
+        report::ErrorReport,
+        response::ResponseBuilder,
+        retry::RetryPolicy,
+        select::SelectOptions,
+        shard::ShardInfo,
+        template::{DynamicCommandSource, FileSource, PollingSource, TemplateCommand},
+        waiter::{
+            ComponentInteraction, ComponentWaiter, InteractionWaiter, WaiterError, WaiterLimits,
+            WaiterOverflow,
+        },
     };
+    #[cfg(feature = "attachment-download")]
+    pub use crate::attachment::{Download, DownloadLimits};
     pub use async_trait::async_trait;
     pub use zephyrus_macros::*;
 }
 
 pub mod twilight_exports {
     pub use twilight_http::{
-        client::{Client, InteractionClient},
-        request::application::interaction::UpdateResponse,
+        client::{Client, ClientBuilder, InteractionClient},
+        request::application::interaction::{CreateFollowup, UpdateResponse},
     };
     pub use twilight_model::{
         application::{
             command::{
-                BaseCommandOptionData, ChannelCommandOptionData, ChoiceCommandOptionData, Command,
-                CommandOption, CommandOptionChoice, CommandOptionType, NumberCommandOptionData,
-                OptionsCommandOptionData,
+                Command, CommandOption, CommandOptionChoice, CommandOptionChoiceValue,
+                CommandOptionType, CommandType,
             },
             interaction::{
-                application_command::{
-                    CommandData, CommandDataOption, CommandOptionValue,
-                },
+                application_command::{CommandData, CommandDataOption, CommandOptionValue},
                 message_component::MessageComponentInteractionData,
                 Interaction,
+                InteractionDataResolved,
+                InteractionMember,
                 InteractionType,
                 InteractionData
             },
+            monetization::Entitlement,
+        },
+        channel::{
+            message::{
+                component::{
+                    ActionRow, Button, ButtonStyle, Component, ComponentType, Container,
+                    MediaGallery, MediaGalleryItem, Section, SelectMenu, SelectMenuOption,
+                    SelectMenuType, Separator, SeparatorSpacingSize, TextDisplay, Thumbnail,
+                    UnfurledMediaItem,
+                },
+                embed::{Embed, EmbedField, EmbedFooter},
+                AllowedMentions, EmojiReactionType, MessageFlags,
+            },
+            Channel, ChannelType, Message,
         },
-        channel::Message,
-        gateway::payload::incoming::InteractionCreate,
-        guild::Permissions,
-        http::interaction::{
-            InteractionResponse, InteractionResponseData, InteractionResponseType,
+        gateway::{payload::incoming::InteractionCreate, Intents},
+        poll::{Poll, PollAnswer, PollLayoutType, PollMedia},
+        guild::{invite::Invite, PartialMember, Permissions, Role},
+        http::{
+            attachment::Attachment,
+            interaction::{
+                InteractionResponse, InteractionResponseData, InteractionResponseType,
+            },
         },
         id::{
             marker::{
-                ApplicationMarker, ChannelMarker, GenericMarker, GuildMarker, MessageMarker,
-                RoleMarker, UserMarker,
+                ApplicationMarker, ChannelMarker, GenericMarker, GuildMarker, InteractionMarker,
+                MessageMarker, RoleMarker, SkuMarker, UserMarker,
             },
             Id,
         },
+        user::User,
+        util::Timestamp,
     };
 }
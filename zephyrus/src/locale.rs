@@ -0,0 +1,156 @@
+use crate::parse_impl::error;
+use crate::prelude::*;
+use crate::twilight_exports::*;
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+/// The maximum number of choices discord allows an option to have.
+const MAX_CHOICES: usize = 25;
+
+macro_rules! locales {
+    ($($variant:ident => $code:literal, $name:literal;)*) => {
+        /// A locale supported by discord, usable as a command argument for per-user or per-guild
+        /// language settings.
+        ///
+        /// The argument is a string option accepting either the code or the english name of the
+        /// locale. Discord supports more locales than the choices an option can have, so use
+        /// [suggestions](Self::suggestions) from an autocomplete function to offer them.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum Locale {
+            $(
+                #[doc = $name]
+                $variant,
+            )*
+        }
+
+        impl Locale {
+            /// Every locale supported by discord.
+            pub const ALL: &'static [Locale] = &[$(Self::$variant),*];
+
+            /// Gets the code discord uses for the locale, such as `en-US`.
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $code,)*
+                }
+            }
+
+            /// Gets the english name of the locale, such as `English, US`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+locales! {
+    Indonesian => "id", "Indonesian";
+    Danish => "da", "Danish";
+    German => "de", "German";
+    EnglishUk => "en-GB", "English, UK";
+    EnglishUs => "en-US", "English, US";
+    Spanish => "es-ES", "Spanish";
+    SpanishLatam => "es-419", "Spanish, LATAM";
+    French => "fr", "French";
+    Croatian => "hr", "Croatian";
+    Italian => "it", "Italian";
+    Lithuanian => "lt", "Lithuanian";
+    Hungarian => "hu", "Hungarian";
+    Dutch => "nl", "Dutch";
+    Norwegian => "no", "Norwegian";
+    Polish => "pl", "Polish";
+    PortugueseBrazil => "pt-BR", "Portuguese, Brazilian";
+    Romanian => "ro", "Romanian";
+    Finnish => "fi", "Finnish";
+    Swedish => "sv-SE", "Swedish";
+    Vietnamese => "vi", "Vietnamese";
+    Turkish => "tr", "Turkish";
+    Czech => "cs", "Czech";
+    Greek => "el", "Greek";
+    Bulgarian => "bg", "Bulgarian";
+    Russian => "ru", "Russian";
+    Ukrainian => "uk", "Ukrainian";
+    Hindi => "hi", "Hindi";
+    Thai => "th", "Thai";
+    ChineseChina => "zh-CN", "Chinese, China";
+    Japanese => "ja", "Japanese";
+    ChineseTaiwan => "zh-TW", "Chinese, Taiwan";
+    Korean => "ko", "Korean";
+}
+
+impl Locale {
+    /// Gets the locale of the user who invoked the given interaction.
+    pub fn of_user(interaction: &Interaction) -> Option<Self> {
+        interaction.locale.as_deref()?.parse().ok()
+    }
+
+    /// Gets the locale of the guild the given interaction was invoked in.
+    pub fn of_guild(interaction: &Interaction) -> Option<Self> {
+        interaction.guild_locale.as_deref()?.parse().ok()
+    }
+
+    /// Creates the autocomplete response suggesting the locales whose code or name contain the
+    /// given input, for use in an [autocomplete](crate::context::AutocompleteContext) function.
+    pub fn suggestions(input: &str) -> InteractionResponseData {
+        let input = input.to_lowercase();
+        let choices = Self::ALL
+            .iter()
+            .filter(|locale| {
+                locale.code().to_lowercase().contains(&input)
+                    || locale.name().to_lowercase().contains(&input)
+            })
+            .take(MAX_CHOICES)
+            .map(|locale| CommandOptionChoice {
+                name: locale.name().to_string(),
+                name_localizations: None,
+                value: CommandOptionChoiceValue::String(locale.code().to_string()),
+            })
+            .collect();
+
+        InteractionResponseData {
+            choices: Some(choices),
+            ..Default::default()
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = ();
+
+    /// Parses the code or the english name of a locale, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(s) || locale.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(())
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.code())
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for Locale {
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let input = String::parse(http_client, data, value).await?;
+        input
+            .parse()
+            .map_err(|_| error("Locale", true, &format!("{} is not a supported locale", input)))
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    framework::Framework,
+    twilight_exports::{CommandOption, CommandOptionChoiceValue, Interaction},
+};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+/// The locale used when a string isn't translated to the locale of an interaction, unless
+/// another one is set using [fallback_locale](crate::builder::FrameworkBuilder::fallback_locale).
+pub const DEFAULT_FALLBACK_LOCALE: &str = "en-US";
+
+/// A source of the translated strings used to localize responses, see
+/// [SlashContext::t](crate::context::SlashContext::t).
+pub trait Localizer: Send + Sync {
+    /// Gets the string with the given key translated to the given locale, or `None` if the
+    /// string isn't translated to it.
+    fn get(&self, locale: &str, key: &str) -> Option<String>;
+}
+
+/// A map of locales to the strings translated to them, mapped by their keys.
+impl Localizer for HashMap<String, HashMap<String, String>> {
+    fn get(&self, locale: &str, key: &str) -> Option<String> {
+        self.get(locale).and_then(|strings| strings.get(key)).cloned()
+    }
+}
+
+/// The [localizer](Localizer) of the framework alongside the locale used as the last fallback.
+pub(crate) struct Translator {
+    pub(crate) localizer: Box<dyn Localizer>,
+    pub(crate) fallback_locale: String,
+}
+
+impl Translator {
+    /// Translates the string with the given key for the given interaction, replacing every
+    /// `{name}` placeholder by the value given to it.
+    ///
+    /// The locale of the user is tried first, then its language without region, the locale of
+    /// the guild, its language and finally the fallback locale. The key itself is returned if
+    /// none of them has the string.
+    pub(crate) fn translate(
+        &self,
+        interaction: &Interaction,
+        key: &str,
+        args: &[(&str, &dyn Display)],
+    ) -> String {
+        let locales = [interaction.locale.as_deref(), interaction.guild_locale.as_deref()];
+        let chain = locales
+            .iter()
+            .flatten()
+            .flat_map(|locale| {
+                let language = locale.split('-').next().filter(|language| language != locale);
+                std::iter::once(*locale).chain(language)
+            })
+            .chain(std::iter::once(self.fallback_locale.as_str()));
+
+        for locale in chain {
+            if let Some(string) = self.localizer.get(locale, key) {
+                return fill_placeholders(string, args);
+            }
+        }
+
+        fill_placeholders(key.to_string(), args)
+    }
+}
+
+/// Replaces every `{name}` placeholder of the given string by the value given to it.
+pub(crate) fn fill_placeholders(
+    mut string: String,
+    args: &[(&str, &dyn Display)],
+) -> String {
+    for (name, value) in args {
+        let placeholder = format!("{{{}}}", name);
+        if string.contains(&placeholder) {
+            string = string.replace(&placeholder, &value.to_string());
+        }
+    }
+
+    string
+}
+
+/// Translates the string with the given key for the interaction of the given context, replacing
+/// the placeholders by the given values, see [SlashContext::t](crate::context::SlashContext::t).
+///
+/// ```ignore
+/// let response = t!(ctx, "greeting", user = ctx.author()?.name);
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($ctx:expr, $key:expr $(,)?) => {
+        $ctx.t($key, &[])
+    };
+    ($ctx:expr, $key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $ctx.t($key, &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+])
+    };
+}
+
+/// A template of every localizable string of the commands of a framework, such as command
+/// names, descriptions, argument names and choice labels, mapped by a key unique to each string.
+///
+/// Keys are made out of the path to the string separated by dots, like `ban.name`,
+/// `ban.user.description` or `ban.reason.choices.spam`. The template can be exported as JSON or
+/// as a Fluent resource, so translators can work from generated files which never drift from
+/// the code.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct LocalizationTemplate {
+    entries: BTreeMap<String, String>,
+}
+
+impl LocalizationTemplate {
+    /// Gets the strings of the template mapped by their keys, sorted by key.
+    pub fn entries(&self) -> &BTreeMap<String, String> {
+        &self.entries
+    }
+
+    /// Gets the string with the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Exports the template as a JSON object mapping every key to its string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.entries).unwrap_or_default()
+    }
+
+    /// Exports the template as a Fluent resource, the dots of the keys being replaced by dashes
+    /// and any other character not allowed by Fluent by underscores.
+    pub fn to_fluent(&self) -> String {
+        let mut resource = String::new();
+
+        for (key, value) in &self.entries {
+            let key = key
+                .chars()
+                .map(|c| match c {
+                    '.' => '-',
+                    c if c.is_ascii_alphanumeric() || c == '-' || c == '_' => c,
+                    _ => '_',
+                })
+                .collect::<String>();
+
+            resource.push_str(&key);
+            resource.push_str(" = ");
+            resource.push_str(&value.replace('\n', "\n    "));
+            resource.push('\n');
+        }
+
+        resource
+    }
+
+    /// Adds the given string with the given key.
+    fn insert(&mut self, key: String, value: &str) {
+        self.entries.insert(key, value.to_string());
+    }
+
+    /// Adds the strings of the given options, including nested subcommands and choices, whose
+    /// keys start with the given prefix.
+    fn add_options(&mut self, prefix: &str, options: &[CommandOption]) {
+        for option in options {
+            let prefix = format!("{}.{}", prefix, option.name);
+            self.insert(format!("{}.name", prefix), &option.name);
+            self.insert(format!("{}.description", prefix), &option.description);
+
+            for choice in option.choices.iter().flatten() {
+                let value = match &choice.value {
+                    CommandOptionChoiceValue::String(value) => value.clone(),
+                    CommandOptionChoiceValue::Integer(value) => value.to_string(),
+                    CommandOptionChoiceValue::Number(value) => value.to_string(),
+                };
+                self.insert(format!("{}.choices.{}", prefix, value), &choice.name);
+            }
+
+            if let Some(options) = &option.options {
+                self.add_options(&prefix, options);
+            }
+        }
+    }
+}
+
+impl<D, E> Framework<D, E> {
+    /// Walks the slash commands of the framework, including groups and the help command,
+    /// producing a [template](LocalizationTemplate) of every localizable string.
+    pub fn extract_localization_template(&self) -> LocalizationTemplate {
+        let mut template = LocalizationTemplate::default();
+
+        for payload in self.command_payloads() {
+            template.insert(format!("{}.name", payload.name), &payload.name);
+            template.insert(format!("{}.description", payload.name), &payload.description);
+            template.add_options(&payload.name, &payload.options);
+        }
+
+        template
+    }
+}
@@ -0,0 +1,81 @@
+use crate::parse_impl::error;
+use crate::prelude::*;
+use crate::twilight_exports::*;
+
+/// A user or a role given as input to a [mentionable](CommandOptionType::Mentionable) argument,
+/// taken out of the resolved data of the interaction.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Mentionable {
+    /// A user, alongside its member if the command was used in a guild.
+    User(User, Option<InteractionMember>),
+    /// A role.
+    Role(Role),
+}
+
+impl Mentionable {
+    /// Gets the id of the mentioned user or role.
+    pub fn id(&self) -> Id<GenericMarker> {
+        match self {
+            Self::User(user, _) => user.id.cast(),
+            Self::Role(role) => role.id.cast(),
+        }
+    }
+
+    /// Gets the mentioned user, if it is one.
+    pub fn user(&self) -> Option<&User> {
+        match self {
+            Self::User(user, _) => Some(user),
+            Self::Role(_) => None,
+        }
+    }
+
+    /// Gets the mentioned role, if it is one.
+    pub fn role(&self) -> Option<&Role> {
+        match self {
+            Self::User(..) => None,
+            Self::Role(role) => Some(role),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for Mentionable {
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_resolved(http_client, data, value, None).await
+    }
+
+    async fn parse_resolved(
+        _: &WrappedClient,
+        _: &T,
+        value: Option<&CommandOptionValue>,
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Result<Self, ParseError> {
+        let id = match value {
+            Some(CommandOptionValue::Mentionable(id)) => *id,
+            _ => return Err(error("Mentionable", true, "Mentionable expected")),
+        };
+        let resolved = match resolved {
+            Some(resolved) => resolved,
+            None => return Err(error("Mentionable", true, "Resolved data expected")),
+        };
+
+        if let Some(user) = resolved.users.get(&id.cast()) {
+            let member = resolved.members.get(&id.cast()).cloned();
+            return Ok(Self::User(user.clone(), member));
+        }
+
+        match resolved.roles.get(&id.cast()) {
+            Some(role) => Ok(Self::Role(role.clone())),
+            None => Err(error("Mentionable", true, "Mentioned user or role not resolved")),
+        }
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::Mentionable
+    }
+}
@@ -1,5 +1,6 @@
 use crate::twilight_exports::Message as TwilightMessage;
 use crate::{context::SlashContext};
+use std::error::Error;
 
 /// A wrapper around twilight's [message](TwilightMessage)
 /// adding a few convenience methods.
@@ -29,4 +30,33 @@ impl<'a, T> Message<'a, T> {
             context,
         }
     }
+
+    /// Gets the inner twilight message.
+    pub fn into_inner(self) -> TwilightMessage {
+        self.inner
+    }
+
+    /// Replaces the content of the message with the given one.
+    pub async fn edit(&mut self, content: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.inner = self
+            .context
+            .http_client()
+            .update_message(self.inner.channel_id, self.inner.id)
+            .content(Some(content))
+            .await?
+            .model()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Deletes the message.
+    pub async fn delete(self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.context
+            .http_client()
+            .delete_message(self.inner.channel_id, self.inner.id)
+            .await?;
+
+        Ok(())
+    }
 }
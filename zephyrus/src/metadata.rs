@@ -0,0 +1,192 @@
+use crate::{
+    argument::{ArgumentLimits, CommandArgument},
+    command::{Command, CommandMap, CommandMeta},
+    concurrency::{ConcurrencyLimit, ConcurrencyScope},
+    framework::Framework,
+    group::{sorted, CommandGroup, GroupParent, ParentType},
+    twilight_exports::{ChannelType, CommandOptionChoice, CommandOptionType, Id, Permissions, SkuMarker},
+};
+use serde::Serialize;
+
+/// A serializable view of the commands and groups of a [framework](Framework), allowing
+/// dashboards and external tools to consume the command tree, obtained using
+/// [metadata](Framework::metadata).
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameworkMetadata {
+    /// The top level commands, sorted by name.
+    pub commands: Vec<CommandMetadata>,
+    /// The top level groups, sorted by name.
+    pub groups: Vec<GroupMetadata>,
+}
+
+/// A serializable view of a [command](Command).
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandMetadata {
+    /// The name of the command.
+    pub name: String,
+    /// The description of the command.
+    pub description: String,
+    /// The arguments of the command.
+    pub arguments: Vec<ArgumentMetadata>,
+    /// The permissions required to use the command.
+    pub required_permissions: Option<Permissions>,
+    /// The category of the command.
+    pub category: Option<&'static str>,
+    /// The key-value pairs attached to the command.
+    pub meta: CommandMeta,
+    /// The maximum number of concurrent executions of the command.
+    pub concurrency: Option<ConcurrencyLimit>,
+    /// The cooldown of the command.
+    pub cooldown: Option<CooldownMetadata>,
+    /// Whether the responses of the command are only seen by the user who invoked it.
+    pub ephemeral: bool,
+    /// The sku the user invoking the command must have an entitlement to.
+    pub premium_sku: Option<Id<SkuMarker>>,
+}
+
+/// A serializable view of a [cooldown](crate::cooldown::Cooldown).
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CooldownMetadata {
+    /// The time the command can't be used again, in seconds.
+    pub seconds: f64,
+    /// The scope the cooldown is applied to.
+    pub scope: ConcurrencyScope,
+}
+
+/// A serializable view of a [command argument](CommandArgument).
+#[derive(Debug, Clone, Serialize)]
+pub struct ArgumentMetadata {
+    /// The name of the argument.
+    pub name: String,
+    /// The description of the argument.
+    pub description: String,
+    /// Whether the argument is required.
+    pub required: bool,
+    /// The type of the argument.
+    pub kind: CommandOptionType,
+    /// The choices of the argument.
+    pub choices: Option<Vec<CommandOptionChoice>>,
+    /// The limits of the argument.
+    pub limits: Option<ArgumentLimits>,
+    /// The types of the channels the argument accepts.
+    pub channel_types: Option<Vec<ChannelType>>,
+    /// Whether the argument is autocompleted.
+    pub autocomplete: bool,
+    /// Whether the value of the argument is sensitive.
+    pub sensitive: bool,
+}
+
+/// A serializable view of a [group parent](GroupParent).
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupMetadata {
+    /// The name of the group.
+    pub name: String,
+    /// The description of the group.
+    pub description: String,
+    /// The permissions required to use the commands of the group.
+    pub required_permissions: Option<Permissions>,
+    /// The category of the group.
+    pub category: Option<&'static str>,
+    /// The subcommands of the group, sorted by name.
+    pub subcommands: Vec<CommandMetadata>,
+    /// The subcommand groups of the group, sorted by name.
+    pub subgroups: Vec<SubgroupMetadata>,
+}
+
+/// A serializable view of a [subcommand group](CommandGroup).
+#[derive(Debug, Clone, Serialize)]
+pub struct SubgroupMetadata {
+    /// The name of the subcommand group.
+    pub name: String,
+    /// The description of the subcommand group.
+    pub description: String,
+    /// The subcommands of the subcommand group, sorted by name.
+    pub subcommands: Vec<CommandMetadata>,
+}
+
+impl<D> CommandArgument<D> {
+    /// Creates a serializable view of this argument.
+    pub fn metadata(&self) -> ArgumentMetadata {
+        ArgumentMetadata {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            required: self.required,
+            kind: self.kind,
+            choices: self.choices.clone(),
+            limits: self.limits,
+            channel_types: self.channel_types.clone(),
+            autocomplete: self.autocomplete.is_some(),
+            sensitive: self.sensitive,
+        }
+    }
+}
+
+impl<D, E> Command<D, E> {
+    /// Creates a serializable view of this command.
+    pub fn metadata(&self) -> CommandMetadata {
+        CommandMetadata {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            arguments: self.arguments.iter().map(CommandArgument::metadata).collect(),
+            required_permissions: self.required_permissions,
+            category: self.category,
+            meta: self.meta.clone(),
+            concurrency: self.concurrency,
+            cooldown: self.cooldown.map(|cooldown| CooldownMetadata {
+                seconds: cooldown.duration.as_secs_f64(),
+                scope: cooldown.scope,
+            }),
+            ephemeral: self.ephemeral,
+            premium_sku: self.premium_sku,
+        }
+    }
+}
+
+impl<D, E> CommandGroup<D, E> {
+    /// Creates a serializable view of this subcommand group.
+    pub fn metadata(&self) -> SubgroupMetadata {
+        SubgroupMetadata {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            subcommands: commands_metadata(&self.subcommands),
+        }
+    }
+}
+
+impl<D, E> GroupParent<D, E> {
+    /// Creates a serializable view of this group.
+    pub fn metadata(&self) -> GroupMetadata {
+        let (subcommands, subgroups) = match &self.kind {
+            ParentType::Simple(commands) => (commands_metadata(commands), Vec::new()),
+            ParentType::Group(groups) => (
+                Vec::new(),
+                sorted(groups).into_values().map(CommandGroup::metadata).collect(),
+            ),
+        };
+
+        GroupMetadata {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            required_permissions: self.required_permissions,
+            category: self.category,
+            subcommands,
+            subgroups,
+        }
+    }
+}
+
+/// Creates the serializable views of the given commands, sorted by name.
+fn commands_metadata<D, E>(commands: &CommandMap<D, E>) -> Vec<CommandMetadata> {
+    sorted(commands).into_values().map(Command::metadata).collect()
+}
+
+impl<D, E> Framework<D, E> {
+    /// Creates a serializable view of every command and group of the framework, such as to
+    /// expose the command tree to a dashboard over an API.
+    pub fn metadata(&self) -> FrameworkMetadata {
+        FrameworkMetadata {
+            commands: commands_metadata(&self.commands),
+            groups: sorted(&self.groups).into_values().map(GroupParent::metadata).collect(),
+        }
+    }
+}
@@ -1,10 +1,10 @@
-use crate::{argument::ArgumentLimits, builder::WrappedClient, twilight_exports::*};
+use crate::{argument::ArgumentLimits, audit::REDACTED, builder::WrappedClient, twilight_exports::*};
 use async_trait::async_trait;
 use std::error::Error;
 
 /// The core trait of this framework, it is used to parse all command arguments
 #[async_trait]
-pub trait Parse<T: Send + Sync>: Sized {
+pub trait Parse<T: Send + Sync>: Sized + Send {
     /// Parses the option into the argument.
     async fn parse(
         _http_client: &WrappedClient,
@@ -12,6 +12,20 @@ pub trait Parse<T: Send + Sync>: Sized {
         _value: Option<&CommandOptionValue>,
     ) -> Result<Self, ParseError>;
 
+    /// Parses the option into the argument, having access to the
+    /// [resolved data](InteractionDataResolved) of the interaction, which contains the users,
+    /// members, roles and channels given as input.
+    ///
+    /// By default this ignores the resolved data and calls [parse](Self::parse).
+    async fn parse_resolved(
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+        _resolved: Option<&InteractionDataResolved>,
+    ) -> Result<Self, ParseError> {
+        Self::parse(http_client, data, value).await
+    }
+
     /// Returns the option type this argument has.
     fn kind() -> CommandOptionType;
 
@@ -26,9 +40,24 @@ pub trait Parse<T: Send + Sync>: Sized {
         None
     }
 
+    /// Whether [parse](Self::parse) already rejects the inputs which are not one of the
+    /// [choices](Self::choices), allowing the framework to skip building them on every parse to
+    /// check the input, by default is false.
+    fn validates_choices() -> bool {
+        false
+    }
+
+    /// Sets the constraints the user input must satisfy, which are both sent to discord and
+    /// checked when parsing the argument.
     fn limits() -> Option<ArgumentLimits> {
         None
     }
+
+    /// Sets the channel types allowed to be used as input, only valid for
+    /// [channel](CommandOptionType::Channel) arguments.
+    fn channel_types() -> Option<Vec<ChannelType>> {
+        None
+    }
 }
 
 /// The errors which can be returned from [Parse](self::Parse) [parse](self::Parse::parse) function.
@@ -42,6 +71,45 @@ pub enum ParseError {
         error: String
     },
     Other(Box<dyn Error + Send + Sync>),
+    /// The argument with the given name failed to parse and has a custom message, set using the
+    /// `#[error_message]` attribute, which is shown instead of the original error.
+    Message {
+        argument_name: String,
+        message: String,
+        source: Box<ParseError>
+    },
+}
+
+impl ParseError {
+    /// Replaces the message of this error with the given one, keeping this error as its source.
+    pub fn with_message(self, argument_name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Message {
+            argument_name: argument_name.into(),
+            message: message.into(),
+            source: Box::new(self)
+        }
+    }
+
+    /// Replaces the details of this error, which may contain the value of the argument, with
+    /// [REDACTED](REDACTED), used for [sensitive](crate::argument::CommandArgument::sensitive)
+    /// arguments.
+    pub fn redact(self) -> Self {
+        match self {
+            Self::Parsing { argument_name, required, type_, .. } => Self::Parsing {
+                argument_name,
+                required,
+                type_,
+                error: REDACTED.to_string()
+            },
+            Self::Other(_) => Self::Other(REDACTED.into()),
+            Self::Message { argument_name, message, source } => Self::Message {
+                argument_name,
+                message,
+                source: Box::new(source.redact())
+            },
+            why @ Self::StructureMismatch(_) => why,
+        }
+    }
 }
 
 impl std::fmt::Display for ParseError {
@@ -58,10 +126,19 @@ impl std::fmt::Display for ParseError {
                 }, type_, error)
             }
             Self::Other(why) => write!(f, "Other: {}", why),
+            Self::Message { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Message { source, .. } => Some(source.as_ref()),
+            _ => None,
         }
     }
 }
-impl Error for ParseError {}
 
 impl From<Box<dyn Error + Send + Sync>> for ParseError {
     fn from(e: Box<dyn Error + Send + Sync>) -> Self {
@@ -72,7 +72,8 @@ impl<T: Send + Sync> Parse<T> for u64 {
         use twilight_model::application::command::CommandOptionValue;
         Some(ArgumentLimits {
             min: Some(CommandOptionValue::Integer(0)),
-            max: None
+            max: None,
+            ..Default::default()
         })
     }
 }
@@ -98,7 +99,8 @@ impl<T: Send + Sync> Parse<T> for f64 {
         use twilight_model::application::command::CommandOptionValue;
         Some(ArgumentLimits {
             min: Some(CommandOptionValue::Number(f64::MIN)),
-            max: Some(CommandOptionValue::Number(f64::MAX))
+            max: Some(CommandOptionValue::Number(f64::MAX)),
+            ..Default::default()
         })
     }
 }
@@ -127,7 +129,8 @@ impl<T: Send + Sync> Parse<T> for f32 {
         use twilight_model::application::command::CommandOptionValue;
         Some(ArgumentLimits {
             min: Some(CommandOptionValue::Number(f32::MIN as f64)),
-            max: Some(CommandOptionValue::Number(f32::MAX as f64))
+            max: Some(CommandOptionValue::Number(f32::MAX as f64)),
+            ..Default::default()
         })
     }
 }
@@ -233,20 +236,30 @@ impl<T: Parse<E>, E: Send + Sync> Parse<E> for Option<T> {
         data: &E,
         value: Option<&CommandOptionValue>,
     ) -> Result<Self, ParseError> {
-        match T::parse(http_client, data, value).await {
-            Ok(parsed) => Ok(Some(parsed)),
-            Err(mut why) => {
-                if value.is_some() {
-                    if let ParseError::Parsing {required, ..} = &mut why {
-                        *required = false;
-                    }
+        Self::parse_resolved(http_client, data, value, None).await
+    }
 
-                    Err(why)
-                } else {
-                    Ok(None)
-                }
-            }
+    async fn parse_resolved(
+        http_client: &WrappedClient,
+        data: &E,
+        value: Option<&CommandOptionValue>,
+        resolved: Option<&InteractionDataResolved>,
+    ) -> Result<Self, ParseError> {
+        // Only a missing option is `None`, a provided value which fails to parse is an error
+        if value.is_none() {
+            return Ok(None);
         }
+
+        T::parse_resolved(http_client, data, value, resolved)
+            .await
+            .map(Some)
+            .map_err(|mut why| {
+                if let ParseError::Parsing { required, .. } = &mut why {
+                    *required = false;
+                }
+
+                why
+            })
     }
 
     fn kind() -> CommandOptionType {
@@ -264,22 +277,35 @@ impl<T: Parse<E>, E: Send + Sync> Parse<E> for Option<T> {
     fn limits() -> Option<ArgumentLimits> {
         T::limits()
     }
+
+    fn channel_types() -> Option<Vec<ChannelType>> {
+        T::channel_types()
+    }
 }
 
 #[async_trait]
 impl<T, E, C> Parse<C> for Result<T, E>
 where
     T: Parse<C>,
-    E: From<ParseError>,
+    E: From<ParseError> + Send,
     C: Send + Sync,
 {
     async fn parse(
         http_client: &WrappedClient,
         data: &C,
         value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_resolved(http_client, data, value, None).await
+    }
+
+    async fn parse_resolved(
+        http_client: &WrappedClient,
+        data: &C,
+        value: Option<&CommandOptionValue>,
+        resolved: Option<&InteractionDataResolved>,
     ) -> Result<Self, ParseError> {
         // as we want to return the error if occurs, we'll map the error and always return Ok
-        Ok(T::parse(http_client, data, value).await.map_err(From::from))
+        Ok(T::parse_resolved(http_client, data, value, resolved).await.map_err(From::from))
     }
 
     fn kind() -> CommandOptionType {
@@ -297,6 +323,10 @@ where
     fn limits() -> Option<ArgumentLimits> {
         T::limits()
     }
+
+    fn channel_types() -> Option<Vec<ChannelType>> {
+        T::channel_types()
+    }
 }
 
 macro_rules! impl_derived_parse {
@@ -356,7 +386,8 @@ macro_rules! impl_derived_parse {
                             } else {
                                 <$derived>::MAX as i64
                             }
-                        }))
+                        })),
+                        ..Default::default()
                     })
                 }
             }
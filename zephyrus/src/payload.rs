@@ -0,0 +1,229 @@
+use crate::{
+    argument::CommandArgument,
+    command::Command,
+    compat,
+    entry_point::EntryPoint,
+    framework::Framework,
+    group::{GroupParent, ParentType},
+    help::HelpCommand,
+    template::TemplateCommand,
+    twilight_exports::{
+        Command as TwilightCommand, CommandOption, CommandOptionType, CommandType, Permissions,
+    },
+};
+use serde::Serialize;
+use tracing::debug;
+
+/// The payload used to register a top level slash command in discord, made out of a
+/// [command](Command), a [group](GroupParent) or the [help command](HelpCommand).
+///
+/// This is used by the framework to register its commands, and can be used to register them
+/// using custom registration flows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommandPayload {
+    /// The name of the command.
+    pub name: String,
+    /// The description of the command.
+    pub description: String,
+    /// The options of the command, including subcommands and subcommand groups.
+    pub options: Vec<CommandOption>,
+    /// The permissions required by default to use the command.
+    pub default_member_permissions: Option<Permissions>,
+}
+
+impl CommandPayload {
+    /// Creates the payload of the given command.
+    pub fn from_command<D, E>(command: &Command<D, E>) -> Self {
+        debug!("Creating payload of command {}", command.name);
+
+        Self {
+            name: command.name.to_string(),
+            description: command.description.to_string(),
+            options: arg_options(&command.arguments),
+            default_member_permissions: command.required_permissions,
+        }
+    }
+
+    /// Creates the payload of the given group, including all of its children.
+    pub fn from_group<D, E>(parent: &GroupParent<D, E>) -> Self {
+        debug!("Creating payload of group {}", parent.name);
+
+        Self {
+            name: parent.name.to_string(),
+            description: parent.description.to_string(),
+            options: group_options(parent),
+            default_member_permissions: parent.required_permissions,
+        }
+    }
+
+    /// Creates the payload of the given help command.
+    pub fn from_help(help: &HelpCommand) -> Self {
+        Self {
+            name: help.name.to_string(),
+            description: help.description.to_string(),
+            options: help.options(),
+            default_member_permissions: None,
+        }
+    }
+
+    /// Creates the payload of a command already registered in discord.
+    pub fn from_remote(command: &TwilightCommand) -> Self {
+        Self {
+            name: command.name.clone(),
+            description: command.description.clone(),
+            options: command.options.clone(),
+            default_member_permissions: command.default_member_permissions,
+        }
+    }
+
+    /// Whether the given remote command is registered as described by this payload.
+    pub fn matches(&self, remote: &TwilightCommand) -> bool {
+        remote.kind == CommandType::ChatInput
+            && fingerprint(std::slice::from_ref(self)) == fingerprint(&[Self::from_remote(remote)])
+    }
+
+    /// Removes the differences between the payloads created by the framework and the ones
+    /// returned by discord which don't change the meaning of the command.
+    fn normalize(&mut self) {
+        normalize_options(&mut self.options);
+    }
+}
+
+/// Computes a stable hash of the given payloads, which doesn't depend on their order.
+///
+/// Two sets of payloads describing the same commands have the same fingerprint, so it can be
+/// used to detect whether the registered commands differ from the local ones.
+pub fn fingerprint(payloads: &[CommandPayload]) -> u64 {
+    let mut payloads = payloads.to_vec();
+    payloads.iter_mut().for_each(CommandPayload::normalize);
+    payloads.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // Converting into a value first sorts the keys of the maps, such as the localizations.
+    let bytes = serde_json::to_value(&payloads)
+        .and_then(|value| serde_json::to_vec(&value))
+        .unwrap_or_default();
+
+    // FNV-1a, used instead of the std hasher because its output is not guaranteed to be stable.
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl<D, E> Framework<D, E> {
+    /// Creates the payloads of all the slash commands of the framework, including groups and the
+    /// help command.
+    pub fn command_payloads(&self) -> Vec<CommandPayload> {
+        let mut payloads = Vec::with_capacity(self.commands.len() + self.groups.len() + 1);
+
+        payloads.extend(self.commands.values().map(CommandPayload::from_command));
+        payloads.extend(self.groups.values().map(CommandPayload::from_group));
+        payloads.extend(self.help.as_ref().map(CommandPayload::from_help));
+
+        if let Some(dynamic) = &self.dynamic_commands {
+            payloads.extend(
+                dynamic
+                    .commands()
+                    .iter()
+                    .filter(|command| !self.is_static_command(&command.name))
+                    .map(TemplateCommand::payload),
+            );
+        }
+
+        payloads
+    }
+
+    /// Computes the [fingerprint](fingerprint) of the slash commands of the framework.
+    pub fn fingerprint(&self) -> u64 {
+        fingerprint(&self.command_payloads())
+    }
+
+    /// Checks whether the given remote commands differ from the ones provided to the framework,
+    /// meaning they have to be registered again.
+    ///
+    /// This allows to skip registration on startup when nothing changed, which is specially
+    /// useful for global commands, as their registration is rate limited.
+    pub fn needs_registration(&self, remote: &[TwilightCommand]) -> bool {
+        if let Some(entry_point) = &self.entry_point {
+            let registered = remote.iter().any(|command| {
+                EntryPoint::<D>::is_entry_point(command.kind) && command.name == entry_point.name
+            });
+
+            if !registered {
+                return true;
+            }
+        }
+
+        let remote = remote
+            .iter()
+            .filter(|command| command.kind == CommandType::ChatInput)
+            .map(CommandPayload::from_remote)
+            .collect::<Vec<_>>();
+
+        fingerprint(&remote) != self.fingerprint()
+    }
+}
+
+/// Creates the options of the given arguments.
+fn arg_options<D>(arguments: &[CommandArgument<D>]) -> Vec<CommandOption> {
+    arguments.iter().map(CommandArgument::as_option).collect()
+}
+
+/// Creates the subcommands or subcommand groups of the given group.
+fn group_options<D, E>(parent: &GroupParent<D, E>) -> Vec<CommandOption> {
+    match &parent.kind {
+        ParentType::Group(map) => map
+            .values()
+            .map(|group| {
+                debug!("Creating payload of subgroup {} of {}", group.name, parent.name);
+
+                let mut option = compat::option(
+                    CommandOptionType::SubCommandGroup,
+                    &group.name,
+                    &group.description,
+                );
+                option.options = Some(group.subcommands.values().map(subcommand).collect());
+                option
+            })
+            .collect(),
+        ParentType::Simple(map) => map.values().map(subcommand).collect(),
+    }
+}
+
+/// Creates a subcommand out of the given command.
+fn subcommand<D, E>(command: &Command<D, E>) -> CommandOption {
+    debug!("Creating payload of subcommand {}", command.name);
+
+    let mut option = compat::option(CommandOptionType::SubCommand, &command.name, &command.description);
+    option.options = Some(arg_options(&command.arguments));
+    option
+}
+
+/// Normalizes the given options and their children.
+fn normalize_options(options: &mut [CommandOption]) {
+    for option in options.iter_mut() {
+        if option.required == Some(false) {
+            option.required = None;
+        }
+        if option.autocomplete == Some(false) {
+            option.autocomplete = None;
+        }
+        if option.channel_types.as_ref().is_some_and(Vec::is_empty) {
+            option.channel_types = None;
+        }
+        if option.choices.as_ref().is_some_and(Vec::is_empty) {
+            option.choices = None;
+        }
+        if option.name_localizations.as_ref().is_some_and(|map| map.is_empty()) {
+            option.name_localizations = None;
+        }
+        if option.description_localizations.as_ref().is_some_and(|map| map.is_empty()) {
+            option.description_localizations = None;
+        }
+
+        match &mut option.options {
+            Some(children) if children.is_empty() => option.options = None,
+            Some(children) => normalize_options(children),
+            None => (),
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! Commands loaded from WASM modules at runtime, enabled by the `wasm` feature.
+//!
+//! A plugin is a WASM module describing a single command, which can be added or changed without
+//! recompiling the bot. Data is exchanged as JSON written to the memory of the module, which must
+//! export:
+//!
+//! - `memory`: the memory of the module.
+//! - `alloc(len: i32) -> i32`: allocates `len` bytes, returning a pointer to them.
+//! - `describe() -> i64`: returns the [manifest](PluginManifest) of the command, as a pointer in
+//!   the upper 32 bits and a length in the lower 32 bits.
+//! - `handle(ptr: i32, len: i32)`: handles an [invocation](PluginInvocation) of the command.
+//!
+//! While handling an invocation the module can import from the `zephyrus` module:
+//!
+//! - `reply(ptr: i32, len: i32)`: sets the content of the response.
+//! - `ephemeral()`: makes the response only visible to the user who invoked the command.
+//! - `log(ptr: i32, len: i32)`: logs a message of the plugin.
+//!
+//! Every invocation runs in a fresh instance with a limited amount of fuel and memory, on the
+//! blocking thread pool of tokio, so a plugin can't block the framework forever nor stall the
+//! other tasks of the runtime while it runs.
+use crate::{
+    command::Command,
+    context::SlashContext,
+    dynamic::{CommandBuilder, DynamicArgument},
+    parse::ParseError,
+    twilight_exports::{
+        CommandOptionType, CommandOptionValue, InteractionResponse, InteractionResponseData,
+        InteractionResponseType, MessageFlags,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    path::Path,
+};
+use tokio::task::JoinError;
+use wasmtime::{
+    Caller, Config, Engine, Extern, Instance, Linker, Memory, Module, Store, StoreLimits,
+    StoreLimitsBuilder,
+};
+
+/// The fuel given to a plugin for every invocation when no other amount is specified.
+pub const DEFAULT_FUEL: u64 = 10_000_000;
+/// The bytes of memory a plugin can use in every invocation when no other amount is specified.
+pub const DEFAULT_MEMORY: usize = 16 * 1024 * 1024;
+
+/// The errors a [plugin](WasmPlugin) can fail with.
+#[derive(Debug)]
+pub enum PluginError {
+    /// The module failed to compile, instantiate or run.
+    Wasm(wasmtime::Error),
+    /// The module doesn't export the given item.
+    MissingExport(&'static str),
+    /// The manifest of the module or the response of the host is not valid JSON.
+    Json(serde_json::Error),
+    /// The module accessed memory out of bounds.
+    OutOfBounds,
+    /// The module handled an invocation without replying to it.
+    NoResponse,
+    /// The task running the module panicked or was cancelled.
+    Task(JoinError),
+}
+
+impl Display for PluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Wasm(why) => write!(f, "Wasm error: {}", why),
+            Self::MissingExport(name) => write!(f, "The module doesn't export {}", name),
+            Self::Json(why) => write!(f, "Invalid JSON: {}", why),
+            Self::OutOfBounds => f.write_str("The module accessed memory out of bounds"),
+            Self::NoResponse => f.write_str("The plugin didn't reply to the command"),
+            Self::Task(why) => write!(f, "The plugin task failed: {}", why),
+        }
+    }
+}
+
+impl Error for PluginError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Json(why) => Some(why),
+            Self::Task(why) => Some(why),
+            _ => None,
+        }
+    }
+}
+
+impl From<wasmtime::Error> for PluginError {
+    fn from(why: wasmtime::Error) -> Self {
+        Self::Wasm(why)
+    }
+}
+
+impl From<serde_json::Error> for PluginError {
+    fn from(why: serde_json::Error) -> Self {
+        Self::Json(why)
+    }
+}
+
+/// The type of an argument of a [plugin](WasmPlugin) command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginArgumentKind {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    User,
+    Channel,
+    Role,
+    Mentionable,
+}
+
+impl PluginArgumentKind {
+    fn option_type(self) -> CommandOptionType {
+        match self {
+            Self::String => CommandOptionType::String,
+            Self::Integer => CommandOptionType::Integer,
+            Self::Number => CommandOptionType::Number,
+            Self::Boolean => CommandOptionType::Boolean,
+            Self::User => CommandOptionType::User,
+            Self::Channel => CommandOptionType::Channel,
+            Self::Role => CommandOptionType::Role,
+            Self::Mentionable => CommandOptionType::Mentionable,
+        }
+    }
+}
+
+/// An argument of a [plugin](WasmPlugin) command, as described by its manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginArgument {
+    /// The name of the argument.
+    pub name: String,
+    /// The description of the argument.
+    pub description: String,
+    /// The type of the argument.
+    pub kind: PluginArgumentKind,
+    /// Whether the argument is required.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// The command a [plugin](WasmPlugin) provides, returned by its `describe` export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// The name of the command.
+    pub name: String,
+    /// The description of the command.
+    pub description: String,
+    /// The arguments of the command.
+    #[serde(default)]
+    pub arguments: Vec<PluginArgument>,
+}
+
+/// An invocation of a [plugin](WasmPlugin) command, given to its `handle` export.
+///
+/// Ids are given as strings, and arguments are given as JSON values, being ids strings too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginInvocation {
+    /// The values of the arguments provided to the command.
+    pub arguments: Map<String, Value>,
+    /// The user who invoked the command.
+    pub user_id: Option<String>,
+    /// The guild the command was invoked in.
+    pub guild_id: Option<String>,
+    /// The channel the command was invoked in.
+    pub channel_id: Option<String>,
+}
+
+/// The state of an instance of a plugin, filled by the host functions.
+struct HostState {
+    content: Option<String>,
+    ephemeral: bool,
+    limits: StoreLimits,
+}
+
+impl HostState {
+    fn new(memory: usize) -> Self {
+        Self {
+            content: None,
+            ephemeral: false,
+            limits: StoreLimitsBuilder::new().memory_size(memory).build(),
+        }
+    }
+}
+
+/// A command handler loaded from a WASM module.
+#[derive(Clone)]
+pub struct WasmPlugin {
+    engine: Engine,
+    module: Module,
+    manifest: PluginManifest,
+    fuel: u64,
+    memory: usize,
+}
+
+impl WasmPlugin {
+    /// Loads the plugin at the given path.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PluginError> {
+        let engine = Self::engine()?;
+        let module = Module::from_file(&engine, path)?;
+        Self::new(engine, module)
+    }
+
+    /// Loads the plugin out of the given bytes of the module.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, PluginError> {
+        let engine = Self::engine()?;
+        let module = Module::new(&engine, bytes)?;
+        Self::new(engine, module)
+    }
+
+    /// Sets the fuel given to the plugin for every invocation, defaults to [DEFAULT_FUEL].
+    pub fn fuel(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    /// Sets the bytes of memory the plugin can use in every invocation, growing its memory
+    /// further fails, defaults to [DEFAULT_MEMORY].
+    pub fn memory_limit(mut self, bytes: usize) -> Self {
+        self.memory = bytes;
+        self
+    }
+
+    /// Gets the manifest of the command provided by the plugin.
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    /// Creates the command provided by the plugin.
+    pub fn into_command<D, E>(self) -> Command<D, E>
+    where
+        E: From<ParseError> + From<PluginError> + 'static,
+    {
+        let builder = self.manifest.arguments.iter().fold(
+            CommandBuilder::new(self.manifest.name.clone(), self.manifest.description.clone()),
+            |builder, argument| {
+                builder.argument(
+                    DynamicArgument::new(
+                        argument.name.clone(),
+                        argument.description.clone(),
+                        argument.kind.option_type(),
+                    )
+                    .required(argument.required)
+                    .parser(|value| Ok(json_value(value))),
+                )
+            },
+        );
+
+        builder.handler(move |ctx: &SlashContext<D>, mut args| {
+            let invocation = PluginInvocation {
+                arguments: self
+                    .manifest
+                    .arguments
+                    .iter()
+                    .filter_map(|argument| {
+                        let value = args.take::<Value>(&argument.name)?;
+                        Some((argument.name.clone(), value))
+                    })
+                    .collect(),
+                user_id: ctx.interaction.author_id().map(|id| id.to_string()),
+                guild_id: ctx.interaction.guild_id.map(|id| id.to_string()),
+                channel_id: ctx
+                    .interaction
+                    .channel
+                    .as_ref()
+                    .map(|channel| channel.id.to_string()),
+            };
+            let plugin = self.clone();
+            Box::pin(async move {
+                tokio::task::spawn_blocking(move || plugin.handle(&invocation))
+                    .await
+                    .map_err(PluginError::Task)?
+                    .map_err(E::from)
+            })
+        })
+    }
+
+    /// Handles the given invocation in a fresh instance of the module.
+    ///
+    /// This runs the module on the current thread, blocking it until the module returns, the
+    /// command created by [into_command](Self::into_command) calls it on the blocking thread pool.
+    pub fn handle(&self, invocation: &PluginInvocation) -> Result<InteractionResponse, PluginError> {
+        let mut store = Store::new(&self.engine, HostState::new(self.memory));
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.fuel)?;
+        let instance = Self::linker(&self.engine)?.instantiate(&mut store, &self.module)?;
+
+        let input = serde_json::to_vec(invocation)?;
+        let ptr = write(&mut store, &instance, &input)?;
+        instance
+            .get_typed_func::<(i32, i32), ()>(&mut store, "handle")
+            .map_err(|_| PluginError::MissingExport("handle"))?
+            .call(&mut store, (ptr, input.len() as i32))?;
+
+        let state = store.into_data();
+        let content = state.content.ok_or(PluginError::NoResponse)?;
+        Ok(InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(InteractionResponseData {
+                content: Some(content),
+                flags: state.ephemeral.then_some(MessageFlags::EPHEMERAL),
+                ..Default::default()
+            }),
+        })
+    }
+
+    fn engine() -> Result<Engine, PluginError> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        Ok(Engine::new(&config)?)
+    }
+
+    /// Reads the manifest of the given module.
+    fn new(engine: Engine, module: Module) -> Result<Self, PluginError> {
+        let mut store = Store::new(&engine, HostState::new(DEFAULT_MEMORY));
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(DEFAULT_FUEL)?;
+        let instance = Self::linker(&engine)?.instantiate(&mut store, &module)?;
+        let packed = instance
+            .get_typed_func::<(), i64>(&mut store, "describe")
+            .map_err(|_| PluginError::MissingExport("describe"))?
+            .call(&mut store, ())?;
+
+        let memory = memory(&mut store, &instance)?;
+        let bytes = read(memory.data(&store), (packed >> 32) as i32, packed as i32)?;
+        let manifest = serde_json::from_slice(bytes)?;
+
+        Ok(Self {
+            engine,
+            module,
+            manifest,
+            fuel: DEFAULT_FUEL,
+            memory: DEFAULT_MEMORY,
+        })
+    }
+
+    /// Creates the linker providing the host functions to the module.
+    fn linker(engine: &Engine) -> Result<Linker<HostState>, PluginError> {
+        let mut linker = Linker::new(engine);
+        linker.func_wrap(
+            "zephyrus",
+            "reply",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> wasmtime::Result<()> {
+                let content = caller_string(&mut caller, ptr, len)?;
+                caller.data_mut().content = Some(content);
+                Ok(())
+            },
+        )?;
+        linker.func_wrap("zephyrus", "ephemeral", |mut caller: Caller<'_, HostState>| {
+            caller.data_mut().ephemeral = true;
+        })?;
+        linker.func_wrap(
+            "zephyrus",
+            "log",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> wasmtime::Result<()> {
+                let message = caller_string(&mut caller, ptr, len)?;
+                tracing::info!(target: "zephyrus::plugin", "{}", message);
+                Ok(())
+            },
+        )?;
+
+        Ok(linker)
+    }
+}
+
+/// Converts the value of an argument into the JSON value given to the plugin.
+fn json_value(value: &CommandOptionValue) -> Value {
+    match value {
+        CommandOptionValue::String(s) => Value::from(s.as_str()),
+        CommandOptionValue::Integer(i) => Value::from(*i),
+        CommandOptionValue::Number(n) => Value::from(*n),
+        CommandOptionValue::Boolean(b) => Value::from(*b),
+        CommandOptionValue::User(id) => Value::from(id.to_string()),
+        CommandOptionValue::Channel(id) => Value::from(id.to_string()),
+        CommandOptionValue::Role(id) => Value::from(id.to_string()),
+        CommandOptionValue::Mentionable(id) => Value::from(id.to_string()),
+        _ => Value::Null,
+    }
+}
+
+fn memory(store: &mut Store<HostState>, instance: &Instance) -> Result<Memory, PluginError> {
+    instance
+        .get_memory(store, "memory")
+        .ok_or(PluginError::MissingExport("memory"))
+}
+
+/// Gets the given region of memory.
+fn read(memory: &[u8], ptr: i32, len: i32) -> Result<&[u8], PluginError> {
+    let start = ptr as u32 as usize;
+    let end = start.checked_add(len as u32 as usize).ok_or(PluginError::OutOfBounds)?;
+    memory.get(start..end).ok_or(PluginError::OutOfBounds)
+}
+
+/// Copies the given bytes into memory allocated by the module, returning a pointer to them.
+fn write(store: &mut Store<HostState>, instance: &Instance, bytes: &[u8]) -> Result<i32, PluginError> {
+    let ptr = instance
+        .get_typed_func::<i32, i32>(&mut *store, "alloc")
+        .map_err(|_| PluginError::MissingExport("alloc"))?
+        .call(&mut *store, bytes.len() as i32)?;
+
+    memory(store, instance)?
+        .write(store, ptr as u32 as usize, bytes)
+        .map_err(|_| PluginError::OutOfBounds)?;
+    Ok(ptr)
+}
+
+/// Reads the given string out of the memory of the module calling a host function.
+fn caller_string(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> wasmtime::Result<String> {
+    let memory = match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => memory,
+        _ => return Err(wasmtime::Error::msg("The module doesn't export memory")),
+    };
+    let bytes = read(memory.data(&*caller), ptr, len)
+        .map_err(|why| wasmtime::Error::msg(why.to_string()))?;
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
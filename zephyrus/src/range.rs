@@ -2,7 +2,8 @@ use std::any::type_name;
 use crate::prelude::*;
 use crate::twilight_exports::*;
 use crate::parse_impl::error;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, RangeInclusive};
+use std::str::FromStr;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 
 
@@ -73,7 +74,8 @@ impl<T, E, const START: i64, const END: i64> Parse<T> for Range<E, START, END>
         use twilight_model::application::command::CommandOptionValue;
         Some(ArgumentLimits {
             min: Some(CommandOptionValue::Integer(START)),
-            max: Some(CommandOptionValue::Integer(END))
+            max: Some(CommandOptionValue::Integer(END)),
+            ..Default::default()
         })
     }
 }
@@ -83,3 +85,175 @@ impl<T: Number, const START: i64, const END: i64> Debug for Range<T, START, END>
         write!(f, "Range<{}, {}, {}>({})", type_name::<T>(), START, END, self.0)
     }
 }
+
+/// A floating point number constrained to the given bounds, both of them inclusive.
+///
+/// As floats can't be used as const generics, the bounds are expressed as integers.
+pub struct BoundedF64<const MIN: i64, const MAX: i64>(f64);
+
+impl<const MIN: i64, const MAX: i64> Deref for BoundedF64<MIN, MAX> {
+    type Target = f64;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> DerefMut for BoundedF64<MIN, MAX> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[async_trait]
+impl<T, const MIN: i64, const MAX: i64> Parse<T> for BoundedF64<MIN, MAX>
+    where
+        T: Send + Sync
+{
+    async fn parse(http_client: &WrappedClient, data: &T, value: Option<&CommandOptionValue>) -> Result<Self, ParseError> {
+        let value = f64::parse(http_client, data, value).await?;
+
+        if value < MIN as f64 || value > MAX as f64 {
+            return Err(error(
+                &format!("BoundedF64<{}, {}>", MIN, MAX),
+                true,
+                "Input out of range"
+            ));
+        }
+
+        Ok(Self(value))
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::Number
+    }
+
+    fn limits() -> Option<ArgumentLimits> {
+        use twilight_model::application::command::CommandOptionValue;
+        Some(ArgumentLimits {
+            min: Some(CommandOptionValue::Number(MIN as f64)),
+            max: Some(CommandOptionValue::Number(MAX as f64)),
+            ..Default::default()
+        })
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> Debug for BoundedF64<MIN, MAX> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "BoundedF64<{}, {}>({})", MIN, MAX, self.0)
+    }
+}
+
+/// An inclusive range given by the user as a string, such as `10-50`, `1..5` or `1..=5`, where
+/// both bounds are included in every form and the start can't be greater than the end.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeArg<T>(pub RangeInclusive<T>);
+
+impl<T> RangeArg<T> {
+    /// Gets the parsed range.
+    pub fn into_inner(self) -> RangeInclusive<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for RangeArg<T> {
+    type Target = RangeInclusive<T>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for RangeArg<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: FromStr + PartialOrd> FromStr for RangeArg<T> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bounds = |start: &str, end: &str| {
+            Some((start.trim().parse::<T>().ok()?, end.trim().parse::<T>().ok()?))
+        };
+
+        let dots = s.split_once("..=").or_else(|| s.split_once(".."));
+        let (start, end) = if let Some((start, end)) = dots {
+            bounds(start, end).ok_or("Invalid range bounds")?
+        } else {
+            // The start may be negative, so every dash after the first character is tried.
+            s.char_indices()
+                .skip(1)
+                .filter(|(_, c)| *c == '-')
+                .find_map(|(i, _)| bounds(&s[..i], &s[i + 1..]))
+                .ok_or("Expected a range such as 10-50 or 1..5")?
+        };
+
+        if start > end {
+            return Err("The start of the range is greater than its end");
+        }
+
+        Ok(Self(start..=end))
+    }
+}
+
+#[async_trait]
+impl<T, E> Parse<T> for RangeArg<E>
+    where
+        T: Send + Sync,
+        E: FromStr + PartialOrd + Send
+{
+    async fn parse(http_client: &WrappedClient, data: &T, value: Option<&CommandOptionValue>) -> Result<Self, ParseError> {
+        let input = String::parse(http_client, data, value).await?;
+
+        input.trim().parse().map_err(|why| error(
+            &format!("RangeArg<{}>", type_name::<E>()),
+            true,
+            why
+        ))
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RangeArg;
+
+    fn parse(input: &str) -> Result<RangeArg<i64>, &'static str> {
+        input.parse()
+    }
+
+    #[test]
+    fn accepted_forms() {
+        assert_eq!(parse("10-50").unwrap().into_inner(), 10..=50);
+        assert_eq!(parse("1..5").unwrap().into_inner(), 1..=5);
+        assert_eq!(parse("1..=5").unwrap().into_inner(), 1..=5);
+        assert_eq!(parse(" 1 .. 5 ").unwrap().into_inner(), 1..=5);
+        assert_eq!(parse("7-7").unwrap().into_inner(), 7..=7);
+    }
+
+    #[test]
+    fn negative_numbers() {
+        assert_eq!(parse("-5-10").unwrap().into_inner(), -5..=10);
+        assert_eq!(parse("-10--5").unwrap().into_inner(), -10..=-5);
+        assert_eq!(parse("-10..-5").unwrap().into_inner(), -10..=-5);
+        assert_eq!(parse("-3..=3").unwrap().into_inner(), -3..=3);
+        assert!("-5-10".parse::<RangeArg<u32>>().is_err());
+    }
+
+    #[test]
+    fn reversed_bounds() {
+        assert!(parse("50-10").is_err());
+        assert!(parse("5..1").is_err());
+        assert!(parse("-5--10").is_err());
+    }
+
+    #[test]
+    fn malformed_input() {
+        for input in ["", "5", "-5", "1-", "-1-", "..5", "1..", "a-b", "1-2-3", "1...5", "1..=", "1 to 5"] {
+            assert!(parse(input).is_err(), "{:?} should be rejected", input);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::twilight_exports::{Command as TwilightCommand, CommandType};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// What happened to a command while registering the commands of the framework.
+#[derive(Debug)]
+pub enum RegistrationStatus {
+    /// The command wasn't registered and was created.
+    Created,
+    /// The command was registered but differed from the local one, so it was updated.
+    Updated,
+    /// The command was already registered as is, so no request was made.
+    Unchanged,
+    /// The command isn't provided to the framework anymore and was deleted.
+    Deleted,
+    /// The request to create, update or delete the command failed with the given error.
+    Failed(Box<dyn Error + Send + Sync>),
+}
+
+impl RegistrationStatus {
+    /// Whether the command is registered as provided to the framework.
+    pub fn is_registered(&self) -> bool {
+        matches!(self, Self::Created | Self::Updated | Self::Unchanged)
+    }
+
+    /// Whether the request made for the command failed.
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+}
+
+/// The registration of a single command, see [RegistrationReport].
+#[derive(Debug)]
+pub struct CommandRegistration {
+    /// The name of the command.
+    pub name: String,
+    /// The type of the command.
+    pub kind: CommandType,
+    /// What happened to the command.
+    pub status: RegistrationStatus,
+    /// The command as registered in discord, or as it was before being deleted. `None` if the
+    /// request made for the command failed.
+    pub command: Option<TwilightCommand>,
+}
+
+/// A report of the registration of the commands of the framework, listing what happened to each
+/// command so partial failures aren't hidden.
+#[derive(Debug, Default)]
+pub struct RegistrationReport {
+    /// The registrations of the commands, in the order they were made.
+    pub entries: Vec<CommandRegistration>,
+}
+
+impl RegistrationReport {
+    /// Adds the registration of a command to the report.
+    pub(crate) fn push(
+        &mut self,
+        name: impl Into<String>,
+        kind: CommandType,
+        status: RegistrationStatus,
+        command: Option<TwilightCommand>,
+    ) {
+        self.entries.push(CommandRegistration {
+            name: name.into(),
+            kind,
+            status,
+            command,
+        });
+    }
+
+    /// Adds the outcome of a request made for the command with the given name and type, which
+    /// is given the status if it succeeded.
+    pub(crate) fn push_result(
+        &mut self,
+        name: impl Into<String>,
+        kind: CommandType,
+        status: RegistrationStatus,
+        result: Result<TwilightCommand, Box<dyn Error + Send + Sync>>,
+    ) {
+        match result {
+            Ok(command) => self.push(name, kind, status, Some(command)),
+            Err(error) => self.push(name, kind, RegistrationStatus::Failed(error), None),
+        }
+    }
+
+    /// Whether the report lists a command with the given name and type, whatever happened to it.
+    pub(crate) fn contains(&self, name: &str, kind: CommandType) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.name == name && entry.kind == kind)
+    }
+
+    /// Iterates over the commands registered as provided to the framework.
+    pub fn registered(&self) -> impl Iterator<Item = &TwilightCommand> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status.is_registered())
+            .filter_map(|entry| entry.command.as_ref())
+    }
+
+    /// Iterates over the commands whose request failed.
+    pub fn failed(&self) -> impl Iterator<Item = &CommandRegistration> {
+        self.entries.iter().filter(|entry| entry.status.is_failed())
+    }
+
+    /// Whether no request failed.
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+
+    /// Counts the commands with the given status.
+    fn count(&self, fun: fn(&RegistrationStatus) -> bool) -> usize {
+        self.entries.iter().filter(|entry| fun(&entry.status)).count()
+    }
+}
+
+impl Display for RegistrationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{} created, {} updated, {} unchanged, {} deleted, {} failed",
+            self.count(|status| matches!(status, RegistrationStatus::Created)),
+            self.count(|status| matches!(status, RegistrationStatus::Updated)),
+            self.count(|status| matches!(status, RegistrationStatus::Unchanged)),
+            self.count(|status| matches!(status, RegistrationStatus::Deleted)),
+            self.count(RegistrationStatus::is_failed),
+        )
+    }
+}
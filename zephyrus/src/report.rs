@@ -0,0 +1,192 @@
+use crate::{
+    audit::{display_value, REDACTED},
+    context::SlashContext,
+    twilight_exports::{
+        ChannelMarker, GuildMarker, Id, InteractionData, InteractionMarker, UserMarker,
+    },
+};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+#[cfg(feature = "sentry")]
+use crate::{
+    error::FrameworkError,
+    hook::{AfterHook, CommandOutcome, ErrorHook},
+    BoxFuture,
+};
+
+/// A report of a command failure or panic including the metadata of the interaction, ready to be
+/// sent to an error tracker such as Sentry from an [error hook](crate::hook::ErrorHook) or an
+/// [after hook](crate::hook::AfterHook).
+///
+/// ```ignore
+/// #[error_handler]
+/// async fn report(ctx: &SlashContext<()>, command: &str, error: FrameworkError) {
+///     let report = ErrorReport::new(ctx, command, &error, &["password"]);
+///     sentry::with_scope(
+///         |scope| report.tags().into_iter().for_each(|(key, value)| scope.set_tag(&key, value)),
+///         || sentry::capture_message(&report.to_string(), sentry::Level::Error),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ErrorReport {
+    /// The interaction which invoked the command.
+    pub interaction_id: Id<InteractionMarker>,
+    /// The path of the command.
+    pub command: String,
+    /// The user who invoked the command.
+    pub user_id: Option<Id<UserMarker>>,
+    /// The guild the command was invoked in.
+    pub guild_id: Option<Id<GuildMarker>>,
+    /// The channel the command was invoked in.
+    pub channel_id: Option<Id<ChannelMarker>>,
+    /// The name and value of every argument provided, with the redacted and
+    /// [sensitive](crate::argument::CommandArgument::sensitive) ones replaced by
+    /// [REDACTED](REDACTED).
+    pub arguments: Vec<(String, String)>,
+    /// The message of the error.
+    pub message: String,
+}
+
+impl ErrorReport {
+    /// Creates the report of the given error produced by the command with the given path,
+    /// redacting the value of the arguments with the given names and of the
+    /// [sensitive](SlashContext::is_sensitive) ones.
+    pub fn new<D>(
+        ctx: &SlashContext<'_, D>,
+        command: &str,
+        error: &impl Display,
+        redacted: &[&str],
+    ) -> Self {
+        let interaction = &ctx.interaction;
+        let options = match interaction.data.as_ref() {
+            Some(InteractionData::ApplicationCommand(data)) => data.options.as_slice(),
+            _ => &[],
+        };
+
+        let arguments = options
+            .iter()
+            .map(|option| {
+                let value = if redacted.contains(&option.name.as_str())
+                    || ctx.is_sensitive(&option.name)
+                {
+                    REDACTED.to_string()
+                } else {
+                    display_value(&option.value)
+                };
+                (option.name.clone(), value)
+            })
+            .collect();
+
+        Self {
+            interaction_id: interaction.id,
+            command: command.to_string(),
+            user_id: interaction.author_id(),
+            guild_id: interaction.guild_id,
+            channel_id: interaction.channel.as_ref().map(|channel| channel.id),
+            arguments,
+            message: error.to_string(),
+        }
+    }
+
+    /// Gets the metadata of the report as key-value pairs, such as the tags of a Sentry event.
+    ///
+    /// Arguments use the `argument.<name>` keys.
+    pub fn tags(&self) -> Vec<(String, String)> {
+        let mut tags = vec![
+            (String::from("interaction"), self.interaction_id.to_string()),
+            (String::from("command"), self.command.clone()),
+        ];
+
+        if let Some(user_id) = self.user_id {
+            tags.push((String::from("user"), user_id.to_string()));
+        }
+        if let Some(guild_id) = self.guild_id {
+            tags.push((String::from("guild"), guild_id.to_string()));
+        }
+        if let Some(channel_id) = self.channel_id {
+            tags.push((String::from("channel"), channel_id.to_string()));
+        }
+
+        for (name, value) in &self.arguments {
+            tags.push((format!("argument.{}", name), value.clone()));
+        }
+
+        tags
+    }
+}
+
+#[cfg(feature = "sentry")]
+impl ErrorReport {
+    /// Sends the report to Sentry as an error event, tagged with its [metadata](Self::tags) and
+    /// having the user who invoked the command as the user of the event.
+    pub fn capture(&self) -> sentry::types::Uuid {
+        sentry::with_scope(
+            |scope| {
+                for (key, value) in self.tags() {
+                    scope.set_tag(&key, value);
+                }
+                if let Some(user_id) = self.user_id {
+                    scope.set_user(Some(sentry::User {
+                        id: Some(user_id.to_string()),
+                        ..Default::default()
+                    }));
+                }
+            },
+            || sentry::capture_message(&self.to_string(), sentry::Level::Error),
+        )
+    }
+}
+
+/// Creates an [error hook](ErrorHook) reporting the panics of the commands to Sentry, redacting
+/// the [sensitive](SlashContext::is_sensitive) arguments. Skipped commands aren't reported.
+///
+/// ```ignore
+/// let framework = Framework::builder(http_client, app_id, ())
+///     .on_error(sentry_error_hook)
+///     .after(sentry_after_hook)
+///     .build();
+/// ```
+#[cfg(feature = "sentry")]
+pub fn sentry_error_hook<D>() -> ErrorHook<D> {
+    ErrorHook(capture_panic)
+}
+
+/// Creates an [after hook](AfterHook) reporting the errors returned by the commands to Sentry,
+/// redacting the [sensitive](SlashContext::is_sensitive) arguments.
+///
+/// As the framework only takes a single after hook, [capture](ErrorReport::capture) can be used
+/// to report the errors from an existing one instead.
+#[cfg(feature = "sentry")]
+pub fn sentry_after_hook<D, E: Display>() -> AfterHook<D, E> {
+    AfterHook(capture_failure)
+}
+
+#[cfg(feature = "sentry")]
+fn capture_panic<'a, D>(
+    ctx: &'a SlashContext<'a, D>,
+    command: &'a str,
+    error: FrameworkError,
+) -> BoxFuture<'a, ()> {
+    if let FrameworkError::Panic(_) = &error {
+        ErrorReport::new(ctx, command, &error, &[]).capture();
+    }
+    Box::pin(std::future::ready(()))
+}
+
+#[cfg(feature = "sentry")]
+fn capture_failure<'a, D, E: Display>(
+    ctx: &'a SlashContext<'a, D>,
+    command: &'a str,
+    outcome: CommandOutcome<E>,
+) -> BoxFuture<'a, ()> {
+    if let Some(Err(why)) = outcome.result() {
+        ErrorReport::new(ctx, command, why, &[]).capture();
+    }
+    Box::pin(std::future::ready(()))
+}
+
+impl Display for ErrorReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "/{} failed: {}", self.command, self.message)
+    }
+}
@@ -0,0 +1,551 @@
+use crate::{
+    compat,
+    twilight_exports::{
+        AllowedMentions, ApplicationMarker, Attachment, Client, Component, CreateFollowup, Embed,
+        Id, Interaction, InteractionResponse, InteractionResponseData, InteractionResponseType,
+        MessageFlags, Poll, PollAnswer, PollLayoutType, PollMedia, Timestamp, UpdateResponse,
+    },
+};
+use serde::Serialize;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use twilight_http::{
+    request::{attachment::AttachmentManager, Request, RequestBuilder},
+    response::marker::EmptyBody,
+    routing::Route,
+    Error as HttpError,
+};
+
+/// The maximum number of answers of a poll.
+pub const MAX_POLL_ANSWERS: usize = 10;
+/// The maximum duration of a poll, in hours.
+pub const MAX_POLL_HOURS: u64 = 768;
+/// The seconds of an hour, the unit of the duration of polls.
+const HOUR: u64 = 3600;
+
+/// The errors produced when creating a [poll].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PollError {
+    /// The duration isn't between 1 and [768](MAX_POLL_HOURS) hours.
+    InvalidDuration(Duration),
+    /// The poll has more than [10](MAX_POLL_ANSWERS) answers, containing how many it has.
+    TooManyAnswers(usize),
+}
+
+impl Display for PollError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidDuration(duration) => write!(
+                f,
+                "Polls must last between 1 and {} hours, got {:?}",
+                MAX_POLL_HOURS, duration
+            ),
+            Self::TooManyAnswers(answers) => write!(
+                f,
+                "Polls can have at most {} answers, got {}",
+                MAX_POLL_ANSWERS, answers
+            ),
+        }
+    }
+}
+
+impl Error for PollError {}
+
+/// A builder of the [responses](InteractionResponse) returned by commands.
+#[derive(Debug, Clone)]
+pub struct ResponseBuilder {
+    kind: InteractionResponseType,
+    data: InteractionResponseData,
+}
+
+impl ResponseBuilder {
+    /// Creates a builder of a response of the given kind.
+    pub fn new(kind: InteractionResponseType) -> Self {
+        Self {
+            kind,
+            data: Default::default(),
+        }
+    }
+
+    /// Creates a builder of a response sending a message.
+    pub fn message() -> Self {
+        Self::new(InteractionResponseType::ChannelMessageWithSource)
+    }
+
+    /// Creates a builder of a response updating the message the used component belongs to.
+    pub fn update() -> Self {
+        Self::new(InteractionResponseType::UpdateMessage)
+    }
+
+    /// Sets the content of the message.
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.data.content = Some(content.into());
+        self
+    }
+
+    /// Adds an embed to the message.
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.data.embeds.get_or_insert_with(Vec::new).push(embed);
+        self
+    }
+
+    /// Sets the components of the message, usually built using a
+    /// [components builder](crate::component::ComponentsBuilder).
+    pub fn components(mut self, components: Vec<Component>) -> Self {
+        self.data.components = Some(components);
+        self
+    }
+
+    /// Sets the [layout components](crate::layout) of the message, usually built using a
+    /// [layout builder](crate::layout::LayoutBuilder), marking it as using them. Such messages
+    /// can't have a content nor embeds.
+    pub fn layout(mut self, components: Vec<Component>) -> Self {
+        self.data.components = Some(components);
+        self.flags(MessageFlags::IS_COMPONENTS_V2)
+    }
+
+    /// Adds the given flags to the message.
+    pub fn flags(mut self, flags: MessageFlags) -> Self {
+        let current = self.data.flags.unwrap_or_else(MessageFlags::empty);
+        self.data.flags = Some(current | flags);
+        self
+    }
+
+    /// Makes the message only visible to the user who used the interaction.
+    pub fn ephemeral(self) -> Self {
+        self.flags(MessageFlags::EPHEMERAL)
+    }
+
+    /// Makes the message not include the embeds of the links it contains.
+    pub fn suppress_embeds(self) -> Self {
+        self.flags(MessageFlags::SUPPRESS_EMBEDS)
+    }
+
+    /// Makes the message not trigger push and desktop notifications.
+    pub fn silent(self) -> Self {
+        self.flags(MessageFlags::SUPPRESS_NOTIFICATIONS)
+    }
+
+    /// Attaches a poll to the message, see [poll] for the meaning of every argument and the
+    /// errors returned.
+    pub fn poll<I, A>(
+        mut self,
+        question: impl Into<String>,
+        answers: I,
+        duration: Duration,
+        allow_multiselect: bool,
+    ) -> Result<Self, PollError>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<String>,
+    {
+        self.data.poll = Some(poll(question, answers, duration, allow_multiselect)?);
+        Ok(self)
+    }
+
+    /// Sets the allowed mentions of the message, overriding the default ones of the framework.
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.data.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Attaches a file with the given name and content to the message.
+    pub fn attach(self, filename: impl Into<String>, file: impl Into<Vec<u8>>) -> Self {
+        self.attachment(|id| Attachment::from_bytes(filename.into(), file.into(), id))
+    }
+
+    /// Attaches a file with the given name, content and description to the message, the
+    /// description being used as alt text.
+    pub fn attach_with_description(
+        self,
+        filename: impl Into<String>,
+        file: impl Into<Vec<u8>>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.attachment(|id| {
+            let mut attachment = Attachment::from_bytes(filename.into(), file.into(), id);
+            attachment.description = Some(description.into());
+            attachment
+        })
+    }
+
+    /// Adds the attachment created by the given function, which receives an id unique within
+    /// the message.
+    fn attachment(mut self, fun: impl FnOnce(u64) -> Attachment) -> Self {
+        let attachments = self.data.attachments.get_or_insert_with(Vec::new);
+        let id = attachments.len() as u64;
+        attachments.push(fun(id));
+        self
+    }
+
+    /// Builds the response.
+    pub fn build(self) -> InteractionResponse {
+        InteractionResponse {
+            kind: self.kind,
+            data: Some(self.data),
+        }
+    }
+}
+
+impl From<ResponseBuilder> for InteractionResponse {
+    fn from(builder: ResponseBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Sets the content, embeds, components, attachments and allowed mentions of the given response
+/// data to the update of an original response, see [update_response] to send polls.
+pub(crate) fn update_with<'a>(
+    mut update: UpdateResponse<'a>,
+    data: &'a InteractionResponseData,
+) -> UpdateResponse<'a> {
+    if let Some(content) = &data.content {
+        update = update.content(Some(content));
+    }
+    if let Some(embeds) = &data.embeds {
+        update = update.embeds(Some(embeds));
+    }
+    if let Some(components) = &data.components {
+        update = update.components(Some(components));
+    }
+    if let Some(attachments) = &data.attachments {
+        update = update.attachments(attachments);
+    }
+    if let Some(allowed_mentions) = &data.allowed_mentions {
+        update = update.allowed_mentions(Some(allowed_mentions));
+    }
+    update
+}
+
+/// Sets the content, embeds, components, attachments, allowed mentions and flags of the given
+/// response data to a followup message, see [create_followup] to send polls.
+pub(crate) fn followup_with<'a>(
+    mut followup: CreateFollowup<'a>,
+    data: &'a InteractionResponseData,
+) -> CreateFollowup<'a> {
+    if let Some(content) = &data.content {
+        followup = followup.content(content);
+    }
+    if let Some(embeds) = &data.embeds {
+        followup = followup.embeds(embeds);
+    }
+    if let Some(components) = &data.components {
+        followup = followup.components(components);
+    }
+    if let Some(attachments) = &data.attachments {
+        followup = followup.attachments(attachments);
+    }
+    if let Some(allowed_mentions) = &data.allowed_mentions {
+        followup = followup.allowed_mentions(Some(allowed_mentions));
+    }
+    if let Some(flags) = data.flags {
+        followup = followup.flags(flags);
+    }
+    followup
+}
+
+/// Creates the attachments of the given files, giving each one an id unique within the message.
+///
+/// This is useful to send files in followups, see
+/// [SlashContext::followup_with_files](crate::context::SlashContext::followup_with_files).
+pub fn attachments<I, N, F>(files: I) -> Vec<Attachment>
+where
+    I: IntoIterator<Item = (N, F)>,
+    N: Into<String>,
+    F: Into<Vec<u8>>,
+{
+    files
+        .into_iter()
+        .enumerate()
+        .map(|(id, (filename, file))| Attachment::from_bytes(filename.into(), file.into(), id as u64))
+        .collect()
+}
+
+/// Creates a poll with the given question and answers, which lasts for the given duration and
+/// allows selecting more than one answer if `allow_multiselect` is true.
+///
+/// Discord allows up to [10](MAX_POLL_ANSWERS) answers and a duration between 1 and
+/// [768](MAX_POLL_HOURS) hours, rounding partial hours up, returning an error otherwise.
+///
+/// Discord expects the duration of the poll rather than when it ends, which twilight's poll model
+/// doesn't have, so the poll is created with the time it ends as its expiry, which is turned back
+/// into its duration when the framework sends it. This is the case of the responses returned by
+/// commands and the ones sent using [SlashContext::reply](crate::context::SlashContext::reply),
+/// which sends them as followups once the interaction is responded, while polls given directly
+/// to twilight's builders last for the default 24 hours.
+pub fn poll<I, A>(
+    question: impl Into<String>,
+    answers: I,
+    duration: Duration,
+    allow_multiselect: bool,
+) -> Result<Poll, PollError>
+where
+    I: IntoIterator<Item = A>,
+    A: Into<String>,
+{
+    if duration < Duration::from_secs(HOUR) || duration > Duration::from_secs(MAX_POLL_HOURS * HOUR) {
+        return Err(PollError::InvalidDuration(duration));
+    }
+
+    let answers = answers
+        .into_iter()
+        .enumerate()
+        .map(|(index, answer)| PollAnswer {
+            answer_id: index as u8 + 1,
+            poll_media: compat::poll_media(answer.into()),
+        })
+        .collect::<Vec<_>>();
+    if answers.len() > MAX_POLL_ANSWERS {
+        return Err(PollError::TooManyAnswers(answers.len()));
+    }
+
+    let hours = (duration.as_secs() + u64::from(duration.subsec_nanos() > 0)).div_ceil(HOUR);
+    let expiry = SystemTime::now()
+        .checked_add(Duration::from_secs(hours * HOUR))
+        .and_then(|end| end.duration_since(UNIX_EPOCH).ok())
+        .and_then(|end| Timestamp::from_secs(end.as_secs() as i64).ok());
+
+    Ok(Poll {
+        answers,
+        allow_multiselect,
+        expiry,
+        layout_type: PollLayoutType::Default,
+        question: compat::poll_media(question.into()),
+        results: None,
+    })
+}
+
+/// A poll as discord expects it when creating it.
+#[derive(Serialize)]
+struct PollCreate<'a> {
+    question: &'a PollMedia,
+    answers: Vec<AnswerCreate<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<u64>,
+    allow_multiselect: bool,
+    layout_type: PollLayoutType,
+}
+
+/// An answer of a [poll being created](PollCreate).
+#[derive(Serialize)]
+struct AnswerCreate<'a> {
+    poll_media: &'a PollMedia,
+}
+
+impl<'a> PollCreate<'a> {
+    /// Creates the payload of the given poll, turning its expiry back into the duration in hours
+    /// it was [created](poll) with, leaving it to discord's default if it has none.
+    fn new(poll: &'a Poll) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs() as i64);
+        // Rounded to the closest hour, as some time passes between creating and sending it
+        let duration = poll.expiry.map(|expiry| {
+            let seconds = (expiry.as_secs() - now).max(0) as u64;
+            ((seconds + HOUR / 2) / HOUR).clamp(1, MAX_POLL_HOURS)
+        });
+
+        Self {
+            question: &poll.question,
+            answers: poll
+                .answers
+                .iter()
+                .map(|answer| AnswerCreate {
+                    poll_media: &answer.poll_media,
+                })
+                .collect(),
+            duration,
+            allow_multiselect: poll.allow_multiselect,
+            layout_type: poll.layout_type,
+        }
+    }
+}
+
+/// The data of a message with a poll, sent with the [payload](PollCreate) discord expects.
+#[derive(Serialize)]
+struct PollMessage<'a> {
+    #[serde(flatten)]
+    data: InteractionResponseData,
+    poll: PollCreate<'a>,
+}
+
+impl<'a> PollMessage<'a> {
+    fn new(data: &InteractionResponseData, poll: &'a Poll) -> Self {
+        Self {
+            data: InteractionResponseData {
+                poll: None,
+                ..data.clone()
+            },
+            poll: PollCreate::new(poll),
+        }
+    }
+}
+
+/// A [response](InteractionResponse) with a poll, see [PollMessage].
+#[derive(Serialize)]
+struct PollResponse<'a> {
+    #[serde(rename = "type")]
+    kind: InteractionResponseType,
+    data: PollMessage<'a>,
+}
+
+/// Sets the given body to the request, as a form if the message has attachments.
+fn with_body(
+    request: RequestBuilder,
+    body: &impl Serialize,
+    attachments: Option<&Vec<Attachment>>,
+) -> Result<Request, HttpError> {
+    // If serializing fails, so does serializing it as JSON, which fails the request
+    let request = match (attachments, serde_json::to_vec(body)) {
+        (Some(attachments), Ok(fields)) => request.form(
+            AttachmentManager::new()
+                .set_files(attachments.iter().collect())
+                .build_form(&fields),
+        ),
+        _ => request.json(body),
+    };
+
+    request.use_authorization_token(false).build()
+}
+
+/// Sends the given response to the given interaction, sending its poll, if any, with the duration
+/// discord expects.
+pub(crate) async fn create_response(
+    http: &Client,
+    interaction: &Interaction,
+    response: &InteractionResponse,
+) -> Result<(), HttpError> {
+    let (data, poll) = match response.data.as_ref().and_then(|data| Some((data, data.poll.as_ref()?))) {
+        Some(found) => found,
+        None => {
+            return http
+                .interaction(interaction.application_id)
+                .create_response(interaction.id, &interaction.token, response)
+                .await
+                .map(|_| ())
+        }
+    };
+
+    let request = Request::builder(&Route::InteractionCallback {
+        interaction_id: interaction.id.get(),
+        interaction_token: &interaction.token,
+        with_response: false,
+    });
+    let body = PollResponse {
+        kind: response.kind,
+        data: PollMessage::new(data, poll),
+    };
+    let request = with_body(request, &body, data.attachments.as_ref())?;
+
+    http.request::<EmptyBody>(request).await.map(|_| ())
+}
+
+/// Sends a followup message with the given data to the interaction with the given token, sending
+/// its poll, if any, with the duration discord expects.
+pub(crate) async fn create_followup(
+    http: &Client,
+    application_id: Id<ApplicationMarker>,
+    token: &str,
+    data: &InteractionResponseData,
+) -> Result<(), HttpError> {
+    let poll = match &data.poll {
+        Some(poll) => poll,
+        None => {
+            let client = http.interaction(application_id);
+            return followup_with(client.create_followup(token), data).await.map(|_| ());
+        }
+    };
+
+    let request = Request::builder(&Route::ExecuteWebhook {
+        thread_id: None,
+        token,
+        wait: None,
+        with_components: Some(data.components.as_ref().is_some_and(|components| !components.is_empty())),
+        webhook_id: application_id.get(),
+    });
+    let request = with_body(request, &PollMessage::new(data, poll), data.attachments.as_ref())?;
+
+    http.request::<EmptyBody>(request).await.map(|_| ())
+}
+
+/// Updates the original response of the interaction with the given token with the given data,
+/// sending its poll, if any, with the duration discord expects, which is only possible when
+/// editing a deferred response.
+pub(crate) async fn update_response(
+    http: &Client,
+    application_id: Id<ApplicationMarker>,
+    token: &str,
+    data: &InteractionResponseData,
+) -> Result<(), HttpError> {
+    let poll = match &data.poll {
+        Some(poll) => poll,
+        None => {
+            let client = http.interaction(application_id);
+            return update_with(client.update_response(token), data).await.map(|_| ());
+        }
+    };
+
+    let request = Request::builder(&Route::UpdateInteractionOriginal {
+        application_id: application_id.get(),
+        interaction_token: token,
+    });
+    // Message flags can't be changed once the response is sent
+    let data = InteractionResponseData {
+        flags: None,
+        ..data.clone()
+    };
+    let request = with_body(request, &PollMessage::new(&data, poll), data.attachments.as_ref())?;
+
+    http.request::<EmptyBody>(request).await.map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{poll, PollCreate, PollError, HOUR};
+    use std::time::Duration;
+
+    fn hours(hours: u64) -> Duration {
+        Duration::from_secs(hours * HOUR)
+    }
+
+    fn duration(poll: &super::Poll) -> Option<u64> {
+        PollCreate::new(poll).duration
+    }
+
+    #[test]
+    fn sends_the_duration_in_hours() {
+        assert_eq!(duration(&poll("?", ["a", "b"], hours(1), false).unwrap()), Some(1));
+        assert_eq!(duration(&poll("?", ["a", "b"], hours(48), false).unwrap()), Some(48));
+        assert_eq!(duration(&poll("?", ["a", "b"], hours(768), false).unwrap()), Some(768));
+        assert_eq!(duration(&poll("?", ["a"], Duration::from_secs(HOUR + 1), false).unwrap()), Some(2));
+
+        let json = serde_json::to_value(PollCreate::new(&poll("?", ["a"], hours(5), true).unwrap())).unwrap();
+        assert_eq!(json["duration"], 5);
+        assert_eq!(json["allow_multiselect"], true);
+        assert_eq!(json["answers"][0]["poll_media"]["text"], "a");
+        assert!(json.get("expiry").is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        for duration in [Duration::ZERO, Duration::from_secs(HOUR - 1), hours(768) + Duration::from_millis(1)] {
+            assert_eq!(
+                poll("?", ["a"], duration, false).unwrap_err(),
+                PollError::InvalidDuration(duration)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_too_many_answers() {
+        assert!(poll("?", (0..10).map(|i| i.to_string()), hours(1), false).is_ok());
+        assert_eq!(
+            poll("?", (0..11).map(|i| i.to_string()), hours(1), false).unwrap_err(),
+            PollError::TooManyAnswers(11)
+        );
+    }
+}
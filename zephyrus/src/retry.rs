@@ -0,0 +1,104 @@
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+use tracing::debug;
+use twilight_http::{error::ErrorType, Error};
+
+/// The policy used to retry the http requests made by the framework itself, such as interaction
+/// responses, autocomplete responses and command registration.
+///
+/// Only transient failures are retried, which are timeouts, connection errors and `5xx`
+/// responses. Between attempts the framework waits an exponentially growing delay with full
+/// jitter, so the attempts of many interactions failing at once don't hit discord together.
+///
+/// Keep in mind interactions must be responded within 3 seconds, so the delays of the policy
+/// should be kept short.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The delay of the first retry, doubled on every following one.
+    pub base_delay: Duration,
+    /// The maximum delay between attempts.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy making at most the given number of attempts, waiting 100 milliseconds
+    /// before the first retry and at most one second between attempts.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+
+    /// Creates a policy which doesn't retry any request.
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    /// Sets the delay of the first retry.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Sets the maximum delay between attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Gets a random delay to wait before the given retry, starting from 1.
+    fn delay(&self, retry: u32) -> Duration {
+        let cap = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry - 1))
+            .min(self.max_delay);
+        let jitter = RandomState::new().build_hasher().finish() % 1_000_000;
+
+        cap.mul_f64(jitter as f64 / 1_000_000.0)
+    }
+
+    /// Executes the request created by the given function, retrying it while it fails with a
+    /// transient error and attempts are left.
+    pub(crate) async fn run<F, Fut, T>(&self, mut request: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match request().await {
+                Err(error) if attempt < self.max_attempts && is_transient(&error) => {
+                    let delay = self.delay(attempt);
+                    debug!("Retrying request after {:?}: {}", delay, error);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Whether the given error is a transient failure worth retrying.
+fn is_transient(error: &Error) -> bool {
+    match error.kind() {
+        ErrorType::RequestError | ErrorType::RequestTimedOut => true,
+        ErrorType::Response { status, .. } => status.is_server_error(),
+        _ => false,
+    }
+}
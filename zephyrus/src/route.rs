@@ -0,0 +1,232 @@
+//! The lookup of the commands and groups targeted by interactions.
+
+use rustc_hash::FxHasher;
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::HashMap,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    hash::Hasher,
+    ops::Deref,
+    ptr::NonNull,
+};
+
+/// The average number of names sharing a displacement in a [perfect hash table](Routes).
+const BUCKET_SIZE: usize = 4;
+/// The number of seeds tried before giving up on building a [perfect hash table](Routes).
+const MAX_SEEDS: u64 = 16;
+
+/// A map of routes by name, which is looked up through a perfect hash table built along with it.
+///
+/// The routes can't be changed once the map is created, the perfect hash table finds the slot of
+/// a name with a single cheap hash and a string comparison, without probing nor collisions. The
+/// map itself can still be read through [Deref](Deref).
+pub struct Routes<V> {
+    map: HashMap<Cow<'static, str>, V>,
+    table: Option<Table<V>>,
+}
+
+/// A perfect hash table pointing into the entries of the map of some [routes](Routes).
+struct Table<V> {
+    seed: u64,
+    displacements: Box<[Displacement]>,
+    entries: Box<[Entry<V>]>,
+}
+
+/// The displacement applied to the hashes of the names of a bucket.
+type Displacement = (u32, u32);
+/// A pointer to the key and the value of an entry of a map.
+type Entry<V> = (NonNull<Cow<'static, str>>, NonNull<V>);
+
+// SAFETY: The table only points into the map owned along with it, which is never modified.
+unsafe impl<V: Send> Send for Routes<V> {}
+unsafe impl<V: Sync> Sync for Routes<V> {}
+
+impl<V> Routes<V> {
+    /// Creates the routes to the values of the given map, building its perfect hash table.
+    pub fn new(map: HashMap<Cow<'static, str>, V>) -> Self {
+        let table = Table::new(&map);
+        Self { map, table }
+    }
+
+    /// Gets the value routed to by the given name.
+    pub fn get(&self, name: &str) -> Option<&V> {
+        let table = match &self.table {
+            Some(table) => table,
+            None => return self.map.get(name),
+        };
+
+        let (key, value) = table.entries[table.slot(name)];
+        // SAFETY: The entries point into `self.map`, which lives as long as `self` and is never
+        // modified, so its keys and values are never moved nor dropped while borrowed.
+        let (key, value) = unsafe { (key.as_ref(), value.as_ref()) };
+        (key == name).then_some(value)
+    }
+
+    /// Gets the map of the routes.
+    pub fn into_inner(self) -> HashMap<Cow<'static, str>, V> {
+        self.map
+    }
+}
+
+impl<V> Table<V> {
+    /// Builds the table of the given map, returning `None` if it is empty or if no seed yields
+    /// a perfect hash function for its names.
+    fn new(map: &HashMap<Cow<'static, str>, V>) -> Option<Self> {
+        if map.is_empty() {
+            return None;
+        }
+
+        let entries = map.iter().collect::<Vec<_>>();
+        let names = entries.iter().map(|(key, _)| key.as_ref()).collect::<Vec<_>>();
+        (0..MAX_SEEDS).find_map(|seed| {
+            let (displacements, slots) = displace(seed, &names)?;
+            let entries = slots
+                .into_iter()
+                .map(|index| {
+                    let (key, value) = entries[index];
+                    (NonNull::from(key), NonNull::from(value))
+                })
+                .collect();
+
+            Some(Self {
+                seed,
+                displacements: displacements.into_boxed_slice(),
+                entries,
+            })
+        })
+    }
+
+    /// Gets the slot the given name is stored into, if the table contains it at all.
+    fn slot(&self, name: &str) -> usize {
+        let hashes = Hashes::new(self.seed, name);
+        let (d1, d2) = self.displacements[hashes.bucket % self.displacements.len()];
+        hashes.displace(d1, d2) % self.entries.len()
+    }
+}
+
+/// Finds a displacement for every bucket of the given names so each of them ends up in its own
+/// slot, following the "hash, displace and compress" algorithm.
+///
+/// Returns the displacements and the index of the name stored in each slot.
+fn displace(seed: u64, names: &[&str]) -> Option<(Vec<Displacement>, Vec<usize>)> {
+    let hashes = names
+        .iter()
+        .map(|name| Hashes::new(seed, name))
+        .collect::<Vec<_>>();
+    let len = names.len();
+
+    let mut buckets = vec![Vec::new(); len.div_ceil(BUCKET_SIZE)];
+    for (index, hash) in hashes.iter().enumerate() {
+        let bucket = hash.bucket % buckets.len();
+        buckets[bucket].push(index);
+    }
+
+    let mut order = (0..buckets.len()).collect::<Vec<_>>();
+    order.sort_by_key(|bucket| Reverse(buckets[*bucket].len()));
+
+    let mut displacements = vec![(0, 0); buckets.len()];
+    let mut slots = vec![None; len];
+    let mut taken = Vec::with_capacity(BUCKET_SIZE);
+
+    for bucket in order {
+        let found = (0..len as u32)
+            .flat_map(|d1| (0..len as u32).map(move |d2| (d1, d2)))
+            .find(|(d1, d2)| {
+                taken.clear();
+                buckets[bucket].iter().all(|index| {
+                    let slot = hashes[*index].displace(*d1, *d2) % len;
+                    let free = slots[slot].is_none() && !taken.contains(&slot);
+                    taken.push(slot);
+                    free
+                })
+            })?;
+
+        displacements[bucket] = found;
+        for (index, slot) in buckets[bucket].iter().zip(&taken) {
+            slots[*slot] = Some(*index);
+        }
+    }
+
+    Some((displacements, slots.into_iter().collect::<Option<_>>()?))
+}
+
+/// The hashes of a name used to find its slot in a perfect hash table.
+struct Hashes {
+    bucket: usize,
+    f1: u32,
+    f2: u32,
+}
+
+impl Hashes {
+    fn new(seed: u64, name: &str) -> Self {
+        let mut hasher = FxHasher::default();
+        hasher.write_u64(seed);
+        hasher.write(name.as_bytes());
+        let first = hasher.finish();
+        hasher.write_u8(0xff);
+        let second = hasher.finish();
+
+        Self {
+            bucket: (first >> 32) as usize,
+            f1: first as u32,
+            f2: second as u32,
+        }
+    }
+
+    fn displace(&self, d1: u32, d2: u32) -> usize {
+        d2.wrapping_add(self.f1.wrapping_mul(d1)).wrapping_add(self.f2) as usize
+    }
+}
+
+impl<V> Deref for Routes<V> {
+    type Target = HashMap<Cow<'static, str>, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<V> From<HashMap<Cow<'static, str>, V>> for Routes<V> {
+    fn from(map: HashMap<Cow<'static, str>, V>) -> Self {
+        Self::new(map)
+    }
+}
+
+impl<V: Debug> Debug for Routes<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        self.map.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Routes;
+    use std::{borrow::Cow, collections::HashMap};
+
+    fn routes(len: usize) -> Routes<usize> {
+        (0..len)
+            .map(|i| (Cow::Owned(format!("command-{}", i)), i))
+            .collect::<HashMap<_, _>>()
+            .into()
+    }
+
+    #[test]
+    fn finds_every_route() {
+        for len in [1, 2, 10, 100, 500] {
+            let routes = routes(len);
+            assert!(routes.table.is_some());
+            for i in 0..len {
+                assert_eq!(routes.get(&format!("command-{}", i)), Some(&i));
+            }
+        }
+    }
+
+    #[test]
+    fn misses_unknown_routes() {
+        let hundred = routes(100);
+        assert_eq!(hundred.get("command-100"), None);
+        assert_eq!(hundred.get(""), None);
+        assert_eq!(routes(0).get("command-0"), None);
+    }
+}
@@ -0,0 +1,388 @@
+//! Commands implemented in [Rhai](https://rhai.rs) scripts, enabled by the `rhai` feature.
+//!
+//! Scripts run in a sandboxed engine with limited operations and sizes and no access to the
+//! system, so they can be defined at runtime by server admins. Every guild can have its own
+//! script for a command, falling back to the default one.
+//!
+//! A script can use the following variables:
+//!
+//! - `args`: a map with the values of the arguments provided to the command, being ids strings.
+//! - `user_id`, `guild_id` and `channel_id`: the ids of the invocation as strings, or `()`.
+//! - `response`: the response of the command, set using `response.reply(text)` and made only
+//!   visible to the user who invoked the command using `response.ephemeral()`.
+//!
+//! If the script doesn't call `reply`, the string it evaluates to is used as the response.
+//!
+//! Scripts are evaluated on the blocking thread pool of tokio, so a script running up to its
+//! [limits](ScriptLimits) doesn't stall the other tasks of the runtime.
+use crate::{
+    command::Command,
+    context::SlashContext,
+    dynamic::{CommandBuilder, DynamicArgument},
+    parse::ParseError,
+    twilight_exports::{
+        CommandOptionType, CommandOptionValue, GuildMarker, Id, InteractionResponse,
+        InteractionResponseData, InteractionResponseType, MessageFlags,
+    },
+};
+use parking_lot::RwLock;
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use tokio::task::JoinError;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    sync::Arc,
+};
+
+/// The errors a [scripted command](ScriptCommand) can fail with.
+#[derive(Debug)]
+pub enum ScriptError {
+    /// The script failed to compile.
+    Compile(rhai::ParseError),
+    /// The script failed while running, including exceeding the [limits](ScriptLimits).
+    Runtime(Box<EvalAltResult>),
+    /// The command has no script for the guild it was invoked in.
+    NoScript,
+    /// The script didn't reply to the command.
+    NoResponse,
+    /// The task running the script panicked or was cancelled.
+    Task(JoinError),
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Compile(why) => write!(f, "The script failed to compile: {}", why),
+            Self::Runtime(why) => write!(f, "The script failed: {}", why),
+            Self::NoScript => f.write_str("The command has no script"),
+            Self::NoResponse => f.write_str("The script didn't reply to the command"),
+            Self::Task(why) => write!(f, "The script task failed: {}", why),
+        }
+    }
+}
+
+impl Error for ScriptError {}
+
+impl From<rhai::ParseError> for ScriptError {
+    fn from(why: rhai::ParseError) -> Self {
+        Self::Compile(why)
+    }
+}
+
+impl From<Box<EvalAltResult>> for ScriptError {
+    fn from(why: Box<EvalAltResult>) -> Self {
+        Self::Runtime(why)
+    }
+}
+
+/// The limits applied to the scripts run by a [script engine](ScriptEngine).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptLimits {
+    /// The maximum number of operations a single invocation can run.
+    pub operations: u64,
+    /// The maximum length of a string.
+    pub string_size: usize,
+    /// The maximum number of items of an array.
+    pub array_size: usize,
+    /// The maximum number of entries of a map.
+    pub map_size: usize,
+    /// The maximum depth of function calls.
+    pub call_levels: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            operations: 100_000,
+            string_size: 4_000,
+            array_size: 1_000,
+            map_size: 1_000,
+            call_levels: 32,
+        }
+    }
+}
+
+impl ScriptLimits {
+    /// Creates the default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of operations a single invocation can run.
+    pub fn operations(mut self, max: u64) -> Self {
+        self.operations = max;
+        self
+    }
+
+    /// Sets the maximum length of a string.
+    pub fn string_size(mut self, max: usize) -> Self {
+        self.string_size = max;
+        self
+    }
+
+    /// Sets the maximum number of items of an array.
+    pub fn array_size(mut self, max: usize) -> Self {
+        self.array_size = max;
+        self
+    }
+
+    /// Sets the maximum number of entries of a map.
+    pub fn map_size(mut self, max: usize) -> Self {
+        self.map_size = max;
+        self
+    }
+
+    /// Sets the maximum depth of function calls.
+    pub fn call_levels(mut self, max: usize) -> Self {
+        self.call_levels = max;
+        self
+    }
+}
+
+/// The response of a script, available to it as the `response` variable.
+#[derive(Debug, Clone, Default)]
+struct ScriptResponse {
+    content: Option<String>,
+    ephemeral: bool,
+}
+
+/// A sandboxed engine compiling and running the scripts of [commands](ScriptCommand).
+#[derive(Clone)]
+pub struct ScriptEngine {
+    engine: Arc<Engine>,
+}
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        Self::new(ScriptLimits::default())
+    }
+}
+
+impl ScriptEngine {
+    /// Creates an engine applying the given limits to its scripts.
+    pub fn new(limits: ScriptLimits) -> Self {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(limits.operations)
+            .set_max_string_size(limits.string_size)
+            .set_max_array_size(limits.array_size)
+            .set_max_map_size(limits.map_size)
+            .set_max_call_levels(limits.call_levels)
+            .on_print(|message| tracing::info!(target: "zephyrus::script", "{}", message))
+            .on_debug(|message, _, _| tracing::debug!(target: "zephyrus::script", "{}", message));
+        engine.disable_symbol("eval");
+
+        engine
+            .register_type_with_name::<ScriptResponse>("Response")
+            .register_fn("reply", |response: &mut ScriptResponse, content: &str| {
+                response.content = Some(content.to_string());
+            })
+            .register_fn("ephemeral", |response: &mut ScriptResponse| {
+                response.ephemeral = true;
+            });
+
+        Self {
+            engine: Arc::new(engine),
+        }
+    }
+
+    /// Creates a command with the given name and description, running its scripts using this
+    /// engine.
+    pub fn command(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
+    ) -> ScriptCommand {
+        ScriptCommand {
+            engine: self.clone(),
+            name: name.into(),
+            description: description.into(),
+            arguments: Vec::new(),
+            scripts: Default::default(),
+        }
+    }
+}
+
+/// An argument of a [scripted command](ScriptCommand).
+#[derive(Debug, Clone)]
+struct ScriptArgument {
+    name: Cow<'static, str>,
+    description: Cow<'static, str>,
+    kind: CommandOptionType,
+    required: bool,
+}
+
+/// The compiled scripts of a command, by the guild they are run in.
+type Scripts = HashMap<Option<Id<GuildMarker>>, Arc<AST>>;
+
+/// A command implemented in scripts, which can be set per guild at any time, even after the
+/// command was provided to the framework.
+///
+/// ```rust,ignore
+/// let engine = ScriptEngine::default();
+/// let greet = engine
+///     .command("greet", "Greets a user")
+///     .argument("name", "The name of the user", CommandOptionType::String, true);
+///
+/// greet.set_script(None, r#"response.reply(`Hello ${args.name}!`)"#)?;
+/// greet.set_script(Some(guild_id), r#"`Welcome to the server, ${args.name}!`"#)?;
+///
+/// let framework = Framework::builder(http_client, app_id, ())
+///     .insert_command(greet.clone().into_command())
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct ScriptCommand {
+    engine: ScriptEngine,
+    name: Cow<'static, str>,
+    description: Cow<'static, str>,
+    arguments: Vec<ScriptArgument>,
+    scripts: Arc<RwLock<Scripts>>,
+}
+
+impl ScriptCommand {
+    /// Adds an argument of the given type to the command.
+    pub fn argument(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
+        kind: CommandOptionType,
+        required: bool,
+    ) -> Self {
+        self.arguments.push(ScriptArgument {
+            name: name.into(),
+            description: description.into(),
+            kind,
+            required,
+        });
+        self
+    }
+
+    /// Compiles and sets the script run when the command is invoked in the given guild, or the
+    /// default script run in guilds without one if no guild is given.
+    pub fn set_script(
+        &self,
+        guild_id: Option<Id<GuildMarker>>,
+        source: &str,
+    ) -> Result<(), ScriptError> {
+        let ast = self.engine.engine.compile(source)?;
+        self.scripts.write().insert(guild_id, Arc::new(ast));
+        Ok(())
+    }
+
+    /// Removes the script of the given guild, or the default script if no guild is given.
+    pub fn remove_script(&self, guild_id: Option<Id<GuildMarker>>) {
+        self.scripts.write().remove(&guild_id);
+    }
+
+    /// Creates the command provided to the framework.
+    pub fn into_command<D, E>(self) -> Command<D, E>
+    where
+        E: From<ParseError> + From<ScriptError> + 'static,
+    {
+        let builder = self.arguments.iter().fold(
+            CommandBuilder::new(self.name.clone(), self.description.clone()),
+            |builder, argument| {
+                builder.argument(
+                    DynamicArgument::new(
+                        argument.name.clone(),
+                        argument.description.clone(),
+                        argument.kind,
+                    )
+                    .required(argument.required)
+                    .parser(|value| Ok(script_value(value))),
+                )
+            },
+        );
+
+        builder.handler(move |ctx: &SlashContext<D>, mut args| {
+            let mut map = Map::new();
+            for argument in &self.arguments {
+                if let Some(value) = args.take::<Dynamic>(&argument.name) {
+                    map.insert(argument.name.as_ref().into(), value);
+                }
+            }
+
+            let mut scope = Scope::new();
+            scope.push("args", map);
+            scope.push("user_id", id_value(ctx.interaction.author_id()));
+            scope.push("guild_id", id_value(ctx.interaction.guild_id));
+            scope.push(
+                "channel_id",
+                id_value(ctx.interaction.channel.as_ref().map(|channel| channel.id)),
+            );
+
+            let command = self.clone();
+            let guild_id = ctx.interaction.guild_id;
+            Box::pin(async move {
+                tokio::task::spawn_blocking(move || command.run(guild_id, scope))
+                    .await
+                    .map_err(ScriptError::Task)?
+                    .map_err(E::from)
+            })
+        })
+    }
+
+    /// Runs the script of the given guild with the given scope.
+    fn run(
+        &self,
+        guild_id: Option<Id<GuildMarker>>,
+        mut scope: Scope<'static>,
+    ) -> Result<InteractionResponse, ScriptError> {
+        // The lock is released before running the script, so scripts can be set meanwhile
+        let ast = {
+            let scripts = self.scripts.read();
+            guild_id
+                .and_then(|id| scripts.get(&Some(id)))
+                .or_else(|| scripts.get(&None))
+                .cloned()
+                .ok_or(ScriptError::NoScript)?
+        };
+
+        scope.push("response", ScriptResponse::default());
+        let output = self
+            .engine
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &ast)?;
+        let response = scope
+            .get_value::<ScriptResponse>("response")
+            .unwrap_or_default();
+
+        let content = response
+            .content
+            .or_else(|| output.into_immutable_string().ok().map(|s| s.to_string()))
+            .ok_or(ScriptError::NoResponse)?;
+
+        Ok(InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(InteractionResponseData {
+                content: Some(content),
+                flags: response.ephemeral.then_some(MessageFlags::EPHEMERAL),
+                ..Default::default()
+            }),
+        })
+    }
+}
+
+/// Converts the value of an argument into the value given to the script.
+fn script_value(value: &CommandOptionValue) -> Dynamic {
+    match value {
+        CommandOptionValue::String(s) => Dynamic::from(s.clone()),
+        CommandOptionValue::Integer(i) => Dynamic::from(*i),
+        CommandOptionValue::Number(n) => Dynamic::from(*n),
+        CommandOptionValue::Boolean(b) => Dynamic::from(*b),
+        CommandOptionValue::User(id) => Dynamic::from(id.to_string()),
+        CommandOptionValue::Channel(id) => Dynamic::from(id.to_string()),
+        CommandOptionValue::Role(id) => Dynamic::from(id.to_string()),
+        CommandOptionValue::Mentionable(id) => Dynamic::from(id.to_string()),
+        _ => Dynamic::UNIT,
+    }
+}
+
+/// Converts the given id into the value given to the script.
+fn id_value<T>(id: Option<Id<T>>) -> Dynamic {
+    id.map(|id| Dynamic::from(id.to_string()))
+        .unwrap_or(Dynamic::UNIT)
+}
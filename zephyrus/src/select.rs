@@ -0,0 +1,45 @@
+use crate::{
+    parse::ParseError,
+    twilight_exports::{Interaction, InteractionData, SelectMenuOption},
+};
+
+/// A type whose values can be chosen using a string select menu.
+///
+/// This is usually implemented for fieldless enums using the `SelectOptions` derive, which
+/// allows setting the `label`, `value`, `description` and `emoji` of every option using
+/// attributes on its variant, and marking options selected by default using `#[selected]`.
+pub trait SelectOptions: Sized {
+    /// Gets the options of the select menu.
+    fn options() -> Vec<SelectMenuOption>;
+
+    /// Parses the given selected value, returning `None` if it doesn't correspond to any option.
+    fn from_value(value: &str) -> Option<Self>;
+
+    /// Gets the value of the option corresponding to this item.
+    fn value(&self) -> &'static str;
+
+    /// Parses all the given selected values.
+    fn from_values(values: &[String]) -> Result<Vec<Self>, ParseError> {
+        values
+            .iter()
+            .map(|value| {
+                Self::from_value(value).ok_or_else(|| ParseError::Parsing {
+                    argument_name: String::new(),
+                    required: true,
+                    type_: std::any::type_name::<Self>().to_string(),
+                    error: format!("Unrecognized option {}", value),
+                })
+            })
+            .collect()
+    }
+
+    /// Parses the values selected in the given interaction, which must be a message component one.
+    fn from_interaction(interaction: &Interaction) -> Result<Vec<Self>, ParseError> {
+        match interaction.data.as_ref() {
+            Some(InteractionData::MessageComponent(data)) => Self::from_values(&data.values),
+            _ => Err(ParseError::StructureMismatch(String::from(
+                "Expected a message component interaction",
+            ))),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+/// A pointer to a function used to get the latency of a shard.
+pub(crate) type LatencyFn = Arc<dyn Fn() -> Option<Duration> + Send + Sync>;
+
+/// Information about the gateway shard an interaction was received on.
+///
+/// This is provided to the framework when using
+/// [process_with_shard](crate::framework::Framework::process_with_shard) and then exposed to the
+/// commands through the [context](crate::context::SlashContext).
+#[derive(Clone)]
+pub struct ShardInfo {
+    /// The id of the shard.
+    pub id: u32,
+    /// A function used to get the current latency of the shard.
+    latency: Option<LatencyFn>,
+}
+
+impl ShardInfo {
+    /// Creates a new [shard info](self::ShardInfo) with the given shard id.
+    pub fn new(id: u32) -> Self {
+        Self { id, latency: None }
+    }
+
+    /// Sets the function used to get the latency of the shard, this is usually a closure
+    /// calling twilight's `Shard::latency` and returning its most recent value.
+    pub fn latency_getter<F>(mut self, fun: F) -> Self
+    where
+        F: Fn() -> Option<Duration> + Send + Sync + 'static,
+    {
+        self.latency = Some(Arc::new(fun));
+        self
+    }
+
+    /// Gets the current latency of the shard, returning `None` if no latency getter was provided
+    /// or the shard didn't report any latency yet.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency.as_ref().and_then(|fun| fun())
+    }
+}
+
+impl Debug for ShardInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShardInfo")
+            .field("id", &self.id)
+            .field("latency", &self.latency())
+            .finish()
+    }
+}
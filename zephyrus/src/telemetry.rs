@@ -0,0 +1,66 @@
+//! Spans and metrics following the OpenTelemetry conventions, exported through `tracing` so
+//! they can be forwarded to an OpenTelemetry collector using `tracing-opentelemetry`.
+//!
+//! Every interaction is dispatched inside a `zephyrus.dispatch` span, which stays entered while
+//! the command handler runs, so the spans created by handlers are children of it and the
+//! OpenTelemetry context of the interaction is available through `Span::current()`.
+//!
+//! Spans only record ids, types and command paths, never the values of arguments, so the
+//! [sensitive](crate::argument::CommandArgument::sensitive) ones don't need to be redacted.
+
+use crate::twilight_exports::Interaction;
+use std::time::Duration;
+use tracing::{field::Empty, info, info_span, Span};
+
+/// Creates the span an interaction is dispatched in.
+pub(crate) fn dispatch_span(interaction: &Interaction) -> Span {
+    let span = info_span!(
+        "zephyrus.dispatch",
+        otel.name = "interaction",
+        otel.kind = "server",
+        otel.status_code = Empty,
+        interaction.id = interaction.id.get(),
+        interaction.kind = ?interaction.kind,
+        command.path = Empty,
+        guild.id = Empty,
+        channel.id = Empty,
+        user.id = Empty,
+    );
+
+    if let Some(guild_id) = interaction.guild_id {
+        span.record("guild.id", guild_id.get());
+    }
+    if let Some(channel) = &interaction.channel {
+        span.record("channel.id", channel.id.get());
+    }
+    if let Some(user_id) = interaction.author_id() {
+        span.record("user.id", user_id.get());
+    }
+
+    span
+}
+
+/// Creates the span the handler of the command with the given path runs in, recording the path
+/// in the current dispatch span.
+pub(crate) fn handler_span(path: &str) -> Span {
+    let current = Span::current();
+    current.record("otel.name", path);
+    current.record("command.path", path);
+
+    info_span!("zephyrus.handler", otel.name = path, command.path = path)
+}
+
+/// Records the outcome of the handler of the command with the given path, marking the current
+/// span as failed if it didn't succeed and emitting the metrics of the execution.
+pub(crate) fn record_handler(path: &str, elapsed: Duration, success: bool) {
+    if !success {
+        Span::current().record("otel.status_code", "ERROR");
+    }
+
+    info!(
+        monotonic_counter.zephyrus.commands = 1u64,
+        histogram.zephyrus.command.duration = elapsed.as_secs_f64(),
+        command.path = path,
+        success,
+    );
+}
@@ -0,0 +1,392 @@
+use crate::{
+    argument::CommandArgument,
+    command::Command,
+    context::SlashContext,
+    payload::CommandPayload,
+    twilight_exports::{
+        AllowedMentions, CommandDataOption, CommandOption, CommandOptionType, CommandOptionValue,
+        Interaction, InteractionData, InteractionResponse, InteractionResponseData,
+        InteractionResponseType, MessageFlags,
+    },
+    BoxFuture,
+};
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::PathBuf,
+    time::SystemTime,
+};
+
+/// A string argument of a [templated command](TemplateCommand).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateArgument {
+    /// The name of the argument, used as the `{name}` placeholder.
+    pub name: String,
+    /// The description of the argument.
+    pub description: String,
+    /// Whether the argument is required.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// A simple command replying with a template, such as the custom tags of a guild.
+///
+/// The template can use the `{name}` placeholder of every argument, alongside the `{user}`
+/// mention of the user who invoked the command and the `{user_id}`, `{guild_id}` and
+/// `{channel_id}` placeholders. The placeholders of optional arguments not provided are left
+/// empty, while unknown placeholders are kept as they are.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateCommand {
+    /// The name of the command.
+    pub name: String,
+    /// The description of the command.
+    pub description: String,
+    /// The template of the response.
+    pub response: String,
+    /// Whether the response is only seen by the user who invoked the command.
+    #[serde(default)]
+    pub ephemeral: bool,
+    /// The arguments of the command.
+    #[serde(default)]
+    pub arguments: Vec<TemplateArgument>,
+}
+
+impl TemplateCommand {
+    /// Creates the payload used to register the command.
+    pub fn payload(&self) -> CommandPayload {
+        CommandPayload {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            options: self
+                .arguments
+                .iter()
+                .map(|argument| CommandOption {
+                    autocomplete: None,
+                    channel_types: None,
+                    choices: None,
+                    description: argument.description.clone(),
+                    description_localizations: None,
+                    kind: CommandOptionType::String,
+                    max_length: None,
+                    max_value: None,
+                    min_length: None,
+                    min_value: None,
+                    name: argument.name.clone(),
+                    name_localizations: None,
+                    options: None,
+                    required: Some(argument.required),
+                })
+                .collect(),
+            default_member_permissions: None,
+        }
+    }
+
+    /// Creates the [command](Command) replying with the rendered template, which the framework
+    /// executes like any other command, so hooks, checks, concurrency limits, cooldowns and the
+    /// audit layer apply to it as well.
+    pub fn into_command<D, E>(self) -> Command<D, E> {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|argument| CommandArgument {
+                name: argument.name.clone().into(),
+                description: argument.description.clone().into(),
+                required: argument.required,
+                kind: CommandOptionType::String,
+                choices: None,
+                limits: None,
+                channel_types: None,
+                autocomplete: None,
+                sensitive: false,
+            })
+            .collect();
+        let name = self.name.clone();
+        let description = self.description.clone();
+        let ephemeral = self.ephemeral;
+
+        let mut command = Command::new(move |ctx: &SlashContext<D>| {
+            let options = match ctx.interaction.data.as_ref() {
+                Some(InteractionData::ApplicationCommand(data)) => data.options.as_slice(),
+                _ => &[],
+            };
+            let response = self.render(&ctx.interaction, options);
+            Box::pin(async move { Ok(response) })
+        })
+        .name(name)
+        .description(description)
+        .ephemeral(ephemeral);
+
+        command.arguments = arguments;
+        command
+    }
+
+    /// Renders the response of the command for the given interaction and options.
+    ///
+    /// The template is scanned once, so placeholders contained in the values of the arguments
+    /// are kept as they are instead of being expanded. The values are provided by the user, so
+    /// the response doesn't allow any mention, regardless of the
+    /// [default allowed mentions](crate::builder::FrameworkBuilder::allowed_mentions).
+    pub fn render(&self, interaction: &Interaction, options: &[CommandDataOption]) -> InteractionResponse {
+        let user_id = interaction.author_id().map(|id| id.to_string());
+        let content = fill(&self.response, |name| {
+            if let Some(argument) = self.arguments.iter().find(|argument| argument.name == name) {
+                let value = options
+                    .iter()
+                    .find(|option| option.name == argument.name)
+                    .and_then(|option| match &option.value {
+                        CommandOptionValue::String(value) => Some(value.clone()),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                return Some(value);
+            }
+
+            let id = |id: Option<String>| id.unwrap_or_default();
+            Some(match name {
+                "user" => user_id.as_ref().map(|id| format!("<@{}>", id)).unwrap_or_default(),
+                "user_id" => id(user_id.clone()),
+                "guild_id" => id(interaction.guild_id.map(|id| id.to_string())),
+                "channel_id" => id(interaction.channel.as_ref().map(|channel| channel.id.to_string())),
+                _ => return None,
+            })
+        });
+
+        InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(InteractionResponseData {
+                content: Some(content),
+                flags: self.ephemeral.then_some(MessageFlags::EPHEMERAL),
+                allowed_mentions: Some(AllowedMentions::default()),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// Fills the `{name}` placeholders of the given template with the values returned by the given
+/// function in a single pass, leaving the placeholders it returns `None` for untouched.
+fn fill(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut content = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        content.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let filled = after
+            .find('}')
+            .and_then(|end| value(&after[..end]).map(|value| (end, value)));
+
+        match filled {
+            Some((end, value)) => {
+                content.push_str(&value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                content.push('{');
+                rest = after;
+            }
+        }
+    }
+    content.push_str(rest);
+    content
+}
+
+/// A source of [templated commands](TemplateCommand) consulted by the framework to add, update
+/// and remove commands at runtime, such as a file or a database.
+#[async_trait]
+pub trait DynamicCommandSource: Send + Sync {
+    /// Loads every command currently provided by the source.
+    async fn load(&self) -> Result<Vec<TemplateCommand>, Box<dyn Error + Send + Sync>>;
+}
+
+/// A [source](DynamicCommandSource) reading the commands out of a JSON file containing an array
+/// of [templated commands](TemplateCommand), which is only read again once modified.
+///
+/// The file is read using `tokio::fs`, so it must be loaded within a tokio runtime.
+#[derive(Debug)]
+pub struct FileSource {
+    path: PathBuf,
+    cache: Mutex<Option<(SystemTime, Vec<TemplateCommand>)>>,
+}
+
+impl FileSource {
+    /// Creates a source reading the file at the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            cache: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl DynamicCommandSource for FileSource {
+    async fn load(&self) -> Result<Vec<TemplateCommand>, Box<dyn Error + Send + Sync>> {
+        let modified = tokio::fs::metadata(&self.path).await?.modified()?;
+        if let Some((cached, commands)) = self.cache.lock().as_ref() {
+            if *cached == modified {
+                return Ok(commands.clone());
+            }
+        }
+
+        let commands: Vec<TemplateCommand> = serde_json::from_slice(&tokio::fs::read(&self.path).await?)?;
+        *self.cache.lock() = Some((modified, commands.clone()));
+        Ok(commands)
+    }
+}
+
+/// A function loading the commands of a [polling source](PollingSource).
+pub type LoadFn = Box<
+    dyn Fn() -> BoxFuture<'static, Result<Vec<TemplateCommand>, Box<dyn Error + Send + Sync>>>
+        + Send
+        + Sync,
+>;
+
+/// A [source](DynamicCommandSource) calling the given function every time it's consulted, such
+/// as one querying a database.
+pub struct PollingSource(LoadFn);
+
+impl PollingSource {
+    /// Creates a source loading the commands using the given function.
+    pub fn new<F>(fun: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, Result<Vec<TemplateCommand>, Box<dyn Error + Send + Sync>>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self(Box::new(fun))
+    }
+}
+
+#[async_trait]
+impl DynamicCommandSource for PollingSource {
+    async fn load(&self) -> Result<Vec<TemplateCommand>, Box<dyn Error + Send + Sync>> {
+        (self.0)().await
+    }
+}
+
+/// The changes made to the [dynamic commands](DynamicCommands) by a reload.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DynamicChanges {
+    /// The commands which were added.
+    pub added: Vec<TemplateCommand>,
+    /// The commands which were changed.
+    pub updated: Vec<TemplateCommand>,
+    /// The commands which were removed.
+    pub removed: Vec<TemplateCommand>,
+}
+
+impl DynamicChanges {
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The commands loaded out of a [source](DynamicCommandSource), which the framework executes
+/// when no other command matches the invoked one.
+pub struct DynamicCommands {
+    source: Box<dyn DynamicCommandSource>,
+    commands: RwLock<HashMap<String, TemplateCommand>>,
+}
+
+impl DynamicCommands {
+    /// Creates the commands of the given source, which aren't loaded until
+    /// [reloaded](Self::reload).
+    pub fn new(source: impl DynamicCommandSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            commands: Default::default(),
+        }
+    }
+
+    /// Gets the command with the given name.
+    pub fn get(&self, name: &str) -> Option<TemplateCommand> {
+        self.commands.read().get(name).cloned()
+    }
+
+    /// Gets every loaded command.
+    pub fn commands(&self) -> Vec<TemplateCommand> {
+        self.commands.read().values().cloned().collect()
+    }
+
+    /// Loads the commands of the source again, returning the changes made.
+    pub async fn reload(&self) -> Result<DynamicChanges, Box<dyn Error + Send + Sync>> {
+        let changes = self.changes().await?;
+        self.apply(&changes);
+        Ok(changes)
+    }
+
+    /// Loads the commands of the source again, returning how they differ from the loaded ones
+    /// without changing them, so they can be [applied](Self::apply) once synchronized.
+    pub async fn changes(&self) -> Result<DynamicChanges, Box<dyn Error + Send + Sync>> {
+        let loaded = self.source.load().await?;
+        let commands = self.commands.read();
+        let mut changes = DynamicChanges::default();
+
+        for command in &loaded {
+            match commands.get(&command.name) {
+                Some(existing) if existing == command => (),
+                Some(_) => changes.updated.push(command.clone()),
+                None => changes.added.push(command.clone()),
+            }
+        }
+
+        changes.removed = commands
+            .values()
+            .filter(|existing| !loaded.iter().any(|command| command.name == existing.name))
+            .cloned()
+            .collect();
+
+        Ok(changes)
+    }
+
+    /// Applies the given changes to the loaded commands, adding or replacing the added and
+    /// updated ones and dropping the removed ones.
+    pub fn apply(&self, changes: &DynamicChanges) {
+        let mut commands = self.commands.write();
+        for command in changes.added.iter().chain(&changes.updated) {
+            commands.insert(command.name.clone(), command.clone());
+        }
+        for command in &changes.removed {
+            commands.remove(&command.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fill;
+
+    fn render(template: &str, text: &str) -> String {
+        fill(template, |name| match name {
+            "text" => Some(text.to_string()),
+            "user" => Some(String::from("<@1>")),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn fills_placeholders() {
+        assert_eq!(render("{user} said {text}", "hi"), "<@1> said hi");
+        assert_eq!(render("{text}{text}", "a"), "aa");
+        assert_eq!(render("no placeholders", "a"), "no placeholders");
+    }
+
+    #[test]
+    fn keeps_unknown_placeholders() {
+        assert_eq!(render("{other} {text}", "a"), "{other} a");
+        assert_eq!(render("{{text}}", "a"), "{a}");
+        assert_eq!(render("{text", "a"), "{text");
+        assert_eq!(render("}{", "a"), "}{");
+    }
+
+    #[test]
+    fn values_are_not_expanded() {
+        assert_eq!(render("{text}", "{user}"), "{user}");
+        assert_eq!(render("{text} {user}", "{text}"), "{text} <@1>");
+    }
+}
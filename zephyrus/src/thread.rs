@@ -0,0 +1,48 @@
+use crate::context::SlashContext;
+use crate::message::Message;
+use crate::twilight_exports::{ChannelMarker, Id};
+use std::error::Error;
+
+/// A handle to a thread created by a [context](SlashContext), allowing to send messages into it
+/// and to archive it.
+pub struct Thread<'a, T> {
+    id: Id<ChannelMarker>,
+    context: &'a SlashContext<'a, T>,
+}
+
+impl<'a, T> Thread<'a, T> {
+    /// Creates a new [thread](self::Thread) handle.
+    pub(crate) fn new(context: &'a SlashContext<'a, T>, id: Id<ChannelMarker>) -> Self {
+        Self { id, context }
+    }
+
+    /// Gets the id of the thread.
+    pub fn id(&self) -> Id<ChannelMarker> {
+        self.id
+    }
+
+    /// Sends a message with the given content into the thread, returning the sent message.
+    pub async fn send(&self, content: &str) -> Result<Message<'a, T>, Box<dyn Error + Send + Sync>> {
+        let message = self
+            .context
+            .http_client()
+            .create_message(self.id)
+            .content(content)
+            .await?
+            .model()
+            .await?;
+
+        Ok(Message::new(self.context, message))
+    }
+
+    /// Archives the thread.
+    pub async fn archive(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.context
+            .http_client()
+            .update_thread(self.id)
+            .archived(true)
+            .await?;
+
+        Ok(())
+    }
+}
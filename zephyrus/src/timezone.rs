@@ -0,0 +1,51 @@
+//! Support for [timezones](Tz) as command arguments, enabled by the `timezone` feature.
+//!
+//! Timezones are string options accepting the name of a timezone of the IANA database, such as
+//! `Europe/Madrid`, ignoring case. There are far more timezones than the choices an option can
+//! have, so use [suggestions] from an autocomplete function to offer them.
+
+use crate::parse_impl::error;
+use crate::prelude::*;
+use crate::twilight_exports::*;
+pub use chrono_tz::Tz;
+
+/// The maximum number of choices discord allows an option to have.
+const MAX_CHOICES: usize = 25;
+
+/// Creates the autocomplete response suggesting the timezones whose name contains the given
+/// input, for use in an [autocomplete](crate::context::AutocompleteContext) function.
+pub fn suggestions(input: &str) -> InteractionResponseData {
+    let input = input.to_lowercase().replace(' ', "_");
+    let choices = chrono_tz::TZ_VARIANTS
+        .iter()
+        .filter(|tz| tz.name().to_lowercase().contains(&input))
+        .take(MAX_CHOICES)
+        .map(|tz| CommandOptionChoice {
+            name: tz.name().to_string(),
+            name_localizations: None,
+            value: CommandOptionChoiceValue::String(tz.name().to_string()),
+        })
+        .collect();
+
+    InteractionResponseData {
+        choices: Some(choices),
+        ..Default::default()
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for Tz {
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let input = String::parse(http_client, data, value).await?;
+        Tz::from_str_insensitive(input.trim())
+            .map_err(|_| error("Timezone", true, &format!("{} is not a known timezone", input)))
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+}
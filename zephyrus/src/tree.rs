@@ -0,0 +1,132 @@
+use crate::{
+    command::{Command, CommandPath},
+    framework::Framework,
+    group::{CommandGroup, GroupParent, ParentType},
+    twilight_exports::Permissions,
+};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// An executable command of the framework's command tree, alongside the groups it belongs to.
+pub struct CommandEntry<'a, D, E> {
+    /// The path used to invoke the command.
+    pub path: CommandPath<'a>,
+    /// The command itself.
+    pub command: &'a Command<D, E>,
+    /// The parent group of the command, if the command is a subcommand.
+    pub parent: Option<&'a GroupParent<D, E>>,
+    /// The subcommand group of the command, if the command is inside of one.
+    pub group: Option<&'a CommandGroup<D, E>>,
+}
+
+impl<D, E> Debug for CommandEntry<'_, D, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("CommandEntry")
+            .field("path", &self.path)
+            .field("command", &self.command.name)
+            .field("parent", &self.parent.map(|parent| &parent.name))
+            .field("group", &self.group.map(|group| &group.name))
+            .finish()
+    }
+}
+
+impl<D, E> Clone for CommandEntry<'_, D, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D, E> Copy for CommandEntry<'_, D, E> {}
+
+impl<'a, D, E> CommandEntry<'a, D, E> {
+    /// Gets the permissions required to execute this command.
+    ///
+    /// As discord only allows setting permissions at top level commands, subcommands use the
+    /// permissions of their parent group.
+    pub fn required_permissions(&self) -> Option<Permissions> {
+        match self.parent {
+            Some(parent) => parent.required_permissions,
+            None => self.command.required_permissions,
+        }
+    }
+
+    /// Gets the category of this command, falling back to the category of its parent group.
+    pub fn category(&self) -> Option<&'static str> {
+        self.command
+            .category
+            .or_else(|| self.parent.and_then(|parent| parent.category))
+    }
+
+    /// Whether this command is a subcommand.
+    pub fn is_subcommand(&self) -> bool {
+        self.parent.is_some()
+    }
+}
+
+impl<D, E> Framework<D, E> {
+    /// Iterates over all the executable commands registered in the framework, including the
+    /// subcommands of every group, sorted by their [path](CommandPath).
+    pub fn iter_commands(&self) -> impl Iterator<Item = CommandEntry<'_, D, E>> {
+        let mut entries = Vec::new();
+
+        for command in self.commands.values() {
+            entries.push(CommandEntry {
+                path: CommandPath::new(&command.name),
+                command,
+                parent: None,
+                group: None,
+            });
+        }
+
+        for parent in self.groups.values() {
+            match &parent.kind {
+                ParentType::Simple(map) => {
+                    for command in map.values() {
+                        entries.push(CommandEntry {
+                            path: CommandPath {
+                                command: &parent.name,
+                                group: None,
+                                subcommand: Some(&command.name),
+                            },
+                            command,
+                            parent: Some(parent),
+                            group: None,
+                        });
+                    }
+                }
+                ParentType::Group(map) => {
+                    for group in map.values() {
+                        for command in group.subcommands.values() {
+                            entries.push(CommandEntry {
+                                path: CommandPath {
+                                    command: &parent.name,
+                                    group: Some(&group.name),
+                                    subcommand: Some(&command.name),
+                                },
+                                command,
+                                parent: Some(parent),
+                                group: Some(group),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        entries.sort_by_key(|entry| entry.path);
+        entries.into_iter()
+    }
+
+    /// Gets the number of executable commands registered in the framework.
+    pub fn command_count(&self) -> usize {
+        let subcommands = self
+            .groups
+            .values()
+            .map(|parent| match &parent.kind {
+                ParentType::Simple(map) => map.len(),
+                ParentType::Group(map) => map.values().map(CommandGroup::len).sum(),
+            })
+            .sum::<usize>();
+
+        self.commands.len() + subcommands
+    }
+}
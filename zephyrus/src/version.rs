@@ -0,0 +1,40 @@
+use crate::parse_impl::error;
+use crate::prelude::*;
+use crate::twilight_exports::*;
+use semver::{Version, VersionReq};
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for Version {
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let input = String::parse(http_client, data, value).await?;
+        Version::parse(input.trim().trim_start_matches('v')).map_err(|why| {
+            error("Version", true, &format!("{} is not a valid version: {}", input, why))
+        })
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+}
+
+#[async_trait]
+impl<T: Send + Sync> Parse<T> for VersionReq {
+    async fn parse(
+        http_client: &WrappedClient,
+        data: &T,
+        value: Option<&CommandOptionValue>,
+    ) -> Result<Self, ParseError> {
+        let input = String::parse(http_client, data, value).await?;
+        VersionReq::parse(input.trim()).map_err(|why| {
+            error("VersionReq", true, &format!("{} is not a valid version requirement: {}", input, why))
+        })
+    }
+
+    fn kind() -> CommandOptionType {
+        CommandOptionType::String
+    }
+}
@@ -1,18 +1,35 @@
 use std::{future::Future, task::{Context, Poll}};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::pin::Pin;
+use parking_lot::Mutex;
 use tokio::sync::oneshot::{Sender, Receiver, channel};
-use crate::{framework::Framework, twilight_exports::Interaction};
+use crate::context::ContextError;
+use crate::twilight_exports::{
+    ComponentType, Id, Interaction, InteractionData, InteractionDataResolved, Message,
+    MessageMarker, User, UserMarker
+};
+#[cfg(feature = "cancellation")]
+use std::future::poll_fn;
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
+
+/// A pointer to a function used by [waiter wakers](WaiterWaker) to check if an interaction
+/// should wake them.
+pub(crate) type WaiterPredicate<T> = Box<dyn Fn(&T, &Interaction) -> bool + Send + 'static>;
 
 pub(crate) fn new_pair<F, T>(fun: F) -> (WaiterWaker<T>, InteractionWaiter)
 where
-    F: Fn(&Framework<T>, &Interaction) -> bool + Send + 'static
+    F: Fn(&T, &Interaction) -> bool + Send + 'static
 {
     let (sender, receiver) = channel();
 
     (
         WaiterWaker {
             predicate: Box::new(fun),
-            sender
+            sender,
+            user: None,
+            message: None
         },
         InteractionWaiter {
             receiver
@@ -20,8 +37,242 @@ where
     )
 }
 
+/// The errors an [interaction waiter](InteractionWaiter) can resolve to instead of an
+/// interaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaiterError {
+    /// The waiter was rejected on registration because a [limit](WaiterLimits) was reached.
+    LimitReached,
+    /// The waiter was evicted by a newer one because a [limit](WaiterLimits) was reached.
+    Evicted,
+    /// The waiter was cancelled through its cancellation token.
+    Cancelled
+}
+
+impl Display for WaiterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::LimitReached => write!(f, "The waiter limit was reached"),
+            Self::Evicted => write!(f, "The waiter was evicted by a newer one"),
+            Self::Cancelled => write!(f, "The waiter was cancelled")
+        }
+    }
+}
+
+impl Error for WaiterError {}
+
+/// What happens when registering a waiter would exceed one of the [limits](WaiterLimits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaiterOverflow {
+    /// The oldest waiter counting towards the limit is evicted, resolving to
+    /// [WaiterError::Evicted](WaiterError::Evicted).
+    #[default]
+    EvictOldest,
+    /// The new waiter is rejected, resolving to
+    /// [WaiterError::LimitReached](WaiterError::LimitReached).
+    Reject
+}
+
+/// The maximum number of waiters which can be waiting at the same time, preventing a command
+/// from registering waiters without bound. No limit is applied by default.
+///
+/// The user and message limits apply to the waiters registered through a
+/// [context](crate::context::SlashContext), which count towards the user who invoked the
+/// interaction and, for component interactions, the message the component belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WaiterLimits {
+    /// The maximum number of waiters across the framework.
+    pub total: Option<usize>,
+    /// The maximum number of waiters registered by the interactions of a user.
+    pub per_user: Option<usize>,
+    /// The maximum number of waiters registered by the components of a message.
+    pub per_message: Option<usize>,
+    /// What happens when a limit is reached.
+    pub overflow: WaiterOverflow
+}
+
+impl WaiterLimits {
+    /// Creates limits which don't limit waiters at all.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of waiters across the framework.
+    pub fn total(mut self, max: usize) -> Self {
+        self.total = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of waiters registered by the interactions of a user.
+    pub fn per_user(mut self, max: usize) -> Self {
+        self.per_user = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of waiters registered by the components of a message.
+    pub fn per_message(mut self, max: usize) -> Self {
+        self.per_message = Some(max);
+        self
+    }
+
+    /// Sets what happens when a limit is reached.
+    pub fn overflow(mut self, overflow: WaiterOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// The waiters registered in the framework, kept within its [limits](WaiterLimits).
+pub struct Waiters<T> {
+    wakers: Mutex<Vec<WaiterWaker<T>>>,
+    limits: WaiterLimits
+}
+
+impl<T> Waiters<T> {
+    pub(crate) fn new(limits: WaiterLimits) -> Self {
+        Self {
+            wakers: Mutex::new(Vec::new()),
+            limits
+        }
+    }
+
+    /// Gets the limits applied to the waiters.
+    pub fn limits(&self) -> WaiterLimits {
+        self.limits
+    }
+
+    /// Gets the number of waiters waiting for an interaction.
+    pub fn len(&self) -> usize {
+        self.wakers.lock().len()
+    }
+
+    /// Whether no waiter is waiting for an interaction.
+    pub fn is_empty(&self) -> bool {
+        self.wakers.lock().is_empty()
+    }
+
+    /// Registers the given waker, counting it towards the user and message of the given
+    /// interaction if any.
+    pub(crate) fn register(&self, mut waker: WaiterWaker<T>, interaction: Option<&Interaction>) {
+        if let Some(interaction) = interaction {
+            waker.user = interaction.author_id();
+            waker.message = interaction.message.as_ref().map(|message| message.id);
+        }
+
+        self.prune();
+        let mut wakers = self.wakers.lock();
+
+        let limits = [
+            (self.limits.per_message, waker.message.map(Scope::Message)),
+            (self.limits.per_user, waker.user.map(Scope::User)),
+            (self.limits.total, Some(Scope::Total))
+        ];
+
+        for (max, scope) in limits {
+            let (max, scope) = match (max, scope) {
+                (Some(max), Some(scope)) => (max, scope),
+                _ => continue
+            };
+
+            if max == 0 {
+                waker.fail(WaiterError::LimitReached);
+                return;
+            }
+
+            if wakers.iter().filter(|waker| scope.contains(waker)).count() < max {
+                continue;
+            }
+
+            match self.limits.overflow {
+                WaiterOverflow::Reject => {
+                    waker.fail(WaiterError::LimitReached);
+                    return;
+                }
+                WaiterOverflow::EvictOldest => {
+                    if let Some(position) = wakers.iter().position(|waker| scope.contains(waker)) {
+                        wakers.remove(position).fail(WaiterError::Evicted);
+                    }
+                }
+            }
+        }
+
+        wakers.push(waker);
+    }
+
+    /// Removes the wakers whose waiter was dropped.
+    pub(crate) fn prune(&self) {
+        self.wakers.lock().retain(|waker| !waker.sender.is_closed());
+    }
+
+    /// Waits for the given waiter to resolve, resolving it to
+    /// [WaiterError::Cancelled](WaiterError::Cancelled) and removing its waker as soon as the
+    /// given token is cancelled.
+    #[cfg(feature = "cancellation")]
+    pub(crate) async fn wait_until_cancelled<W, O>(
+        &self,
+        waiter: W,
+        token: &CancellationToken
+    ) -> Result<O, Box<dyn Error + Send + Sync>>
+    where
+        W: Future<Output = Result<O, Box<dyn Error + Send + Sync>>> + Unpin
+    {
+        let mut waiter = Some(waiter);
+        let cancelled = token.cancelled();
+        tokio::pin!(cancelled);
+
+        let result = poll_fn(|cx| {
+            if cancelled.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+            Pin::new(waiter.as_mut().unwrap()).poll(cx).map(Some)
+        })
+        .await;
+
+        match result {
+            Some(result) => result,
+            None => {
+                drop(waiter.take());
+                self.prune();
+                Err(Box::new(WaiterError::Cancelled))
+            }
+        }
+    }
+
+    /// Wakes the first waiter satisfied by the given interaction, giving the interaction back if
+    /// no waiter was woken.
+    pub(crate) fn wake(&self, data: &T, interaction: Interaction) -> Option<Interaction> {
+        let mut lock = self.wakers.lock();
+        match lock.iter().position(|waker| waker.check(data, &interaction)) {
+            Some(position) => {
+                lock.remove(position).wake(interaction);
+                None
+            }
+            None => Some(interaction),
+        }
+    }
+}
+
+/// The waiters a [limit](WaiterLimits) counts.
+#[derive(Clone, Copy)]
+enum Scope {
+    Total,
+    User(Id<UserMarker>),
+    Message(Id<MessageMarker>)
+}
+
+impl Scope {
+    fn contains<T>(&self, waker: &WaiterWaker<T>) -> bool {
+        match self {
+            Self::Total => true,
+            Self::User(user) => waker.user == Some(*user),
+            Self::Message(message) => waker.message == Some(*message)
+        }
+    }
+}
+
+/// A future resolving to the interaction which satisfied the waiter's predicate.
 pub struct InteractionWaiter {
-    receiver: Receiver<Interaction>
+    receiver: Receiver<Result<Interaction, WaiterError>>
 }
 
 impl Future for InteractionWaiter {
@@ -29,23 +280,101 @@ impl Future for InteractionWaiter {
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         Pin::new(&mut self.receiver).poll(cx)
-            .map_err(|e| {
-                Box::new(e) as Box<_>
+            .map(|result| match result {
+                Ok(Ok(interaction)) => Ok(interaction),
+                Ok(Err(e)) => Err(Box::new(e) as Box<_>),
+                Err(e) => Err(Box::new(e) as Box<_>)
             })
     }
 }
 
+/// A component interaction received by a [component waiter](ComponentWaiter), with its data
+/// already extracted.
+#[derive(Debug, Clone)]
+pub struct ComponentInteraction {
+    /// The custom id of the component.
+    pub custom_id: String,
+    /// The type of the component.
+    pub kind: ComponentType,
+    /// The values selected in the select menu, empty if the component isn't one.
+    pub values: Vec<String>,
+    /// The users, roles, channels and members selected in the select menu.
+    pub resolved: Option<InteractionDataResolved>,
+    /// The message the component belongs to.
+    pub message: Option<Message>,
+    /// The user who used the component.
+    pub user: Option<User>,
+    /// The interaction itself, used to respond it.
+    pub interaction: Interaction
+}
+
+impl ComponentInteraction {
+    /// Extracts the data of the given component interaction.
+    pub fn new(mut interaction: Interaction) -> Result<Self, ContextError> {
+        let data = match interaction.data.take() {
+            Some(InteractionData::MessageComponent(data)) => *data,
+            _ => return Err(ContextError::MissingField("data"))
+        };
+
+        Ok(Self {
+            custom_id: data.custom_id,
+            kind: data.component_type,
+            values: data.values,
+            resolved: data.resolved,
+            message: interaction.message.clone(),
+            user: interaction.author().cloned(),
+            interaction
+        })
+    }
+
+    /// Gets the selected values as ids, such as the ones of user, role, channel and
+    /// mentionable select menus, skipping the values which aren't ids.
+    pub fn selected_ids<T>(&self) -> Vec<Id<T>> {
+        self.values.iter().filter_map(|value| value.parse().ok()).collect()
+    }
+}
+
+/// A future resolving to the component interaction which satisfied the waiter's predicate.
+pub struct ComponentWaiter {
+    inner: InteractionWaiter
+}
+
+impl ComponentWaiter {
+    pub(crate) fn new(inner: InteractionWaiter) -> Self {
+        Self {
+            inner
+        }
+    }
+}
+
+impl Future for ComponentWaiter {
+    type Output = Result<ComponentInteraction, Box<dyn std::error::Error + Send + Sync>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.inner).poll(cx)
+            .map(|result| Ok(ComponentInteraction::new(result?)?))
+    }
+}
+
+/// The framework-side half of a waiter, used to wake the [waiter](InteractionWaiter).
 pub struct WaiterWaker<T> {
-    pub predicate: Box<dyn Fn(&Framework<T>, &Interaction) -> bool + Send + 'static>,
-    pub sender: Sender<Interaction>
+    pub predicate: WaiterPredicate<T>,
+    pub sender: Sender<Result<Interaction, WaiterError>>,
+    user: Option<Id<UserMarker>>,
+    message: Option<Id<MessageMarker>>
 }
 
 impl<T> WaiterWaker<T> {
-    pub fn check(&self, framework: &Framework<T>, interaction: &Interaction) -> bool {
-        (self.predicate)(framework, interaction)
+    pub fn check(&self, data: &T, interaction: &Interaction) -> bool {
+        (self.predicate)(data, interaction)
     }
 
     pub fn wake(self, interaction: Interaction) {
-        let _ = self.sender.send(interaction);
+        let _ = self.sender.send(Ok(interaction));
+    }
+
+    /// Resolves the waiter to the given error.
+    fn fail(self, error: WaiterError) {
+        let _ = self.sender.send(Err(error));
     }
 }
@@ -0,0 +1,14 @@
+use zephyrus::prelude::*;
+
+struct NotParse;
+
+#[command]
+#[description = "Says hello"]
+async fn hello(
+    _ctx: &SlashContext<()>,
+    #[description = "The name"] _name: NotParse,
+) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
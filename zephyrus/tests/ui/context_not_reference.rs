@@ -0,0 +1,9 @@
+use zephyrus::prelude::*;
+
+#[command]
+#[description = "Says hello"]
+async fn hello(_ctx: SlashContext<'_, ()>) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
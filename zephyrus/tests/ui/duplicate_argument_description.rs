@@ -0,0 +1,12 @@
+use zephyrus::prelude::*;
+
+#[command]
+#[description = "Says hello"]
+async fn hello(
+    _ctx: &SlashContext<()>,
+    #[description = "The name"] #[description = "Again"] name: String,
+) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
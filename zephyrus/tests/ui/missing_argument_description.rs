@@ -0,0 +1,9 @@
+use zephyrus::prelude::*;
+
+#[command]
+#[description = "Says hello"]
+async fn hello(_ctx: &SlashContext<()>, name: String) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
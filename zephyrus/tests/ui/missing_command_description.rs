@@ -0,0 +1,8 @@
+use zephyrus::prelude::*;
+
+#[command]
+async fn hello(_ctx: &SlashContext<()>) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
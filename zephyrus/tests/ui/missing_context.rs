@@ -0,0 +1,9 @@
+use zephyrus::prelude::*;
+
+#[command]
+#[description = "Says hello"]
+async fn hello() -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
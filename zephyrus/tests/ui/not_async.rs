@@ -0,0 +1,9 @@
+use zephyrus::prelude::*;
+
+#[command]
+#[description = "Says hello"]
+fn hello(_ctx: &SlashContext<()>) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use zephyrus::prelude::*;
+
+#[command]
+#[description = "Says hello"]
+async fn hello(
+    _ctx: &SlashContext<()>,
+    #[description = "The greeting"] greeting: Option<String>,
+    #[description = "The name"] name: String,
+) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use zephyrus::prelude::*;
+
+#[command]
+#[description = "Links an account"]
+async fn link(
+    _ctx: &SlashContext<()>,
+    #[sensitive = "yes"] #[description = "The token"] token: String,
+) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}
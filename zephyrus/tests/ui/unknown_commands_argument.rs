@@ -0,0 +1,14 @@
+use zephyrus::prelude::*;
+
+struct Music;
+
+#[commands(dynamic_dispatch)]
+impl Music {
+    #[command]
+    #[description = "Shows the current volume"]
+    async fn volume(&self, _ctx: &SlashContext<'_, Music>) -> CommandResult {
+        unimplemented!()
+    }
+}
+
+fn main() {}
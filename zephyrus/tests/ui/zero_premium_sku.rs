@@ -0,0 +1,10 @@
+use zephyrus::prelude::*;
+
+#[command]
+#[description = "Says hello"]
+#[premium_required = 0]
+async fn hello(_ctx: &SlashContext<()>) -> CommandResult {
+    unimplemented!()
+}
+
+fn main() {}